@@ -15,7 +15,7 @@ fn main() {
     let proof = MerkleProof::parse(&raw_proof).unwrap();
     let result = proof.verify_proof().unwrap();
     println!(
-        "proof: transactions count = {}, hash count = {}, tree height = {},\nmerkle root = {:?}, hashes count = {}, flags={:?},\ncomputed merkle root = {}, position = {}",
+        "proof: transactions count = {}, hash count = {}, tree height = {},\nmerkle root = {:?}, hashes count = {}, flags={:?},\ncomputed merkle root = {}, positions = {:?}",
         proof.transactions_count,
         proof.hashes.len(),
         proof.compute_partial_tree_height(),
@@ -23,6 +23,10 @@ fn main() {
         proof.hashes.len(),
         proof.flag_bits,
         result.extracted_root,
-        result.transaction_position
+        result
+            .matched_transactions
+            .iter()
+            .map(|matched| matched.transaction_position)
+            .collect::<Vec<_>>()
     );
 }
@@ -30,6 +30,10 @@ pub enum Address {
     // output: OP_0 {sha256(redeem_script)}
     // witness: [redeem_script_sig ...] {redeem_script}
     P2WSHv0(H256),
+    // input: <>
+    // output: OP_1 {tweaked x-only pubkey}
+    // witness: {signature} (key path) or [script_sig ...] {script} {control_block} (script path)
+    P2TRv1(H256),
 }
 
 impl Address {
@@ -40,6 +44,7 @@ impl Address {
         const OP_CHECK_SIG: u8 = OpCode::OpCheckSig as u8;
         const OP_EQUAL: u8 = OpCode::OpEqual as u8;
         const OP_0: u8 = OpCode::Op0 as u8;
+        const OP_1: u8 = OpCode::Op1 as u8;
 
         match script.as_bytes() {
             &[OP_DUP, OP_HASH_160, HASH160_SIZE_HEX, ref addr @ .., OP_EQUAL_VERIFY, OP_CHECK_SIG]
@@ -56,6 +61,9 @@ impl Address {
             &[OP_0, HASH160_SIZE_HEX, ref addr @ ..] if addr.len() == HASH160_SIZE_HEX as usize => {
                 Ok(Self::P2WPKHv0(H160::from_slice(addr)))
             }
+            &[OP_1, HASH256_SIZE_HEX, ref addr @ ..] if addr.len() == HASH256_SIZE_HEX as usize => {
+                Ok(Self::P2TRv1(H256::from_slice(addr)))
+            }
             _ => Err(Error::InvalidBtcAddress),
         }
     }
@@ -94,6 +102,13 @@ impl Address {
                 script.append(script_hash);
                 script
             }
+            Self::P2TRv1(output_key) => {
+                let mut script = Script::new();
+                script.append(OpCode::Op1);
+                script.append(HASH256_SIZE_HEX);
+                script.append(output_key);
+                script
+            }
         }
     }
 
@@ -112,7 +127,7 @@ impl Address {
     pub fn is_zero(&self) -> bool {
         match self {
             Self::P2PKH(hash) | Self::P2SH(hash) | Self::P2WPKHv0(hash) => hash.is_zero(),
-            Self::P2WSHv0(hash) => hash.is_zero(),
+            Self::P2WSHv0(hash) | Self::P2TRv1(hash) => hash.is_zero(),
         }
     }
 }
@@ -194,6 +209,21 @@ pub mod global {
     }
 }
 
+/// Computes the taproot output key `Q = P + t·G` as defined in BIP341, where `P` is the
+/// point with even Y coordinate for the x-only `internal_key` and `t` is the `tweak`.
+/// Only the x-coordinate of `Q` is committed to in the P2TR script.
+pub(crate) fn taproot_output_key(internal_key: &[u8], tweak: &[u8; 32]) -> Result<H256, Secp256k1Error> {
+    let mut compressed = [0; PUBLIC_KEY_SIZE];
+    // lift_x: the x-only key always refers to the point with even Y coordinate
+    compressed[0] = 0x02;
+    compressed[1..].copy_from_slice(internal_key);
+
+    let mut public_key = Secp256k1PublicKey::from_slice(&compressed)?;
+    // Q = P + t·G
+    public_key.add_exp_assign(global::SECP256K1, tweak)?;
+    Ok(H256::from_slice(&public_key.serialize()[1..]))
+}
+
 /// To avoid the use of OP_RETURN during the issue process, we use an On-chain Key Derivation scheme (OKD) for
 /// Bitcoin’s ECDSA (secp256k1 curve). The vault-registry maintains a "master" public key for each registered
 /// Vault which can then be used to derive additional deposit addresses on-demand. Each new issue request triggers
@@ -315,6 +345,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_p2tr_script_pub_key() {
+        // bc1p2wsldez5mud2yam29q22wgfh9439spgduvct83k3pm50fcxa5dps59h4z5
+        let script =
+            Script::from(hex::decode("512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343").unwrap());
+        assert!(script.is_p2tr_v1());
+
+        let address = Address::from_script_pub_key(&script).unwrap();
+        assert_eq!(
+            address,
+            Address::P2TRv1(H256::from_slice(
+                &hex::decode("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343").unwrap()
+            ))
+        );
+        assert_eq!(address.to_script_pub_key(), script);
+    }
+
+    #[test]
+    fn test_unknown_witness_version_is_invalid() {
+        // OP_2 {32 bytes} is reserved for future segwit versions
+        let script =
+            Script::from(hex::decode("522053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343").unwrap());
+        assert_err!(Address::from_script_pub_key(&script), Error::InvalidBtcAddress);
+    }
+
     #[test]
     fn test_check_secret_key_constraints() {
         assert_err!(
@@ -65,6 +65,23 @@ impl SetCompact for U256 {
     }
 }
 
+pub trait GetBlockProof {
+    fn get_block_proof(self) -> U256;
+}
+
+// https://github.com/bitcoin/bitcoin/blob/7fcf53f7b4524572d1d0c9a5fdc388e87eb02416/src/chain.cpp
+impl GetBlockProof for U256 {
+    /// Returns the expected number of hashes required to find a block meeting this target,
+    /// i.e. 2**256 / (target + 1). We cannot represent 2**256 directly, but as 2**256 is at
+    /// least as large as target + 1, it is equal to ((2**256 - target - 1) / (target + 1)) + 1.
+    fn get_block_proof(self) -> U256 {
+        if self.is_zero() {
+            return U256::zero();
+        }
+        (!self / self.saturating_add(U256::one())).saturating_add(U256::one())
+    }
+}
+
 // https://github.com/bitcoin/bitcoin/blob/7fcf53f7b4524572d1d0c9a5fdc388e87eb02416/src/test/arith_uint256_tests.cpp
 #[cfg(test)]
 mod tests {
@@ -100,4 +117,19 @@ mod tests {
             assert_eq!(U256::set_compact(input).and_then(|num| num.get_compact()), output)
         }
     }
+
+    #[test]
+    fn block_proof_from_target() {
+        // difficulty 1, e.g. the bitcoin genesis block
+        let target = U256::set_compact(0x1d00ffff).unwrap();
+        assert_eq!(target.get_block_proof(), U256::from(0x100010001u64));
+
+        // higher targets require less work
+        let easy_target = U256::set_compact(0x207fffff).unwrap();
+        assert_eq!(easy_target.get_block_proof(), U256::from(2));
+        assert!(easy_target.get_block_proof() < target.get_block_proof());
+
+        assert_eq!(U256::zero().get_block_proof(), U256::zero());
+        assert_eq!(U256::max_value().get_block_proof(), U256::one());
+    }
 }
@@ -36,17 +36,40 @@ pub struct MerkleProof {
 struct MerkleProofTraversal {
     bits_used: usize,
     hashes_used: usize,
-    merkle_position: Option<u32>,
-    hash_position: Option<usize>,
+    matched_transactions: Vec<MatchedTransaction>,
 }
 
+/// A transaction that is matched by a partial merkle tree
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct ProofResult {
-    pub extracted_root: H256Le,
+pub struct MatchedTransaction {
     pub transaction_hash: H256Le,
     pub transaction_position: u32,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProofResult {
+    pub extracted_root: H256Le,
+    /// Transactions matched by the proof, ordered by their position in the block
+    pub matched_transactions: Vec<MatchedTransaction>,
+}
+
+impl ProofResult {
+    /// Returns true if the transaction with the given hash is matched by the proof
+    pub fn includes(&self, transaction_hash: H256Le) -> bool {
+        self.matched_transactions
+            .iter()
+            .any(|matched| matched.transaction_hash == transaction_hash)
+    }
+
+    /// Returns the hashes of all transactions matched by the proof
+    pub fn transaction_hashes(&self) -> Vec<H256Le> {
+        self.matched_transactions
+            .iter()
+            .map(|matched| matched.transaction_hash)
+            .collect()
+    }
+}
+
 impl MerkleTree {
     pub fn compute_width(transactions_count: u32, height: u32) -> u32 {
         (transactions_count + (1 << height) - 1) >> height
@@ -125,8 +148,10 @@ impl MerkleProof {
             }
             let hash = self.hashes[traversal.hashes_used];
             if height == 0 && parent_of_hash {
-                traversal.merkle_position = Some(pos);
-                traversal.hash_position = Some(traversal.hashes_used);
+                traversal.matched_transactions.push(MatchedTransaction {
+                    transaction_hash: hash,
+                    transaction_position: pos,
+                });
             }
             traversal.hashes_used = traversal.hashes_used.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
             return Ok(hash);
@@ -138,7 +163,13 @@ impl MerkleProof {
 
         let left = self.traverse_and_extract(next_height, left_index, traversal)?;
         let right = if right_index < self.compute_partial_tree_width(next_height) {
-            self.traverse_and_extract(next_height, right_index, traversal)?
+            let right = self.traverse_and_extract(next_height, right_index, traversal)?;
+            // the left and right branches cover distinct transactions so they should never
+            // be identical, otherwise the same transaction could be matched twice (CVE-2012-2459)
+            if right == left {
+                return Err(Error::MalformedMerkleProof);
+            }
+            right
         } else {
             left
         };
@@ -148,12 +179,12 @@ impl MerkleProof {
     }
 
     /// Computes the merkle root of the proof partial merkle tree
+    /// and extracts all matched transactions
     pub fn verify_proof(&self) -> Result<ProofResult, Error> {
         let mut traversal = MerkleProofTraversal {
             bits_used: 0,
             hashes_used: 0,
-            merkle_position: None,
-            hash_position: None,
+            matched_transactions: Vec::new(),
         };
 
         // fail if no transactions
@@ -172,8 +203,11 @@ impl MerkleProof {
         }
 
         let root = self.traverse_and_extract(self.compute_partial_tree_height(), 0, &mut traversal)?;
-        let merkle_position = traversal.merkle_position.ok_or(Error::InvalidMerkleProof)?;
-        let hash_position = traversal.hash_position.ok_or(Error::InvalidMerkleProof)?;
+
+        // fail if no transactions are matched
+        if traversal.matched_transactions.is_empty() {
+            return Err(Error::InvalidMerkleProof);
+        }
 
         // fail if all hashes are not used
         if traversal.hashes_used != self.hashes.len() {
@@ -200,8 +234,7 @@ impl MerkleProof {
 
         Ok(ProofResult {
             extracted_root: root,
-            transaction_hash: self.hashes[hash_position],
-            transaction_position: merkle_position,
+            matched_transactions: traversal.matched_transactions,
         })
     }
 
@@ -306,8 +339,10 @@ mod tests {
         );
         ProofResult {
             extracted_root: merkle_root,
-            transaction_hash: tx_id,
-            transaction_position: 0,
+            matched_transactions: vec![MatchedTransaction {
+                transaction_hash: tx_id,
+                transaction_position: 0,
+            }],
         }
     }
 
@@ -316,10 +351,11 @@ mod tests {
         let mock_proof_result = sample_valid_proof_result();
 
         let proof = MerkleProof::parse(&hex::decode(PROOF_HEX).unwrap()).unwrap();
-        MerkleProof::verify_proof.mock_safe(move |_| MockResult::Return(Ok(mock_proof_result)));
+        let expected_proof_result = mock_proof_result.clone();
+        MerkleProof::verify_proof.mock_safe(move |_| MockResult::Return(Ok(mock_proof_result.clone())));
 
         let res = MerkleProof::verify_proof(&proof).unwrap();
-        assert_eq!(res, mock_proof_result);
+        assert_eq!(res, expected_proof_result);
     }
 
     #[test]
@@ -367,9 +403,14 @@ mod tests {
         let merkle_root = H256Le::from_bytes_le(&proof.block_header.merkle_root.to_bytes_le());
         let result = proof.verify_proof().unwrap();
         assert_eq!(result.extracted_root, merkle_root);
-        assert_eq!(result.transaction_position, 48);
         let expected_tx_hash = H256Le::from_hex_be("61a05151711e4716f31f7a3bb956d1b030c4d92093b843fa2e771b95564f0704");
-        assert_eq!(result.transaction_hash, expected_tx_hash);
+        assert_eq!(
+            result.matched_transactions,
+            vec![MatchedTransaction {
+                transaction_hash: expected_tx_hash,
+                transaction_position: 48,
+            }]
+        );
     }
 
     #[test]
@@ -9,7 +9,7 @@ use sha2::{Digest, Sha256};
 #[cfg(test)]
 use mocktopus::macros::mockable;
 
-use crate::{Error, SetCompact};
+use crate::{address::taproot_output_key, formatter::Formattable, utils::sha256_tagged, Error, SetCompact};
 use sp_core::U256;
 use sp_std::{prelude::*, vec};
 
@@ -19,6 +19,17 @@ use crate::{address::Address, types::*};
 const SECP256K1_TAG_PUBKEY_EVEN: u8 = 0x02;
 const SECP256K1_TAG_PUBKEY_ODD: u8 = 0x03;
 
+// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#script-validation-rules
+const TAPROOT_ANNEX_TAG: u8 = 0x50;
+const TAPROOT_LEAF_MASK: u8 = 0xfe;
+const TAPROOT_LEAF_TAPSCRIPT: u8 = 0xc0;
+const TAPROOT_CONTROL_BASE_SIZE: usize = 33;
+const TAPROOT_CONTROL_NODE_SIZE: usize = 32;
+const TAPROOT_CONTROL_MAX_NODE_COUNT: usize = 128;
+
+// https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+const SCHNORR_SIGNATURE_SIZE: usize = 64;
+
 // https://github.com/bitcoin/bitcoin/blob/7fcf53f7b4524572d1d0c9a5fdc388e87eb02416/src/script/script.h#L39
 const LOCKTIME_THRESHOLD: u32 = 500_000_000;
 
@@ -436,7 +447,86 @@ fn parse_transaction_output(raw_output: &[u8]) -> Result<(TransactionOutput, usi
     ))
 }
 
-pub(crate) fn extract_address_hash_witness<B: AsRef<[u8]>>(witness_script: B) -> Result<Address, Error> {
+pub(crate) fn extract_address_witness(witness: &[Vec<u8>]) -> Result<Address, Error> {
+    // the spent output is not known here, so an input is only treated as a taproot spend
+    // if its witness has the annex or control block structure defined in BIP-341
+    match witness {
+        // key path spends only reveal the schnorr signature, the output key cannot be
+        // recovered from the input so we are unable to derive the address - without the
+        // annex a single element could just as well be a P2WSH script without arguments
+        [signature, annex] if is_taproot_annex(annex) && is_schnorr_signature(signature) => {
+            Err(Error::UnsupportedInputFormat)
+        }
+        [.., script, control_block, annex] if is_taproot_annex(annex) && is_taproot_control_block(control_block) => {
+            extract_address_taproot_script_path(script, control_block)
+        }
+        [.., script, control_block] if is_taproot_control_block(control_block) => {
+            extract_address_taproot_script_path(script, control_block)
+        }
+        // the last element in the witness slice is either the
+        // compressed public key (P2WPKH) or the redeem script (P2WSH)
+        [.., witness_script] => extract_address_hash_witness(witness_script),
+        [] => Err(Error::MalformedTransaction),
+    }
+}
+
+fn is_taproot_annex(annex: &[u8]) -> bool {
+    annex.first() == Some(&TAPROOT_ANNEX_TAG)
+}
+
+fn is_schnorr_signature(signature: &[u8]) -> bool {
+    // the optional trailing byte is the sighash type
+    signature.len() == SCHNORR_SIGNATURE_SIZE || signature.len() == SCHNORR_SIGNATURE_SIZE + 1
+}
+
+fn is_taproot_control_block(control_block: &[u8]) -> bool {
+    // only tapscript leaves are supported, other leaf versions are not yet defined and
+    // undefined opcodes (0xc0, 0xc1) cannot start a valid P2WSH witness script
+    control_block.len() >= TAPROOT_CONTROL_BASE_SIZE
+        && (control_block.len() - TAPROOT_CONTROL_BASE_SIZE) % TAPROOT_CONTROL_NODE_SIZE == 0
+        && (control_block.len() - TAPROOT_CONTROL_BASE_SIZE) / TAPROOT_CONTROL_NODE_SIZE
+            <= TAPROOT_CONTROL_MAX_NODE_COUNT
+        && control_block[0] & TAPROOT_LEAF_MASK == TAPROOT_LEAF_TAPSCRIPT
+}
+
+// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#script-validation-rules
+fn extract_address_taproot_script_path(script: &[u8], control_block: &[u8]) -> Result<Address, Error> {
+    let leaf_version = control_block[0] & TAPROOT_LEAF_MASK;
+    let internal_key = &control_block[1..TAPROOT_CONTROL_BASE_SIZE];
+
+    let mut leaf = vec![leaf_version];
+    leaf.extend(
+        CompactUint {
+            value: script.len() as u64,
+        }
+        .format(),
+    );
+    leaf.extend(script);
+    let mut node = sha256_tagged(b"TapLeaf", &leaf);
+
+    // the path is ordered from the leaf to the root, siblings are sorted lexicographically
+    for sibling in control_block[TAPROOT_CONTROL_BASE_SIZE..].chunks(TAPROOT_CONTROL_NODE_SIZE) {
+        let mut branch = Vec::with_capacity(2 * TAPROOT_CONTROL_NODE_SIZE);
+        if node[..] < sibling[..] {
+            branch.extend(&node);
+            branch.extend(sibling);
+        } else {
+            branch.extend(sibling);
+            branch.extend(&node);
+        }
+        node = sha256_tagged(b"TapBranch", &branch);
+    }
+
+    let mut tweak_input = Vec::with_capacity(2 * TAPROOT_CONTROL_NODE_SIZE);
+    tweak_input.extend(internal_key);
+    tweak_input.extend(&node);
+    let tweak = sha256_tagged(b"TapTweak", &tweak_input);
+
+    let output_key = taproot_output_key(internal_key, &tweak).map_err(|_| Error::MalformedTransaction)?;
+    Ok(Address::P2TRv1(output_key))
+}
+
+fn extract_address_hash_witness<B: AsRef<[u8]>>(witness_script: B) -> Result<Address, Error> {
     let witness_script = witness_script.as_ref();
     // first check if the witness is the compressed public key
     // https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#p2wpkh
@@ -457,7 +547,6 @@ pub(crate) fn extract_address_hash_witness<B: AsRef<[u8]>>(witness_script: B) ->
     // https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#p2wsh
     let mut hasher = Sha256::default();
     hasher.input(witness_script);
-    // WARNING: this will decode P2TR inputs incorrectly
     Ok(Address::P2WSHv0(H256::from_slice(&hasher.result()[..])))
 }
 
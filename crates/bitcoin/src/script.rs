@@ -51,6 +51,13 @@ impl Script {
             && self.bytes[1] == HASH256_SIZE_HEX
     }
 
+    pub fn is_p2tr_v1(&self) -> bool {
+        // first byte is version
+        self.len() == P2TR_V1_SCRIPT_SIZE as usize
+            && self.bytes[0] == OpCode::Op1 as u8
+            && self.bytes[1] == HASH256_SIZE_HEX
+    }
+
     pub fn is_p2pkh(&self) -> bool {
         self.len() == P2PKH_SCRIPT_SIZE as usize
             && self.bytes[0] == OpCode::OpDup as u8
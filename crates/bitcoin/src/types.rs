@@ -7,7 +7,7 @@ pub use crate::merkle::MerkleProof;
 use crate::{
     formatter::{Formattable, TryFormattable},
     merkle::MerkleTree,
-    parser::{extract_address_hash_scriptsig, extract_address_hash_witness},
+    parser::{extract_address_hash_scriptsig, extract_address_witness},
     utils::{log2, reverse_endianness, sha256d_le},
     Address, Error, PublicKey, Script,
 };
@@ -236,6 +236,7 @@ pub const P2PKH_SCRIPT_SIZE: u32 = 25;
 pub const P2SH_SCRIPT_SIZE: u32 = 23;
 pub const P2WPKH_V0_SCRIPT_SIZE: u32 = 22;
 pub const P2WSH_V0_SCRIPT_SIZE: u32 = 34;
+pub const P2TR_V1_SCRIPT_SIZE: u32 = 34;
 pub const HASH160_SIZE_HEX: u8 = 0x14;
 pub const HASH256_SIZE_HEX: u8 = 0x20;
 pub const MAX_OPRETURN_SIZE: usize = 83;
@@ -287,15 +288,7 @@ impl TransactionInput {
     }
 
     pub fn extract_address(&self) -> Result<Address, Error> {
-        extract_address_hash_scriptsig(&self.script).or_else(|_| {
-            // the last element in the witness slice is either the
-            // compressed public key (P2WPKH) or the redeem script (P2WSH)
-            if let Some(witness_script) = self.witness.last() {
-                extract_address_hash_witness(witness_script)
-            } else {
-                Err(Error::MalformedTransaction)
-            }
-        })
+        extract_address_hash_scriptsig(&self.script).or_else(|_| extract_address_witness(&self.witness))
     }
 }
 
@@ -792,7 +785,7 @@ mod tests {
     use super::*;
     use sp_std::str::FromStr;
 
-    use crate::{parser::parse_transaction, Address};
+    use crate::{merkle::MatchedTransaction, parser::parse_transaction, Address};
 
     fn sample_example_real_rawtx() -> String {
         "0200000000010140d43a99926d43eb0e619bf0b3d83b4a31f60c176beecfb9d35bf45e54d0f7420100000017160014a4b4ca48de0b3fffc15404a1acdc8dbaae226955ffffffff0100e1f5050000000017a9144a1154d50b03292b3024370901711946cb7cccc387024830450221008604ef8f6d8afa892dee0f31259b6ce02dd70c545cfcfed8148179971876c54a022076d771d6e91bed212783c9b06e0de600fab2d518fad6f15a2b191d7fbd262a3e0121039d25ab79f41f75ceaf882411fd41fa670a4c672c23ffaf0e361a969cde0692e800000000".to_owned()
@@ -1024,6 +1017,55 @@ mod tests {
         MerkleProof::parse(&bytes).unwrap();
     }
 
+    #[test]
+    fn test_merkle_proof_with_multiple_transactions() {
+        clear_mocks();
+        let address = Address::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+
+        let transactions: Vec<_> = (1..=4)
+            .map(|value| {
+                TransactionBuilder::new()
+                    .with_version(2)
+                    .add_input(TransactionInputBuilder::new().build())
+                    .add_output(TransactionOutput::payment(value, &address))
+                    .build()
+            })
+            .collect();
+
+        let mut builder = BlockBuilder::new();
+        builder
+            .with_version(4)
+            .with_coinbase(&address, 50, 3)
+            .with_timestamp(1588814835);
+        for transaction in transactions.iter() {
+            builder.add_transaction(transaction.clone());
+        }
+        let block = builder.mine(U256::from(2).pow(254.into())).unwrap();
+
+        let proof = block
+            .merkle_proof(&[transactions[3].tx_id(), transactions[1].tx_id()])
+            .unwrap();
+        let result = proof.verify_proof().unwrap();
+
+        assert_eq!(result.extracted_root, block.header.merkle_root);
+        // matches are ordered by their position in the block, the coinbase is at position 0
+        assert_eq!(
+            result.matched_transactions,
+            vec![
+                MatchedTransaction {
+                    transaction_hash: transactions[1].tx_id(),
+                    transaction_position: 2,
+                },
+                MatchedTransaction {
+                    transaction_hash: transactions[3].tx_id(),
+                    transaction_position: 4,
+                },
+            ]
+        );
+        assert!(result.includes(transactions[1].tx_id()));
+        assert!(!result.includes(transactions[0].tx_id()));
+    }
+
     #[test]
     fn extract_witness_address_native_p2wsh() {
         // source: https://blockstream.info/tx/babdc5ac2572569233b4e4720bcfc89f290db8eac6132427914c8272a5233278
@@ -1111,6 +1153,63 @@ mod tests {
         assert_eq!(&extr_address, &address);
     }
 
+    #[test]
+    fn extract_witness_address_p2tr_script_path() {
+        let transaction_input = TransactionInput {
+            source: TransactionInputSource::FromOutput(H256Le::zero(), 0),
+            script: vec![],
+            sequence: 0,
+            witness: vec![
+                vec![1; 64],
+                hex::decode("20742c9308369c7d3440a492917885c955744f6e156cd9924b0dc1da43429e09bbac").unwrap(),
+                hex::decode("c05bf08d58a430f8c222bffaf9127249c5cdff70a2d68b2b45637eb662b6b88eb57d10de8554ed5ca40f9d0f0e0f4375b5b338af3fb96d33c9b2f53b5289b8f4fe").unwrap(),
+            ],
+        };
+
+        let address = Address::P2TRv1(
+            H256::from_str(&"6a96ed7c4d3ca65e0f470749ed86b401d0715de1e21a131500c5c40e4c6a5f87").unwrap(),
+        );
+
+        assert_eq!(transaction_input.extract_address().unwrap(), address);
+
+        // the annex does not change the committed output key
+        let mut transaction_input = transaction_input;
+        transaction_input.witness.push(vec![0x50, 1, 2, 3]);
+        assert_eq!(transaction_input.extract_address().unwrap(), address);
+    }
+
+    #[test]
+    fn extract_witness_address_p2tr_key_path_unsupported() {
+        let transaction_input = TransactionInput {
+            source: TransactionInputSource::FromOutput(H256Le::zero(), 0),
+            script: vec![],
+            sequence: 0,
+            witness: vec![vec![1; 65], vec![0x50, 1, 2, 3]],
+        };
+
+        assert_err!(transaction_input.extract_address(), Error::UnsupportedInputFormat);
+    }
+
+    #[test]
+    fn extract_witness_address_p2wsh_with_only_script() {
+        use sha2::{Digest, Sha256};
+
+        // a single 64 byte element is not treated as a schnorr signature without the annex
+        let witness_script = vec![0x51; 64];
+        let transaction_input = TransactionInput {
+            source: TransactionInputSource::FromOutput(H256Le::zero(), 0),
+            script: vec![],
+            sequence: 0,
+            witness: vec![witness_script.clone()],
+        };
+
+        let mut hasher = Sha256::default();
+        hasher.input(&witness_script);
+        let address = Address::P2WSHv0(H256::from_slice(&hasher.result()[..]));
+
+        assert_eq!(transaction_input.extract_address().unwrap(), address);
+    }
+
     #[test]
     fn p2pk_not_allowed() {
         // source: https://blockstream.info/tx/f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16?expand
@@ -38,6 +38,25 @@ pub fn sha256d(bytes: &[u8]) -> [u8; 32] {
     ret
 }
 
+/// Computes the tagged SHA256 hash defined in BIP340, which is
+/// `SHA256(SHA256(tag) || SHA256(tag) || data)`
+///
+/// # Arguments
+/// * tag: domain separation tag (e.g. `TapLeaf`)
+/// * data: bytes to hash
+pub fn sha256_tagged(tag: &[u8], data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag);
+
+    let mut hasher = Sha256::default();
+    hasher.input(&tag_hash);
+    hasher.input(&tag_hash);
+    hasher.input(data);
+
+    let mut ret = [0; 32];
+    ret.copy_from_slice(&hasher.result()[..]);
+    ret
+}
+
 /// Concatenates and hashes two inputs for merkle proving.
 ///
 /// # Arguments
@@ -6,6 +6,7 @@ use bitcoin::{
         Block, BlockBuilder, RawBlockHeader, Transaction, TransactionBuilder, TransactionInputBuilder,
         TransactionInputSource, TransactionOutput,
     },
+    Script,
 };
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
 use frame_system::RawOrigin;
@@ -29,16 +30,8 @@ fn mine_genesis<T: Config>(account_id: T::AccountId, address: &BtcAddress, heigh
     block
 }
 
-fn mine_block_with_one_tx<T: Config>(
-    account_id: T::AccountId,
-    prev: Block,
-    address: &BtcAddress,
-    value: i32,
-    op_return: &[u8],
-) -> (Block, Transaction) {
-    let prev_block_hash = prev.header.hash;
-
-    let transaction = TransactionBuilder::new()
+fn build_transaction(prev: &Block, address: &BtcAddress, value: i32, op_return: &[u8]) -> Transaction {
+    TransactionBuilder::new()
         .with_version(2)
         .add_input(
             TransactionInputBuilder::new()
@@ -56,22 +49,43 @@ fn mine_block_with_one_tx<T: Config>(
         )
         .add_output(TransactionOutput::payment(value.into(), address))
         .add_output(TransactionOutput::op_return(0, op_return))
-        .build();
+        .build()
+}
 
-    let block = BlockBuilder::new()
-        .with_previous_hash(prev_block_hash)
+fn mine_block_with_txs<T: Config>(
+    account_id: T::AccountId,
+    prev: Block,
+    address: &BtcAddress,
+    transactions: &[Transaction],
+) -> Block {
+    let mut block_builder = BlockBuilder::new();
+    block_builder
+        .with_previous_hash(prev.header.hash)
         .with_version(4)
         .with_coinbase(address, 50, 3)
-        .with_timestamp(1588813835)
-        .add_transaction(transaction.clone())
-        .mine(U256::from(2).pow(254.into()))
-        .unwrap();
+        .with_timestamp(1588813835);
+    for transaction in transactions {
+        block_builder.add_transaction(transaction.clone());
+    }
+    let block = block_builder.mine(U256::from(2).pow(254.into())).unwrap();
 
     let raw_block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
     let block_header = BtcRelay::<T>::parse_raw_block_header(&raw_block_header).unwrap();
 
     BtcRelay::<T>::_store_block_header(&account_id, block_header).unwrap();
 
+    block
+}
+
+fn mine_block_with_one_tx<T: Config>(
+    account_id: T::AccountId,
+    prev: Block,
+    address: &BtcAddress,
+    value: i32,
+    op_return: &[u8],
+) -> (Block, Transaction) {
+    let transaction = build_transaction(&prev, address, value, op_return);
+    let block = mine_block_with_txs::<T>(account_id, prev, address, &[transaction.clone()]);
     (block, transaction)
 }
 
@@ -122,6 +136,49 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(origin), raw_block_header)
 
+    store_block_headers {
+        let n in 1 .. MAX_BLOCK_HEADERS_PER_BATCH;
+
+        let origin: T::AccountId = account("Origin", 0, 0);
+
+        let address = BtcAddress::P2PKH(H160::from([0; 20]));
+        let height = 0;
+
+        let init_block = BlockBuilder::new()
+            .with_version(4)
+            .with_coinbase(&address, 50, 3)
+            .with_timestamp(1588813835)
+            .mine(U256::from(2).pow(254.into())).unwrap();
+
+        let raw_block_header = RawBlockHeader::from_bytes(&init_block.header.try_format().unwrap())
+            .expect("could not serialize block header");
+        let block_header = BtcRelay::<T>::parse_raw_block_header(&raw_block_header).unwrap();
+
+        BtcRelay::<T>::_initialize(origin.clone(), block_header, height).unwrap();
+
+        let mut prev_block_hash = init_block.header.hash;
+        let mut raw_block_headers = Vec::new();
+        for i in 0..n {
+            let block = BlockBuilder::new()
+                .with_previous_hash(prev_block_hash)
+                .with_version(4)
+                .with_coinbase(&address, 50, 3)
+                .with_timestamp(1588814835 + i)
+                .mine(U256::from(2).pow(254.into())).unwrap();
+            prev_block_hash = block.header.hash;
+
+            raw_block_headers.push(
+                RawBlockHeader::from_bytes(&block.header.try_format().unwrap())
+                    .expect("could not serialize block header")
+            );
+        }
+        let raw_block_headers: BoundedVec<_, _> = raw_block_headers.try_into().unwrap();
+
+    }: _(RawOrigin::Signed(origin), raw_block_headers)
+    verify {
+        assert_eq!(BtcRelay::<T>::get_best_block(), prev_block_hash);
+    }
+
     verify_and_validate_transaction {
         let origin: T::AccountId = account("Origin", 0, 0);
 
@@ -162,6 +219,39 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(origin), tx_id, proof, Some(0))
 
+    verify_transactions_inclusion {
+        let n in 1 .. MAX_TRANSACTIONS_PER_BATCH;
+        // bytes of padding in the first transaction, leaving room for its inputs and outputs
+        let b in 0 .. MAX_RAW_TRANSACTION_SIZE - 1_000;
+
+        let origin: T::AccountId = account("Origin", 0, 0);
+
+        let address = BtcAddress::P2PKH(H160::from([0; 20]));
+
+        let height = 0;
+        let block = mine_genesis::<T>(origin.clone(), &address, height);
+
+        let op_return = H256::zero().as_bytes().to_vec();
+        let mut transactions: Vec<_> = (0..n).map(|value| build_transaction(&block, &address, value as i32, &op_return)).collect();
+        transactions[0].outputs.push(TransactionOutput {
+            value: 0,
+            script: Script::from(vec![0u8; b as usize]),
+        });
+        let block = mine_block_with_txs::<T>(origin.clone(), block, &address, &transactions);
+
+        let tx_ids: Vec<_> = transactions.iter().map(|transaction| transaction.tx_id()).collect();
+        let proof = block.merkle_proof(&tx_ids).unwrap().try_format().unwrap();
+        let raw_txs = transactions
+            .iter()
+            .map(|transaction| transaction.format_with(true).try_into().unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+
+        Security::<T>::set_active_block_number(100u32.into());
+
+    }: _(RawOrigin::Signed(origin), proof, raw_txs, Some(0))
+
     validate_transaction {
         let origin: T::AccountId = account("Origin", 0, 0);
 
@@ -176,6 +266,35 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(origin), raw_tx, value.into(), address, Some(H256::from_slice(&op_return)))
 
+    set_pruning_window {
+    }: _(RawOrigin::Root, DIFFICULTY_ADJUSTMENT_INTERVAL)
+
+    prune_block_headers {
+        let n in 0 .. MAX_PRUNED_BLOCK_HEADERS_PER_BLOCK;
+
+        // store n main chain headers below the pruning height
+        let best_block_height = n + DIFFICULTY_ADJUSTMENT_INTERVAL;
+        Chains::<T>::insert(0, MAIN_CHAIN_ID);
+        ChainsIndex::<T>::insert(MAIN_CHAIN_ID, BlockChain {
+            chain_id: MAIN_CHAIN_ID,
+            start_height: 0,
+            max_height: best_block_height,
+        });
+        for height in 0..n {
+            let block_hash = H256Le::from_bytes_le(H256::from_low_u64_le(height as u64 + 1).as_bytes());
+            ChainsHashes::<T>::insert(MAIN_CHAIN_ID, height, block_hash);
+            BlockHeaders::<T>::insert(block_hash, RichBlockHeader::<T::BlockNumber>::default());
+        }
+        BestBlockHeight::<T>::put(best_block_height);
+        PruningWindow::<T>::put(DIFFICULTY_ADJUSTMENT_INTERVAL);
+
+    }: {
+        assert_eq!(BtcRelay::<T>::prune_block_headers(n).unwrap().0, n);
+    }
+    verify {
+        assert_eq!(BtcRelay::<T>::get_block_chain_from_id(MAIN_CHAIN_ID).unwrap().start_height, n);
+    }
+
 }
 
 impl_benchmark_test_suite!(BtcRelay, crate::mock::ExtBuilder::build(), crate::mock::Test);
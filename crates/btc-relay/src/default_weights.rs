@@ -36,9 +36,13 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn initialize() -> Weight;
 	fn store_block_header() -> Weight;
+	fn store_block_headers(n: u32, ) -> Weight;
 	fn verify_and_validate_transaction() -> Weight;
 	fn verify_transaction_inclusion() -> Weight;
 	fn validate_transaction() -> Weight;
+	fn set_pruning_window() -> Weight;
+	fn prune_block_headers(n: u32, ) -> Weight;
+	fn verify_transactions_inclusion(n: u32, b: u32, ) -> Weight;
 }
 
 /// Weights for btc_relay using the Substrate node and recommended hardware.
@@ -73,6 +77,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(5 as u64))
 	}
 	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	// Storage: BTCRelay ChainsHashes (r:1 w:1)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: BTCRelay BestBlock (r:0 w:1)
+	// Storage: BTCRelay BestBlockHeight (r:0 w:1)
+	fn store_block_headers(n: u32, ) -> Weight {
+		Weight::from_ref_time(21_435_000 as u64)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_ref_time(46_871_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Security ParachainStatus (r:1 w:0)
 	// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
 	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
 	// Storage: BTCRelay Chains (r:1 w:0)
@@ -99,6 +120,40 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_ref_time(11_660_000 as u64)
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 	}
+	// Storage: BTCRelay PruningWindow (r:0 w:1)
+	fn set_pruning_window() -> Weight {
+		Weight::from_ref_time(9_231_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: BTCRelay PruningWindow (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: BTCRelay ChainsIndex (r:2 w:1)
+	// Storage: BTCRelay ChainsHashes (r:1 w:1)
+	// Storage: BTCRelay BlockHeaders (r:0 w:1)
+	fn prune_block_headers(n: u32, ) -> Weight {
+		Weight::from_ref_time(18_652_000 as u64)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_ref_time(9_517_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: BTCRelay Chains (r:1 w:0)
+	// Storage: BTCRelay BlockHeaders (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: BTCRelay StableParachainConfirmations (r:1 w:0)
+	fn verify_transactions_inclusion(n: u32, b: u32, ) -> Weight {
+		Weight::from_ref_time(37_208_000 as u64)
+			// Standard Error: 5_000
+			.saturating_add(Weight::from_ref_time(5_102_000 as u64).saturating_mul(n as u64))
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(4_000 as u64).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -132,6 +187,23 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(5 as u64))
 	}
 	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	// Storage: BTCRelay ChainsHashes (r:1 w:1)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: BTCRelay BestBlock (r:0 w:1)
+	// Storage: BTCRelay BestBlockHeight (r:0 w:1)
+	fn store_block_headers(n: u32, ) -> Weight {
+		Weight::from_ref_time(21_435_000 as u64)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_ref_time(46_871_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Security ParachainStatus (r:1 w:0)
 	// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
 	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
 	// Storage: BTCRelay Chains (r:1 w:0)
@@ -158,5 +230,39 @@ impl WeightInfo for () {
 		Weight::from_ref_time(11_660_000 as u64)
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 	}
+	// Storage: BTCRelay PruningWindow (r:0 w:1)
+	fn set_pruning_window() -> Weight {
+		Weight::from_ref_time(9_231_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: BTCRelay PruningWindow (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: BTCRelay ChainsIndex (r:2 w:1)
+	// Storage: BTCRelay ChainsHashes (r:1 w:1)
+	// Storage: BTCRelay BlockHeaders (r:0 w:1)
+	fn prune_block_headers(n: u32, ) -> Weight {
+		Weight::from_ref_time(18_652_000 as u64)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_ref_time(9_517_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: BTCRelay Chains (r:1 w:0)
+	// Storage: BTCRelay BlockHeaders (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: BTCRelay StableParachainConfirmations (r:1 w:0)
+	fn verify_transactions_inclusion(n: u32, b: u32, ) -> Weight {
+		Weight::from_ref_time(37_208_000 as u64)
+			// Standard Error: 5_000
+			.saturating_add(Weight::from_ref_time(5_102_000 as u64).saturating_mul(n as u64))
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(4_000 as u64).saturating_mul(b as u64))
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+	}
 }
 
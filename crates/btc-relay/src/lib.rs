@@ -31,6 +31,7 @@
 
 mod ext;
 
+pub mod migration;
 pub mod types;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -53,11 +54,13 @@ use mocktopus::macros::mockable;
 
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
-    ensure, runtime_print,
-    traits::Get,
+    ensure, runtime_print, storage,
+    traits::{ConstU32, Get},
     transactional,
+    weights::Weight,
+    BoundedVec,
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_root, ensure_signed};
 use sp_core::{H256, U256};
 use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, One};
 use sp_std::{
@@ -71,7 +74,7 @@ use bitcoin::{
     merkle::{MerkleProof, ProofResult},
     parser::{parse_block_header, parse_transaction},
     types::{BlockChain, BlockHeader, H256Le, RawBlockHeader, Transaction, Value},
-    Error as BitcoinError, SetCompact,
+    Error as BitcoinError, GetBlockProof, SetCompact,
 };
 pub use types::{OpReturnPaymentData, RichBlockHeader};
 
@@ -102,7 +105,15 @@ pub mod pallet {
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+        fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            // headers can only be pruned once they have been migrated
+            if migration::v1::is_migrating::<T>() {
+                return migration::v1::migrate_block_headers_with_weight_limit::<T>(remaining_weight);
+            }
+            Self::prune_block_headers_with_weight_limit(remaining_weight)
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -192,6 +203,47 @@ pub mod pallet {
             Ok(Pays::No.into())
         }
 
+        /// Stores a batch of contiguous block headers, where every header must
+        /// build on the previous one. The first header may extend any stored block.
+        ///
+        /// # Arguments
+        ///
+        /// * `raw_block_headers` - 80 byte raw Bitcoin block headers, ordered by height.
+        ///
+        /// # <weight>
+        /// Key: N (len of raw_block_headers), C (len of chains), P (len of positions)
+        /// - Same as `store_block_header` for each of the N headers.
+        ///
+        /// Total Complexity: O(N * (C + P))
+        /// # </weight>
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::store_block_headers(raw_block_headers.len() as u32))]
+        #[transactional]
+        pub fn store_block_headers(
+            origin: OriginFor<T>,
+            raw_block_headers: BoundedVec<RawBlockHeader, ConstU32<MAX_BLOCK_HEADERS_PER_BATCH>>,
+        ) -> DispatchResultWithPostInfo {
+            let relayer = ensure_signed(origin)?;
+
+            ensure!(!raw_block_headers.is_empty(), Error::<T>::InvalidBatchSize);
+
+            let mut prev_block_hash = None;
+            for raw_block_header in raw_block_headers.iter() {
+                let block_header = Self::parse_raw_block_header(raw_block_header)?;
+                if let Some(prev_block_hash) = prev_block_hash {
+                    ensure!(
+                        block_header.hash_prev_block == prev_block_hash,
+                        Error::<T>::NonContiguousBatch
+                    );
+                }
+                Self::_store_block_header(&relayer, block_header)?;
+                prev_block_hash = Some(block_header.hash);
+            }
+
+            // don't take tx fees on success
+            Ok(Pays::No.into())
+        }
+
         /// Verifies the inclusion of `tx_id` into the relay, and validates the given raw Bitcoin transaction, according
         /// to the supported transaction format (see <https://spec.interlay.io/intro/accepted-format.html>)
         ///
@@ -288,6 +340,69 @@ pub mod pallet {
             Self::_validate_transaction(transaction, expected_btc, recipient_btc_address, op_return_id)?;
             Ok(().into())
         }
+
+        /// Verifies the inclusion of multiple transactions in the same block using a single merkle proof
+        ///
+        /// # Arguments
+        ///
+        /// * `raw_merkle_proof` - The raw merkle proof as returned by bitcoin `gettxoutproof`, matching all
+        ///   transactions
+        /// * `raw_txs` - raw Bitcoin transactions to check for
+        /// * `confirmations` - The number of confirmations needed to accept the proof. If `none`, the value stored in
+        ///   the `StableBitcoinConfirmations` storage item is used.
+        ///
+        /// # <weight>
+        /// Key: N (len of raw_txs), B (total len of raw_merkle_proof and raw_txs)
+        /// - Every transaction is parsed and hashed, as is the merkle proof.
+        ///
+        /// Total Complexity: O(N + B)
+        /// # </weight>
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::verify_transactions_inclusion(
+            raw_txs.len() as u32,
+            raw_txs
+                .iter()
+                .fold(raw_merkle_proof.len() as u32, |len, raw_tx| len.saturating_add(raw_tx.len() as u32))
+        ))]
+        #[transactional]
+        pub fn verify_transactions_inclusion(
+            origin: OriginFor<T>,
+            raw_merkle_proof: Vec<u8>,
+            raw_txs: BoundedVec<
+                BoundedVec<u8, ConstU32<MAX_RAW_TRANSACTION_SIZE>>,
+                ConstU32<MAX_TRANSACTIONS_PER_BATCH>,
+            >,
+            confirmations: Option<u32>,
+        ) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+            ensure!(!raw_txs.is_empty(), Error::<T>::InvalidBatchSize);
+
+            let tx_ids = raw_txs
+                .iter()
+                .map(|raw_tx| Ok(Self::parse_transaction(raw_tx)?.tx_id()))
+                .collect::<Result<Vec<_>, DispatchError>>()?;
+            let merkle_proof = Self::parse_merkle_proof(&raw_merkle_proof)?;
+            Self::_verify_transactions_inclusion(&tx_ids, merkle_proof, confirmations)?;
+            Ok(().into())
+        }
+
+        /// Set the number of blocks below the best block for which block headers are retained.
+        /// Older headers are pruned when blocks have spare weight. Zero disables pruning.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the dispatch origin of this call (must be _Root_)
+        /// * `pruning_window` - number of blocks to retain, at least `DIFFICULTY_ADJUSTMENT_INTERVAL`
+        ///
+        /// # Weight: `O(1)`
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_pruning_window())]
+        #[transactional]
+        pub fn set_pruning_window(origin: OriginFor<T>, pruning_window: u32) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            Self::_set_pruning_window(pruning_window)?;
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -319,6 +434,9 @@ pub mod pallet {
             fork_height: u32,
             fork_id: u32,
         },
+        PruningWindowChange {
+            pruning_window: u32,
+        },
     }
 
     #[pallet::error]
@@ -421,6 +539,14 @@ pub mod pallet {
         InvalidOpReturnTransaction,
         /// Invalid compact value in header
         InvalidCompact,
+        /// Batch is empty or exceeds the maximum size
+        InvalidBatchSize,
+        /// Block headers in the batch do not form a contiguous chain
+        NonContiguousBatch,
+        /// Pruning window is shorter than a difficulty adjustment interval
+        InvalidPruningWindow,
+        /// Block headers are still being migrated to the current storage version
+        HeaderMigrationInProgress,
     }
 
     /// Store Bitcoin block headers
@@ -428,7 +554,7 @@ pub mod pallet {
     pub(super) type BlockHeaders<T: Config> =
         StorageMap<_, Blake2_128Concat, H256Le, RichBlockHeader<T::BlockNumber>, ValueQuery>;
 
-    /// Priority queue of BlockChain elements, ordered by the maximum height (descending).
+    /// Priority queue of BlockChain elements, ordered by the cumulative chainwork (descending).
     /// The first index into this mapping (0) is considered to be the longest chain. The value
     /// of the entry is the index into `ChainsIndex` to retrieve the `BlockChain`.
     #[pallet::storage]
@@ -480,6 +606,12 @@ pub mod pallet {
     #[pallet::getter(fn disable_inclusion_check)]
     pub(super) type DisableInclusionCheck<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    /// Number of blocks below the best block for which block headers are retained,
+    /// older headers are pruned. Zero disables pruning.
+    #[pallet::storage]
+    #[pallet::getter(fn pruning_window)]
+    pub(super) type PruningWindow<T: Config> = StorageValue<_, u32, ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         /// Global security parameter k for stable Bitcoin transactions
@@ -526,6 +658,15 @@ pub const TARGET_SPACING: u32 = 10 * 60;
 /// See: <https://spec.interlay.io/intro/accepted-format.html#accepted-bitcoin-transaction-format>
 pub const ACCEPTED_MAX_TRANSACTION_OUTPUTS: usize = 3;
 
+/// Maximum number of block headers that can be submitted in a single batch
+pub const MAX_BLOCK_HEADERS_PER_BATCH: u32 = 100;
+
+/// Maximum number of transactions that can be verified against a single merkle proof
+pub const MAX_TRANSACTIONS_PER_BATCH: u32 = 100;
+
+/// Maximum size of a raw transaction in a batch, the standardness limit of Bitcoin Core
+pub const MAX_RAW_TRANSACTION_SIZE: u32 = 100_000;
+
 /// Unrounded Maximum Target
 /// 0x00000000FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF
 pub const UNROUNDED_MAX_TARGET: U256 = U256([
@@ -538,6 +679,9 @@ pub const UNROUNDED_MAX_TARGET: U256 = U256([
 /// Main chain id
 pub const MAIN_CHAIN_ID: u32 = 0;
 
+/// Maximum number of block headers that are pruned in a single block
+pub const MAX_PRUNED_BLOCK_HEADERS_PER_BLOCK: u32 = 1_000;
+
 #[cfg_attr(test, mockable)]
 impl<T: Config> Pallet<T> {
     pub fn _initialize(relayer: T::AccountId, basic_block_header: BlockHeader, block_height: u32) -> DispatchResult {
@@ -550,8 +694,12 @@ impl<T: Config> Pallet<T> {
             Error::<T>::InvalidStartHeight
         );
 
-        // construct the BlockChain struct
-        Self::create_and_store_blockchain(block_height, &basic_block_header)?;
+        // construct the BlockChain struct, we only count chainwork from the initial block
+        Self::create_and_store_blockchain(
+            block_height,
+            &basic_block_header,
+            basic_block_header.target.get_block_proof(),
+        )?;
 
         // Set BestBlock and BestBlockHeight to the submitted block
         Self::update_chain_head(&basic_block_header, block_height);
@@ -569,6 +717,11 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn _store_block_header(relayer: &T::AccountId, basic_block_header: BlockHeader) -> DispatchResult {
+        ensure!(
+            !migration::v1::is_migrating::<T>(),
+            Error::<T>::HeaderMigrationInProgress
+        );
+
         let prev_header = Self::get_block_header_from_hash(basic_block_header.hash_prev_block)?;
 
         // check if the prev block is the highest block in the chain
@@ -585,6 +738,12 @@ impl<T: Config> Pallet<T> {
         // ensure the block header is valid
         Self::verify_block_header(&basic_block_header, current_block_height, prev_header)?;
 
+        // accumulate the proof-of-work of this block on top of its parent
+        let chain_work = prev_header
+            .chain_work
+            .checked_add(basic_block_header.target.get_block_proof())
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+
         // Update the blockchain
         // check if we create a new blockchain or extend the existing one
         runtime_print!("Prev max height: {:?}", prev_blockchain.max_height);
@@ -594,10 +753,16 @@ impl<T: Config> Pallet<T> {
 
         let chain_id = if is_new_fork {
             // create new blockchain element
-            Self::create_and_store_blockchain(current_block_height, &basic_block_header)?
+            let chain_id = Self::create_and_store_blockchain(current_block_height, &basic_block_header, chain_work)?;
+
+            // a single block at a higher difficulty may already put the new fork ahead
+            let blockchain = Self::get_block_chain_from_id(chain_id)?;
+            Self::reorganize_chains(&blockchain)?;
+            chain_id
         } else {
             // extend the current chain
-            let blockchain = Self::extend_blockchain(current_block_height, &basic_block_header, prev_blockchain)?;
+            let blockchain =
+                Self::extend_blockchain(current_block_height, &basic_block_header, chain_work, prev_blockchain)?;
 
             // Update the pointer to BlockChain in ChainsIndex
             // todo: remove - this is already done in extend_blockchain
@@ -706,10 +871,69 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// interface to redeem; verifies inclusion and returns the amount paid to the recipient, which
+    /// may differ from the requested amount
+    ///
+    /// # Arguments
+    ///
+    /// * `merkle_proof` - the merkle proof of the transaction
+    /// * `transaction` - the transaction paying out the request
+    /// * `recipient_btc_address` - the address that is expected to be paid
+    /// * `op_return_id` - the id of the request, which must be committed to in the OP_RETURN
+    pub fn get_and_verify_op_return_payment<V: TryFrom<Value>>(
+        merkle_proof: MerkleProof,
+        transaction: Transaction,
+        recipient_btc_address: BtcAddress,
+        op_return_id: H256,
+    ) -> Result<V, DispatchError> {
+        // Verify that the transaction is indeed included in the main chain
+        Self::_verify_transaction_inclusion(transaction.tx_id(), merkle_proof, None)?;
+
+        let payment_data = OpReturnPaymentData::<T>::try_from(transaction)?;
+        let payment_value = payment_data
+            .get_payment_to(recipient_btc_address, Some(op_return_id))?
+            .try_into()
+            .map_err(|_| Error::<T>::InvalidPaymentAmount)?;
+
+        Ok(payment_value)
+    }
+
+    /// interface to redeem to check that a payment settling multiple requests is included and is valid
+    ///
+    /// # Arguments
+    ///
+    /// * `merkle_proof` - the merkle proof of the transaction
+    /// * `transaction` - the transaction paying out all requests
+    /// * `expected_payments` - the expected amount and recipient of every request
+    /// * `op_return_ids` - the ids of all requests, in the order in which they are committed to in the OP_RETURN
+    pub fn verify_and_validate_op_return_batch_transaction<V: TryInto<Value>>(
+        merkle_proof: MerkleProof,
+        transaction: Transaction,
+        expected_payments: Vec<(V, BtcAddress)>,
+        op_return_ids: &[H256],
+    ) -> Result<(), DispatchError> {
+        // Verify that the transaction is indeed included in the main chain
+        Self::_verify_transaction_inclusion(transaction.tx_id(), merkle_proof, None)?;
+
+        // Parse transaction and check that it matches the given parameters
+        Self::validate_op_return_batch_transaction(transaction, expected_payments, op_return_ids)?;
+        Ok(())
+    }
+
     pub fn _verify_transaction_inclusion(
         tx_id: H256Le,
         merkle_proof: MerkleProof,
         confirmations: Option<u32>,
+    ) -> Result<(), DispatchError> {
+        Self::_verify_transactions_inclusion(&[tx_id], merkle_proof, confirmations)
+    }
+
+    /// Verifies that all given transactions are included in the same main chain block,
+    /// such that the block header only needs to be checked once
+    pub fn _verify_transactions_inclusion(
+        tx_ids: &[H256Le],
+        merkle_proof: MerkleProof,
+        confirmations: Option<u32>,
     ) -> Result<(), DispatchError> {
         if Self::disable_inclusion_check() {
             return Ok(());
@@ -719,8 +943,11 @@ impl<T: Config> Pallet<T> {
         let block_hash = merkle_proof.block_header.hash;
         let stored_block_header = Self::verify_block_header_inclusion(block_hash, confirmations)?;
 
-        // fail if the transaction hash is invalid
-        ensure!(proof_result.transaction_hash == tx_id, Error::<T>::InvalidTxid);
+        // fail if any of the transaction hashes is not matched by the proof
+        ensure!(
+            tx_ids.iter().all(|tx_id| proof_result.includes(*tx_id)),
+            Error::<T>::InvalidTxid
+        );
 
         // fail if the merkle root is invalid
         ensure!(
@@ -735,8 +962,13 @@ impl<T: Config> Pallet<T> {
         block_hash: H256Le,
         confirmations: Option<u32>,
     ) -> Result<BlockHeader, DispatchError> {
+        ensure!(
+            !migration::v1::is_migrating::<T>(),
+            Error::<T>::HeaderMigrationInProgress
+        );
+
         let best_block_height = Self::get_best_block_height();
-        Self::ensure_no_ongoing_fork(best_block_height)?;
+        Self::ensure_no_ongoing_fork()?;
 
         let rich_header = Self::get_block_header_from_hash(block_hash)?;
 
@@ -768,6 +1000,27 @@ impl<T: Config> Pallet<T> {
         )
     }
 
+    /// Checks if a transaction settling multiple requests is valid. Returns the return-to-self address, if any
+    fn validate_op_return_batch_transaction<V: TryInto<i64>>(
+        transaction: Transaction,
+        expected_payments: Vec<(V, BtcAddress)>,
+        op_return_ids: &[H256],
+    ) -> Result<Option<BtcAddress>, DispatchError> {
+        let expected_payments = expected_payments
+            .into_iter()
+            .map(|(amount, address)| {
+                let amount = amount.try_into().map_err(|_| Error::<T>::InvalidPaymentAmount)?;
+                Ok((amount, address))
+            })
+            .collect::<Result<Vec<_>, DispatchError>>()?;
+
+        let payment_data = OpReturnPaymentData::<T>::try_from_batch(transaction, expected_payments.len())?;
+        payment_data.ensure_valid_batch_payment_to(
+            &expected_payments,
+            OpReturnPaymentData::<T>::batch_op_return(op_return_ids),
+        )
+    }
+
     pub fn is_fully_initialized() -> Result<bool, DispatchError> {
         if !StartBlockHeight::<T>::exists() {
             return Ok(false);
@@ -890,6 +1143,16 @@ impl<T: Config> Pallet<T> {
         Self::get_block_header_from_hash(block_hash)
     }
 
+    /// Get the highest block header of the given blockchain
+    fn get_chain_tip(blockchain: &BlockChain) -> Result<RichBlockHeader<T::BlockNumber>, DispatchError> {
+        Self::get_block_header_from_height(blockchain, blockchain.max_height)
+    }
+
+    /// Get the cumulative chainwork of the given blockchain
+    fn get_chain_work(blockchain: &BlockChain) -> Result<U256, DispatchError> {
+        Ok(Self::get_chain_tip(blockchain)?.chain_work)
+    }
+
     /// Storage setter functions
     /// Set a new chain with position and id
     fn set_chain_from_position_and_id(position: u32, id: u32) {
@@ -937,7 +1200,11 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Create a new blockchain element with a new chain id
-    fn create_and_store_blockchain(block_height: u32, basic_block_header: &BlockHeader) -> Result<u32, DispatchError> {
+    fn create_and_store_blockchain(
+        block_height: u32,
+        basic_block_header: &BlockHeader,
+        chain_work: U256,
+    ) -> Result<u32, DispatchError> {
         // get a new chain id
         let chain_id = Self::increment_chain_counter()?;
 
@@ -947,11 +1214,17 @@ impl<T: Config> Pallet<T> {
         // Store a pointer to BlockChain in ChainsIndex
         Self::set_block_chain_from_id(blockchain.chain_id, &blockchain);
 
+        // Store the header first, since its chainwork determines the position in Chains
+        Self::store_rich_header(
+            basic_block_header.clone(),
+            block_height,
+            blockchain.chain_id,
+            chain_work,
+        );
+
         // Store the reference to the blockchain in Chains
         Self::insert_sorted(&blockchain)?;
 
-        Self::store_rich_header(basic_block_header.clone(), block_height, blockchain.chain_id);
-
         Ok(blockchain.chain_id)
     }
 
@@ -980,6 +1253,7 @@ impl<T: Config> Pallet<T> {
     fn extend_blockchain(
         block_height: u32,
         basic_block_header: &BlockHeader,
+        chain_work: U256,
         prev_blockchain: BlockChain,
     ) -> Result<BlockChain, DispatchError> {
         let mut blockchain = prev_blockchain;
@@ -992,7 +1266,12 @@ impl<T: Config> Pallet<T> {
         blockchain.max_height = block_height;
         Self::set_block_chain_from_id(blockchain.chain_id, &blockchain);
 
-        Self::store_rich_header(basic_block_header.clone(), block_height, blockchain.chain_id);
+        Self::store_rich_header(
+            basic_block_header.clone(),
+            block_height,
+            blockchain.chain_id,
+            chain_work,
+        );
 
         Ok(blockchain)
     }
@@ -1164,7 +1443,7 @@ impl<T: Config> Pallet<T> {
             },
         );
 
-        // we swapped main chain and `fork`, so it will need to be resorted. The new max_height of this fork
+        // we swapped main chain and `fork`, so it will need to be resorted. The new chainwork of this fork
         // is strictly smaller than before, so do a single bubble sort pass to the right
         let start = Self::get_chain_position_from_chain_id(fork.chain_id)?;
         // ideally we'd iterate over start..Chains::<T>::len(), but unfortunately Chains does not implement
@@ -1176,9 +1455,11 @@ impl<T: Config> Pallet<T> {
                 break;
             }
 
-            let height1 = Self::get_block_chain_from_id(Self::get_chain_id_from_position(i)?)?.max_height;
-            let height2 = Self::get_block_chain_from_id(Self::get_chain_id_from_position(i + 1)?)?.max_height;
-            if height1 < height2 {
+            let work1 = Self::get_chain_work(&Self::get_block_chain_from_id(Self::get_chain_id_from_position(i)?)?)?;
+            let work2 = Self::get_chain_work(&Self::get_block_chain_from_id(Self::get_chain_id_from_position(
+                i + 1,
+            )?)?)?;
+            if work1 < work2 {
                 Self::swap_chain(i, i + 1);
             } else {
                 break;
@@ -1241,8 +1522,8 @@ impl<T: Config> Pallet<T> {
         })
     }
     /// Checks if a newly inserted fork results in an update to the sorted
-    /// Chains mapping. This happens when the max height of the fork is greater
-    /// than the max height of the previous element in the Chains mapping.
+    /// Chains mapping. This happens when the chainwork of the fork is greater
+    /// than the chainwork of the previous element in the Chains mapping.
     ///
     /// # Arguments
     ///
@@ -1250,11 +1531,11 @@ impl<T: Config> Pallet<T> {
     fn reorganize_chains(fork: &BlockChain) -> Result<(), DispatchError> {
         // get the position of the fork in Chains
         let fork_position: u32 = Self::get_chain_position_from_chain_id(fork.chain_id)?;
-        // check if the previous element in Chains has a lower block_height
+        let fork_work = Self::get_chain_work(fork)?;
+        // check if the previous element in Chains has less chainwork
         let mut current_position = fork_position;
-        let mut current_height = fork.max_height;
 
-        // swap elements as long as previous block height is smaller
+        // swap elements as long as previous chainwork is smaller
         while current_position > 0 {
             // get the previous position
             let prev_position = current_position - 1;
@@ -1267,17 +1548,17 @@ impl<T: Config> Pallet<T> {
                 continue;
             };
 
-            // get the previous blockchain height
-            let prev_height = Self::get_block_chain_from_id(prev_blockchain_id)?.max_height;
-            // swap elements if block height is greater
-            if prev_height < current_height {
+            // get the tip of the previous blockchain
+            let prev_blockchain = Self::get_block_chain_from_id(prev_blockchain_id)?;
+            let prev_tip = Self::get_chain_tip(&prev_blockchain)?;
+            // swap elements if chainwork is greater
+            if prev_tip.chain_work < fork_work {
                 // Check if swap occurs on the main chain element
                 if prev_blockchain_id == MAIN_CHAIN_ID {
-                    // if the previous position is the top element
-                    // and the current height is more than the
-                    // STABLE_TRANSACTION_CONFIRMATIONS ahead
-                    // we are swapping the main chain
-                    if prev_height + Self::get_stable_transaction_confirmations() <= current_height {
+                    // if the previous position is the top element and the fork has
+                    // accumulated at least STABLE_TRANSACTION_CONFIRMATIONS blocks
+                    // worth of work more than the main chain, we are swapping the main chain
+                    if Self::has_sufficient_work_lead(&prev_tip, fork_work)? {
                         // Swap the mainchain. As an optimization, this function returns the
                         // new best block hash and its height
                         let (new_chain_tip_hash, new_chain_tip_height) = Self::swap_main_blockchain(&fork)?;
@@ -1291,7 +1572,7 @@ impl<T: Config> Pallet<T> {
                         });
                     } else {
                         Self::deposit_event(Event::<T>::ForkAheadOfMainChain {
-                            main_chain_height: prev_height,
+                            main_chain_height: prev_blockchain.max_height,
                             fork_height: fork.max_height,
                             fork_id: fork.chain_id,
                         });
@@ -1303,9 +1584,8 @@ impl<T: Config> Pallet<T> {
                     Self::swap_chain(prev_position, current_position);
                 }
 
-                // update the current chain to the previous one
+                // continue comparing the fork with the next higher element
                 current_position = prev_position;
-                current_height = prev_height;
             } else {
                 break;
             }
@@ -1314,7 +1594,29 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    /// Insert a new fork into the Chains mapping sorted by its max height
+    /// Checks whether a fork with the given chainwork is far enough ahead of the main chain
+    /// to replace it, i.e. whether it leads by at least STABLE_TRANSACTION_CONFIRMATIONS
+    /// blocks at the difficulty of the current main chain tip.
+    ///
+    /// # Arguments
+    ///
+    /// * `main_chain_tip` - the highest block of the main chain
+    /// * `fork_work` - the cumulative chainwork of the fork
+    fn has_sufficient_work_lead(
+        main_chain_tip: &RichBlockHeader<T::BlockNumber>,
+        fork_work: U256,
+    ) -> Result<bool, DispatchError> {
+        let required_work = main_chain_tip
+            .block_header
+            .target
+            .get_block_proof()
+            .checked_mul(Self::get_stable_transaction_confirmations().into())
+            .and_then(|lead| lead.checked_add(main_chain_tip.chain_work))
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+        Ok(fork_work >= required_work)
+    }
+
+    /// Insert a new fork into the Chains mapping sorted by its chainwork
     ///
     /// # Arguments
     ///
@@ -1331,16 +1633,17 @@ impl<T: Config> Pallet<T> {
         let mut position_blockchain = max_chain_element;
 
         // Starting from the second highest element, find where to insert the new fork
-        // the previous element's block height should be higher or equal
-        // the next element's block height should be lower or equal
+        // the previous element's chainwork should be higher or equal
+        // the next element's chainwork should be lower or equal
         // NOTE: we never want to insert a new main chain through this function
+        let blockchain_work = Self::get_chain_work(blockchain)?;
         for (curr_position, curr_chain_id) in chains.iter().skip(1) {
-            // get the height of the current chain_id
-            let curr_height = Self::get_block_chain_from_id(*curr_chain_id)?.max_height;
+            // get the chainwork of the current chain_id
+            let curr_work = Self::get_chain_work(&Self::get_block_chain_from_id(*curr_chain_id)?)?;
 
-            // if the height of the current blockchain is lower than
+            // if the chainwork of the current blockchain is lower than
             // the new blockchain, it should be inserted at that position
-            if curr_height <= blockchain.max_height {
+            if curr_work <= blockchain_work {
                 position_blockchain = *curr_position;
                 break;
             };
@@ -1410,18 +1713,185 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn ensure_no_ongoing_fork(best_block_height: u32) -> Result<(), DispatchError> {
+    pub fn _set_pruning_window(pruning_window: u32) -> DispatchResult {
+        ensure!(
+            pruning_window == 0 || pruning_window >= DIFFICULTY_ADJUSTMENT_INTERVAL,
+            Error::<T>::InvalidPruningWindow
+        );
+        PruningWindow::<T>::set(pruning_window);
+        Self::deposit_event(Event::<T>::PruningWindowChange { pruning_window });
+        Ok(())
+    }
+
+    /// Returns the height below which block headers are pruned, or None if pruning is disabled
+    fn get_pruning_height() -> Option<u32> {
+        Self::get_pruning_height_for_window(Self::pruning_window())
+    }
+
+    /// Returns the height below which block headers are pruned with the given pruning window,
+    /// or None if the window is zero
+    pub(crate) fn get_pruning_height_for_window(pruning_window: u32) -> Option<u32> {
+        match pruning_window {
+            0 => None,
+            // never prune the headers needed to compute the next difficulty target
+            pruning_window => {
+                Some(Self::get_best_block_height().saturating_sub(pruning_window.max(DIFFICULTY_ADJUSTMENT_INTERVAL)))
+            }
+        }
+    }
+
+    /// Prunes as many block headers as fit into the given weight
+    ///
+    /// # Arguments
+    ///
+    /// * `remaining_weight` - the weight that is available for pruning
+    ///
+    /// # Returns
+    ///
+    /// The weight that was consumed
+    fn prune_block_headers_with_weight_limit(remaining_weight: Weight) -> Weight {
+        let base_weight = <T as Config>::WeightInfo::prune_block_headers(0);
+        // both the pruned headers and the visited chains are bounded by `max_headers`
+        let weight_per_header = <T as Config>::WeightInfo::prune_block_headers(1)
+            .saturating_sub(base_weight)
+            .saturating_add(T::DbWeight::get().reads(1));
+        if remaining_weight.ref_time() < base_weight.ref_time().saturating_add(weight_per_header.ref_time()) {
+            return Weight::from_ref_time(0);
+        }
+
+        let max_headers =
+            remaining_weight.ref_time().saturating_sub(base_weight.ref_time()) / weight_per_header.ref_time().max(1);
+        let max_headers = max_headers.min(MAX_PRUNED_BLOCK_HEADERS_PER_BLOCK as u64) as u32;
+
+        // on_idle is not transactional, discard partial changes on failure
+        match storage::with_storage_layer(|| Self::prune_block_headers(max_headers)) {
+            Ok((num_pruned, num_chains)) => Self::prune_block_headers_weight(num_pruned, num_chains),
+            Err(e) => {
+                runtime_print!("Failed to prune block headers: {:?}", e);
+                Self::prune_block_headers_weight(max_headers, max_headers)
+            }
+        }
+    }
+
+    /// Returns the weight of pruning `num_pruned` block headers while visiting `num_chains` chains
+    pub(crate) fn prune_block_headers_weight(num_pruned: u32, num_chains: u32) -> Weight {
+        <T as Config>::WeightInfo::prune_block_headers(num_pruned)
+            .saturating_add(T::DbWeight::get().reads(num_chains as u64))
+    }
+
+    /// Removes up to `max_headers` block headers that are more than the pruning window below
+    /// the best block, lowest first. Chains that no longer contain any blocks are removed.
+    /// Note that forks which branched off below the pruning height can no longer become the
+    /// main chain, since their connection to the main chain has been pruned.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_headers` - the maximum number of headers to prune and chains to visit
+    ///
+    /// # Returns
+    ///
+    /// The number of pruned block headers and the number of visited chains
+    pub fn prune_block_headers(max_headers: u32) -> Result<(u32, u32), DispatchError> {
+        match Self::get_pruning_height() {
+            Some(pruning_height) => Self::prune_block_headers_below(pruning_height, max_headers),
+            None => Ok((0, 0)),
+        }
+    }
+
+    /// Removes up to `max_headers` block headers below the given height, lowest first.
+    /// Chains that no longer contain any blocks are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pruning_height` - the height below which block headers are removed
+    /// * `max_headers` - the maximum number of headers to prune and chains to visit
+    ///
+    /// # Returns
+    ///
+    /// The number of pruned block headers and the number of visited chains
+    pub(crate) fn prune_block_headers_below(
+        pruning_height: u32,
+        max_headers: u32,
+    ) -> Result<(u32, u32), DispatchError> {
+        // only read as many chains as needed to prune `max_headers`
+        let mut num_chains = 0;
+        let mut num_prunable = 0;
+        let mut chains = Vec::new();
+        for chain in ChainsIndex::<T>::iter_values() {
+            if num_chains >= max_headers || num_prunable >= max_headers {
+                break;
+            }
+            num_chains += 1;
+            if chain.start_height < pruning_height {
+                num_prunable += pruning_height
+                    .min(chain.max_height.saturating_add(1))
+                    .saturating_sub(chain.start_height);
+                chains.push(chain);
+            }
+        }
+
+        let mut num_pruned = 0;
+        for mut chain in chains {
+            if num_pruned >= max_headers {
+                break;
+            }
+
+            while chain.start_height < pruning_height && chain.start_height <= chain.max_height {
+                if num_pruned >= max_headers {
+                    break;
+                }
+                if let Ok(block_hash) = ChainsHashes::<T>::try_get(chain.chain_id, chain.start_height) {
+                    ChainsHashes::<T>::remove(chain.chain_id, chain.start_height);
+                    BlockHeaders::<T>::remove(block_hash);
+                    num_pruned += 1;
+                }
+                chain.start_height = chain
+                    .start_height
+                    .checked_add(1)
+                    .ok_or(Error::<T>::ArithmeticOverflow)?;
+            }
+
+            if chain.start_height > chain.max_height {
+                // the main chain always extends beyond the pruning height, so this is a dead fork
+                Self::remove_blockchain(chain.chain_id)?;
+            } else {
+                Self::set_block_chain_from_id(chain.chain_id, &chain);
+            }
+        }
+
+        Ok((num_pruned, num_chains))
+    }
+
+    /// Removes an empty blockchain from ChainsIndex and Chains
+    fn remove_blockchain(chain_id: u32) -> DispatchResult {
+        let position = Self::get_chain_position_from_chain_id(chain_id)?;
+        ChainsIndex::<T>::remove(chain_id);
+
+        // move all subsequent chains up by one, the keys in Chains must be consecutive
+        let mut current_position = position;
+        while let Some(next_chain_id) = Chains::<T>::get(current_position + 1) {
+            Self::set_chain_from_position_and_id(current_position, next_chain_id);
+            current_position += 1;
+        }
+        Chains::<T>::remove(current_position);
+
+        Ok(())
+    }
+
+    fn ensure_no_ongoing_fork() -> Result<(), DispatchError> {
         // check if there is a next best fork
         match Self::get_chain_id_from_position(1) {
-            // if yes, check that the main chain is at least Self::confirmations() ahead
+            // if yes, check that the main chain leads it by at least Self::confirmations() blocks worth of work
             Ok(id) => {
-                let next_best_fork_height = Self::get_block_chain_from_id(id)?.max_height;
+                let main_chain_work = Self::get_chain_work(&Self::get_block_chain_from_id(MAIN_CHAIN_ID)?)?;
+                let next_best_fork_tip = Self::get_chain_tip(&Self::get_block_chain_from_id(id)?)?;
 
-                runtime_print!("Best block height: {}", best_block_height);
-                runtime_print!("Next best fork height: {}", next_best_fork_height);
-                // fail if there is an ongoing fork
+                runtime_print!("Main chain work: {:?}", main_chain_work);
+                runtime_print!("Next best fork work: {:?}", next_best_fork_tip.chain_work);
+                // fail if there is an ongoing fork, i.e. if the main chain would not be far enough ahead to
+                // replace the fork if their roles were swapped
                 ensure!(
-                    best_block_height >= next_best_fork_height + Self::get_stable_transaction_confirmations(),
+                    Self::has_sufficient_work_lead(&next_best_fork_tip, main_chain_work)?,
                     Error::<T>::OngoingFork
                 );
             }
@@ -1431,9 +1901,9 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn store_rich_header(basic_block_header: BlockHeader, block_height: u32, chain_id: u32) {
+    fn store_rich_header(basic_block_header: BlockHeader, block_height: u32, chain_id: u32, chain_work: U256) {
         let para_height = ext::security::active_block_number::<T>();
-        let block_header = RichBlockHeader::new(basic_block_header, chain_id, block_height, para_height);
+        let block_header = RichBlockHeader::new(basic_block_header, chain_id, block_height, para_height, chain_work);
         Self::set_block_header_from_hash(basic_block_header.hash, &block_header);
     }
 
@@ -0,0 +1,505 @@
+use super::*;
+use frame_support::{pallet_prelude::StorageVersion, storage, traits::OnRuntimeUpgrade};
+use sp_std::vec::Vec;
+
+/// The log target.
+const TARGET: &'static str = "runtime::btc_relay::migration";
+
+pub mod v0 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+
+    /// Bitcoin Enriched Block Headers, before the cumulative chainwork was tracked
+    #[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+    pub struct RichBlockHeader<BlockNumber> {
+        pub block_header: BlockHeader,
+        pub block_height: u32,
+        pub chain_id: u32,
+        pub para_height: BlockNumber,
+    }
+
+    #[frame_support::storage_alias]
+    pub(crate) type BlockHeaders<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        H256Le,
+        RichBlockHeader<<T as frame_system::Config>::BlockNumber>,
+        ValueQuery,
+    >;
+}
+
+pub mod v1 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+
+    /// Progress of the migration of the block headers, which continues in `on_idle`
+    /// after the runtime upgrade
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+    pub enum MigrationProgress {
+        /// Removing the headers below the pruning height, they are not migrated
+        Pruning { pruning_height: u32 },
+        /// Computing the chainwork of the `chain_index`-th chain, ordered by start height,
+        /// from `block_height` upwards
+        Migrating { chain_index: u32, block_height: u32 },
+    }
+
+    #[frame_support::storage_alias]
+    pub(crate) type HeaderMigrationProgress<T: Config> = StorageValue<Pallet<T>, MigrationProgress, OptionQuery>;
+
+    /// Returns true while the block headers are still in the V0 encoding, in which case
+    /// they cannot be read
+    pub fn is_migrating<T: Config>() -> bool {
+        HeaderMigrationProgress::<T>::exists()
+    }
+
+    /// Migrate the btc-relay pallet from V0 to V1. The block headers below the pruning window `W`
+    /// are removed and the cumulative chainwork of the remaining block headers is computed. Since
+    /// this touches up to two pruning windows worth of headers, the upgrade only records the
+    /// pruning height and the headers are migrated in bounded chunks in `on_idle`. Until then,
+    /// no headers can be stored and no inclusion can be verified.
+    pub struct MigrateToV1<T, W>(sp_std::marker::PhantomData<(T, W)>);
+
+    impl<T: Config, W: Get<u32>> OnRuntimeUpgrade for MigrateToV1<T, W> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "Can only upgrade from version 0");
+            Ok(Vec::new())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version != 0 {
+                log::warn!(
+                    target: TARGET,
+                    "skipping v0 to v1 migration: executed on wrong storage version.\
+            				Expected version 0, found {:?}",
+                    version,
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let pruning_height = Pallet::<T>::get_pruning_height_for_window(W::get()).unwrap_or_default();
+            HeaderMigrationProgress::<T>::put(MigrationProgress::Pruning { pruning_height });
+
+            log::info!(
+                target: TARGET,
+                "migrating block headers from height {} in on_idle",
+                pruning_height
+            );
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(2, 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Must upgrade");
+            assert!(is_migrating::<T>(), "Must schedule the header migration");
+            Ok(())
+        }
+    }
+
+    /// Migrates as many block headers as fit into the given weight
+    ///
+    /// # Arguments
+    ///
+    /// * `remaining_weight` - the weight that is available for the migration
+    ///
+    /// # Returns
+    ///
+    /// The weight that was consumed
+    pub(crate) fn migrate_block_headers_with_weight_limit<T: Config>(remaining_weight: Weight) -> Weight {
+        let base_weight =
+            <T as Config>::WeightInfo::prune_block_headers(0).saturating_add(T::DbWeight::get().reads_writes(1, 1));
+        // a header is either pruned or rewritten, charge whatever is more expensive
+        let weight_per_header = <T as Config>::WeightInfo::prune_block_headers(1)
+            .saturating_sub(<T as Config>::WeightInfo::prune_block_headers(0))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .ref_time()
+            .max(migrate_block_header_weight::<T>().ref_time());
+        if remaining_weight.ref_time() < base_weight.ref_time().saturating_add(weight_per_header) {
+            return Weight::from_ref_time(0);
+        }
+
+        let max_headers = remaining_weight.ref_time().saturating_sub(base_weight.ref_time()) / weight_per_header.max(1);
+        let max_headers = max_headers.min(MAX_PRUNED_BLOCK_HEADERS_PER_BLOCK as u64) as u32;
+
+        // on_idle is not transactional, discard partial changes on failure
+        match storage::with_storage_layer(|| migrate_block_headers::<T>(max_headers)) {
+            Ok(weight) => weight,
+            Err(e) => {
+                log::error!(target: TARGET, "failed to migrate block headers: {:?}", e);
+                base_weight.saturating_add(Weight::from_ref_time(
+                    weight_per_header.saturating_mul(max_headers as u64),
+                ))
+            }
+        }
+    }
+
+    fn migrate_block_header_weight<T: Config>() -> Weight {
+        // the chain hash, the header and its parent, the parent is usually cached
+        T::DbWeight::get().reads_writes(3, 1)
+    }
+
+    /// Prunes or migrates up to `max_headers` block headers, and removes the migration progress
+    /// once all block headers have been migrated
+    ///
+    /// # Returns
+    ///
+    /// The weight that was consumed
+    pub(crate) fn migrate_block_headers<T: Config>(max_headers: u32) -> Result<Weight, DispatchError> {
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+        let progress = match HeaderMigrationProgress::<T>::get() {
+            Some(MigrationProgress::Pruning { pruning_height }) => {
+                let (num_pruned, num_chains) = Pallet::<T>::prune_block_headers_below(pruning_height, max_headers)?;
+                weight.saturating_accrue(Pallet::<T>::prune_block_headers_weight(num_pruned, num_chains));
+                log::info!(target: TARGET, "pruned {} block headers", num_pruned);
+
+                // headers of chains that were not visited are migrated below, they remain readable
+                if num_pruned < max_headers {
+                    MigrationProgress::Migrating {
+                        chain_index: 0,
+                        block_height: 0,
+                    }
+                } else {
+                    MigrationProgress::Pruning { pruning_height }
+                }
+            }
+            Some(MigrationProgress::Migrating {
+                mut chain_index,
+                mut block_height,
+            }) => {
+                // the parent of the first block in a chain always has a lower height, and is therefore
+                // part of a chain that starts lower - so if we process the chains ordered by their start
+                // height, the chainwork of the parent is always known by the time we reach a block.
+                // Chains do not change while the headers are migrated.
+                let mut chains = ChainsIndex::<T>::iter_values().collect::<Vec<_>>();
+                chains.sort_by_key(|chain| (chain.start_height, chain.chain_id));
+                weight.saturating_accrue(T::DbWeight::get().reads(chains.len() as u64));
+
+                let mut num_migrated = 0;
+                while num_migrated < max_headers {
+                    let chain = match chains.get(chain_index as usize) {
+                        Some(chain) => chain,
+                        None => break,
+                    };
+                    if block_height > chain.max_height {
+                        chain_index = chain_index.saturating_add(1);
+                        block_height = 0;
+                        continue;
+                    }
+                    let height = block_height.max(chain.start_height);
+                    migrate_block_header::<T>(chain.chain_id, height);
+                    weight.saturating_accrue(migrate_block_header_weight::<T>());
+                    num_migrated += 1;
+                    block_height = height.saturating_add(1);
+                }
+                log::info!(target: TARGET, "migrated {} block headers", num_migrated);
+
+                if chain_index as usize >= chains.len() {
+                    log::info!(target: TARGET, "finished migrating block headers");
+                    HeaderMigrationProgress::<T>::kill();
+                    return Ok(weight);
+                }
+                MigrationProgress::Migrating {
+                    chain_index,
+                    block_height,
+                }
+            }
+            None => return Ok(weight),
+        };
+
+        HeaderMigrationProgress::<T>::put(progress);
+        Ok(weight)
+    }
+
+    /// Computes the chainwork of the block at the given height in the given chain. The block the
+    /// relay was initialized with has no parent and the parent of the first retained block was
+    /// pruned, in which case the work is counted from zero. Forks that branched off below the
+    /// pruning height can no longer become the main chain anyway.
+    fn migrate_block_header<T: Config>(chain_id: u32, block_height: u32) {
+        let block_hash = match ChainsHashes::<T>::try_get(chain_id, block_height) {
+            Ok(block_hash) => block_hash,
+            Err(_) => return,
+        };
+
+        let header = v0::BlockHeaders::<T>::get(block_hash);
+        let prev_chain_work = BlockHeaders::<T>::try_get(header.block_header.hash_prev_block)
+            .map(|prev_header| prev_header.chain_work)
+            .unwrap_or_default();
+        let chain_work = prev_chain_work.saturating_add(header.block_header.target.get_block_proof());
+
+        BlockHeaders::<T>::insert(
+            block_hash,
+            RichBlockHeader::new(
+                header.block_header,
+                header.chain_id,
+                header.block_height,
+                header.para_height,
+                chain_work,
+            ),
+        );
+    }
+}
+
+pub mod v2 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+
+    /// Migrate the btc-relay pallet from V1 to V2, enabling pruning with the given window.
+    /// The existing backlog of block headers is not pruned during the upgrade, it is pruned
+    /// in bounded chunks by `on_idle` instead.
+    pub struct MigrateToV2<T, W>(sp_std::marker::PhantomData<(T, W)>);
+
+    impl<T: Config, W: Get<u32>> OnRuntimeUpgrade for MigrateToV2<T, W> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Can only upgrade from version 1");
+
+            let num_headers = BlockHeaders::<T>::iter_keys().count() as u32;
+            Ok(num_headers.encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version != 1 {
+                log::warn!(
+                    target: TARGET,
+                    "skipping v1 to v2 migration: executed on wrong storage version.\
+            				Expected version 1, found {:?}",
+                    version,
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let weight = T::DbWeight::get().reads(1);
+
+            if let Err(e) = Pallet::<T>::_set_pruning_window(W::get()) {
+                log::error!(target: TARGET, "failed to set pruning window: {:?}", e);
+                return weight;
+            }
+
+            log::info!(target: TARGET, "enabled pruning with a window of {} blocks", W::get());
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            weight
+                .saturating_add(<T as Config>::WeightInfo::set_pruning_window())
+                .saturating_add(T::DbWeight::get().writes(1))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            assert_eq!(StorageVersion::get::<Pallet<T>>(), 2, "Must upgrade");
+            assert_eq!(Pallet::<T>::pruning_window(), W::get());
+
+            let num_headers_before = u32::decode(&mut &state[..]).map_err(|_| "Failed to decode state")?;
+            let num_headers_after = BlockHeaders::<T>::iter_keys().count() as u32;
+            assert_eq!(
+                num_headers_after, num_headers_before,
+                "Must not prune during the upgrade"
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "try-runtime")]
+mod test {
+    use super::*;
+    use crate::mock::*;
+    use frame_support::traits::Hooks;
+
+    fn header_with_parent(nonce: u32, hash_prev_block: H256Le, target: U256) -> BlockHeader {
+        let mut block_header = BlockHeader {
+            nonce,
+            hash_prev_block,
+            target,
+            ..Default::default()
+        };
+        block_header.update_hash().unwrap();
+        block_header
+    }
+
+    fn insert_v0_header(block_header: BlockHeader, chain_id: u32, block_height: u32) {
+        ChainsHashes::<Test>::insert(chain_id, block_height, block_header.hash);
+        v0::BlockHeaders::<Test>::insert(
+            block_header.hash,
+            v0::RichBlockHeader {
+                block_header,
+                block_height,
+                chain_id,
+                para_height: Default::default(),
+            },
+        );
+    }
+
+    fn migrate_all_block_headers() {
+        // one header per block to exercise resuming the migration
+        while v1::is_migrating::<Test>() {
+            v1::migrate_block_headers::<Test>(1).unwrap();
+        }
+    }
+
+    #[test]
+    fn migration_v0_to_v1_works() {
+        run_test(|| {
+            // assume that we are at v0
+            StorageVersion::new(0).put::<BTCRelay>();
+
+            let easy_target = U256::from(2).pow(240.into());
+            let hard_target = U256::from(2).pow(230.into());
+
+            // main chain: genesis -> main_1 -> main_2
+            let genesis = header_with_parent(0, H256Le::zero(), easy_target);
+            let main_1 = header_with_parent(1, genesis.hash, easy_target);
+            let main_2 = header_with_parent(2, main_1.hash, easy_target);
+            // fork: genesis -> fork_1
+            let fork_1 = header_with_parent(10, genesis.hash, hard_target);
+
+            insert_v0_header(genesis, MAIN_CHAIN_ID, 0);
+            insert_v0_header(main_1, MAIN_CHAIN_ID, 1);
+            insert_v0_header(main_2, MAIN_CHAIN_ID, 2);
+            insert_v0_header(fork_1, 1, 1);
+            // insert the fork first, the migration must not depend on the iteration order
+            ChainsIndex::<Test>::insert(
+                1,
+                BlockChain {
+                    chain_id: 1,
+                    start_height: 1,
+                    max_height: 1,
+                },
+            );
+            ChainsIndex::<Test>::insert(
+                MAIN_CHAIN_ID,
+                BlockChain {
+                    chain_id: MAIN_CHAIN_ID,
+                    start_height: 0,
+                    max_height: 2,
+                },
+            );
+
+            type Migration = v1::MigrateToV1<Test, frame_support::traits::ConstU32<DIFFICULTY_ADJUSTMENT_INTERVAL>>;
+            let state = Migration::pre_upgrade().unwrap();
+            let _w = Migration::on_runtime_upgrade();
+            Migration::post_upgrade(state).unwrap();
+            migrate_all_block_headers();
+
+            let easy_work = easy_target.get_block_proof();
+            let hard_work = hard_target.get_block_proof();
+            assert_eq!(BlockHeaders::<Test>::get(genesis.hash).chain_work, easy_work);
+            assert_eq!(BlockHeaders::<Test>::get(main_1.hash).chain_work, easy_work * 2u32);
+            assert_eq!(BlockHeaders::<Test>::get(main_2.hash).chain_work, easy_work * 3u32);
+            assert_eq!(BlockHeaders::<Test>::get(fork_1.hash).chain_work, easy_work + hard_work);
+            assert_eq!(BlockHeaders::<Test>::get(fork_1.hash).chain_id, 1);
+
+            assert_eq!(StorageVersion::get::<BTCRelay>(), 1);
+        });
+    }
+
+    #[test]
+    fn migration_v0_to_v1_only_migrates_pruning_window() {
+        run_test(|| {
+            StorageVersion::new(0).put::<BTCRelay>();
+
+            let target = U256::from(2).pow(240.into());
+            let window = DIFFICULTY_ADJUSTMENT_INTERVAL;
+            let best_block_height = window + 1;
+
+            // main chain with headers at the first two and the last two heights
+            let old_1 = header_with_parent(0, H256Le::zero(), target);
+            let old_2 = header_with_parent(1, old_1.hash, target);
+            let new_1 = header_with_parent(2, H256Le::zero(), target);
+            let new_2 = header_with_parent(3, new_1.hash, target);
+            insert_v0_header(old_1, MAIN_CHAIN_ID, 0);
+            insert_v0_header(old_2, MAIN_CHAIN_ID, 1);
+            insert_v0_header(new_1, MAIN_CHAIN_ID, best_block_height - 1);
+            insert_v0_header(new_2, MAIN_CHAIN_ID, best_block_height);
+            ChainsIndex::<Test>::insert(
+                MAIN_CHAIN_ID,
+                BlockChain {
+                    chain_id: MAIN_CHAIN_ID,
+                    start_height: 0,
+                    max_height: best_block_height,
+                },
+            );
+            BestBlockHeight::<Test>::put(best_block_height);
+
+            type Migration = v1::MigrateToV1<Test, frame_support::traits::ConstU32<DIFFICULTY_ADJUSTMENT_INTERVAL>>;
+            let state = Migration::pre_upgrade().unwrap();
+            let _w = Migration::on_runtime_upgrade();
+            Migration::post_upgrade(state).unwrap();
+
+            // the relay cannot be used until the headers have been migrated
+            assert_eq!(StorageVersion::get::<BTCRelay>(), 1);
+            assert_eq!(
+                BTCRelay::verify_block_header_inclusion(new_2.hash, Some(0)),
+                Err(TestError::HeaderMigrationInProgress.into())
+            );
+            migrate_all_block_headers();
+
+            // the header below the pruning height is removed rather than left undecodable
+            assert!(!BlockHeaders::<Test>::contains_key(old_1.hash));
+            assert!(!ChainsHashes::<Test>::contains_key(MAIN_CHAIN_ID, 0));
+            assert_eq!(
+                BTCRelay::get_block_chain_from_id(MAIN_CHAIN_ID).unwrap().start_height,
+                1
+            );
+            assert!(BlockHeaders::<Test>::try_get(old_2.hash).is_ok());
+            assert_eq!(
+                BlockHeaders::<Test>::get(new_2.hash).chain_work,
+                target.get_block_proof() * 2u32
+            );
+            assert_eq!(StorageVersion::get::<BTCRelay>(), 1);
+        });
+    }
+
+    #[test]
+    fn migration_v1_to_v2_works() {
+        run_test(|| {
+            StorageVersion::new(1).put::<BTCRelay>();
+
+            let num_headers = MAX_PRUNED_BLOCK_HEADERS_PER_BLOCK + 10;
+            let best_block_height = num_headers + DIFFICULTY_ADJUSTMENT_INTERVAL - 1;
+            Chains::<Test>::insert(0, MAIN_CHAIN_ID);
+            ChainsIndex::<Test>::insert(
+                MAIN_CHAIN_ID,
+                BlockChain {
+                    chain_id: MAIN_CHAIN_ID,
+                    start_height: 0,
+                    max_height: best_block_height,
+                },
+            );
+            for height in 0..num_headers {
+                let block_hash = H256Le::from_bytes_le(H256::from_low_u64_le(height as u64 + 1).as_bytes());
+                ChainsHashes::<Test>::insert(MAIN_CHAIN_ID, height, block_hash);
+                BlockHeaders::<Test>::insert(block_hash, RichBlockHeader::<BlockNumber>::default());
+            }
+            BestBlockHeight::<Test>::put(best_block_height);
+
+            type Migration = v2::MigrateToV2<Test, frame_support::traits::ConstU32<DIFFICULTY_ADJUSTMENT_INTERVAL>>;
+            let state = Migration::pre_upgrade().unwrap();
+            let _w = Migration::on_runtime_upgrade();
+            Migration::post_upgrade(state).unwrap();
+            assert_eq!(BlockHeaders::<Test>::iter_keys().count(), num_headers as usize);
+
+            // the backlog is pruned in bounded chunks
+            BTCRelay::on_idle(0, Weight::MAX);
+            assert_eq!(
+                BlockHeaders::<Test>::iter_keys().count(),
+                (num_headers - MAX_PRUNED_BLOCK_HEADERS_PER_BLOCK) as usize
+            );
+            BTCRelay::on_idle(0, Weight::MAX);
+
+            // the last header below the difficulty adjustment interval is retained
+            assert_eq!(BlockHeaders::<Test>::iter_keys().count(), 1);
+            assert_eq!(
+                BTCRelay::get_block_chain_from_id(MAIN_CHAIN_ID).unwrap().start_height,
+                num_headers - 1
+            );
+            assert_eq!(BTCRelay::pruning_window(), DIFFICULTY_ADJUSTMENT_INTERVAL);
+            assert_eq!(StorageVersion::get::<BTCRelay>(), 2);
+        });
+    }
+}
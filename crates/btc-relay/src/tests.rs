@@ -1,13 +1,17 @@
 /// Tests for BTC-Relay
 use sp_core::U256;
 
-use crate::{ext, mock::*, types::*, BtcAddress, Error, DIFFICULTY_ADJUSTMENT_INTERVAL};
+use crate::{
+    ext, mock::*, types::*, BtcAddress, Error, DIFFICULTY_ADJUSTMENT_INTERVAL, MAX_BLOCK_HEADERS_PER_BATCH,
+    MAX_RAW_TRANSACTION_SIZE, MAX_TRANSACTIONS_PER_BATCH,
+};
 
 type Event = crate::Event<Test>;
 
 use crate::{Chains, ChainsIndex};
-use bitcoin::{formatter::TryFormattable, merkle::*, parser::*, types::*};
-use frame_support::{assert_err, assert_ok};
+use bitcoin::{formatter::TryFormattable, merkle::*, parser::*, types::*, GetBlockProof};
+use codec::{Decode, Encode};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::ConstU32, BoundedVec};
 use mocktopus::mocking::*;
 use sp_std::{
     convert::{TryFrom, TryInto},
@@ -49,6 +53,7 @@ fn get_block_header_from_hash_succeeds() {
             block_height,
             chain_id,
             para_height: Default::default(),
+            chain_work: Default::default(),
         };
 
         BTCRelay::set_block_header_from_hash(rich_header.block_hash(), &rich_header);
@@ -184,6 +189,7 @@ fn store_block_header_on_mainchain_succeeds() {
             block_height,
             chain_id,
             para_height: Default::default(),
+            chain_work: Default::default(),
         };
         BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_header)));
 
@@ -218,6 +224,7 @@ fn store_block_header_on_fork_succeeds() {
             block_height: block_height - 1,
             chain_id,
             para_height: Default::default(),
+            chain_work: Default::default(),
         };
         BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_header)));
 
@@ -245,6 +252,7 @@ mod store_block_header_tests {
     use std::iter::successors;
 
     use crate::MAIN_CHAIN_ID;
+    use frame_support::weights::Weight;
 
     use super::*;
     fn from_prev(nonce: u32, prev: H256Le) -> BlockHeader {
@@ -257,6 +265,20 @@ mod store_block_header_tests {
         ret
     }
 
+    fn from_prev_with_target(nonce: u32, prev: H256Le, target: U256) -> BlockHeader {
+        let mut ret = BlockHeader {
+            target,
+            ..from_prev(nonce, prev)
+        };
+        ret.update_hash().unwrap();
+        ret
+    }
+
+    fn chain_work_at_position(position: u32) -> U256 {
+        let chain = ChainsIndex::<Test>::get(Chains::<Test>::get(position).unwrap()).unwrap();
+        BTCRelay::get_chain_work(&chain).unwrap()
+    }
+
     fn check_store_block_header_invariants() {
         let mainchain = ChainsIndex::<Test>::get(0).unwrap();
 
@@ -273,29 +295,32 @@ mod store_block_header_tests {
         }
         let chains = chains.into_iter().map(|(_, value)| value).collect::<Vec<_>>();
 
-        // The keys in ``ChainsIndex`` MUST be consecutive, i.e. for each ``i``, if ``ChainsIndex[i]`` does not exist,
-        // ``ChainsIndex[i+1]`` MUST NOT exist either.
-        for (arr_idx, (key, _value)) in chains_index.iter().enumerate() {
-            assert_eq!(arr_idx as u32, *key);
+        // Every chain in ``Chains`` MUST exist in ``ChainsIndex`` and vice versa. The keys in ``ChainsIndex``
+        // are not necessarily consecutive, since chains that have been pruned are removed.
+        assert_eq!(chains.len(), chains_index.len());
+        for chain_id in chains.iter() {
+            assert!(chains_index.iter().any(|(key, _)| key == chain_id));
         }
-        let chains_index = chains_index.into_iter().map(|(_, value)| value).collect::<Vec<_>>();
 
-        // for all i > 0, `ChainsIndex[i].maxHeight < ChainsIndex[0].maxHeight + STABLE_BITCOIN_CONFIRMATIONS`
-        for chain in chains_index.iter().skip(1) {
-            assert!(chain.max_height < mainchain.max_height + BTCRelay::get_stable_transaction_confirmations());
+        // ChainsIndex[i].chainRef = i
+        for (key, chain) in chains_index.iter() {
+            assert_eq!(*key, chain.chain_id);
         }
+        let chains_index = chains_index.into_iter().map(|(_, value)| value).collect::<Vec<_>>();
 
-        // The values in ``Chains`` MUST be such that for each ``0 < i < j``, ``ChainsIndex[Chains[i]].maxHeight >=
-        // ChainsIndex[Chains[j]].maxHeight``.
-        for i in 1..chains.len() - 1 {
-            if chains_index[chains[i] as usize].max_height < chains_index[chains[i + 1] as usize].max_height {
-                assert!(chains_index[chains[i] as usize].max_height >= chains_index[chains[i + 1] as usize].max_height);
-            }
+        // for all i > 0, the chainwork of `ChainsIndex[i]` is less than that of `ChainsIndex[0]` plus
+        // STABLE_BITCOIN_CONFIRMATIONS blocks at the difficulty of the main chain tip
+        let main_tip = BTCRelay::get_chain_tip(&mainchain).unwrap();
+        let max_fork_work = main_tip.chain_work
+            + main_tip.block_header.target.get_block_proof() * BTCRelay::get_stable_transaction_confirmations();
+        for chain in chains_index.iter().skip(1) {
+            assert!(BTCRelay::get_chain_work(chain).unwrap() < max_fork_work);
         }
 
-        // ChainsIndex[i].chainRef = i
-        for (idx, chain) in chains_index.iter().enumerate() {
-            assert_eq!(idx as u32, chain.chain_id);
+        // The values in ``Chains`` MUST be such that for each ``0 < i < j``, the chainwork of
+        // ``ChainsIndex[Chains[i]]`` is greater than or equal to that of ``ChainsIndex[Chains[j]]``.
+        for i in 1..chains.len().saturating_sub(1) {
+            assert!(chain_work_at_position(i as u32) >= chain_work_at_position(i as u32 + 1));
         }
 
         // BestBlock MUST refer the latest block from the main chain
@@ -319,7 +344,7 @@ mod store_block_header_tests {
 
         // ChainsHashes MUST ONLY contain items for heights that are in the corresponding chain
         for (chain_idx, height, _hash) in crate::ChainsHashes::<Test>::iter() {
-            let chain = &chains_index[chain_idx as usize];
+            let chain = chains_index.iter().find(|chain| chain.chain_id == chain_idx).unwrap();
             assert!(height >= chain.start_height && height <= chain.max_height);
         }
         // For each chain, ChainsHashes MUST contain exactly `chain_length` hashes
@@ -340,7 +365,7 @@ mod store_block_header_tests {
     fn assert_is_block(height: u32, block_header: &BlockHeader) {
         Security::set_active_block_number(ext::security::active_block_number::<Test>() + 1000);
 
-        BTCRelay::ensure_no_ongoing_fork.mock_safe(|_| MockResult::Return(Ok(())));
+        BTCRelay::ensure_no_ongoing_fork.mock_safe(|| MockResult::Return(Ok(())));
         assert_ok!(BTCRelay::verify_block_header_inclusion(block_header.hash, Some(0)));
         BTCRelay::ensure_no_ongoing_fork.clear_mock();
 
@@ -399,10 +424,7 @@ mod store_block_header_tests {
     }
 
     fn assert_ongoing_fork() {
-        assert_err!(
-            BTCRelay::ensure_no_ongoing_fork(BTCRelay::get_best_block_height()),
-            TestError::OngoingFork
-        );
+        assert_err!(BTCRelay::ensure_no_ongoing_fork(), TestError::OngoingFork);
     }
 
     fn store_header_and_check_invariants(block: &BlockHeader) {
@@ -492,6 +514,242 @@ mod store_block_header_tests {
         })
     }
 
+    #[test]
+    fn store_block_header_equal_height_forks_are_ordered_by_chain_work() {
+        run_test(|| {
+            BTCRelay::verify_block_header.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+            let easy_target = U256::from(2).pow(240.into());
+            let hard_target = U256::from(2).pow(239.into());
+
+            let genesis = from_prev_with_target(0, H256Le::zero(), easy_target);
+            assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+
+            // extend the main chain such that the blocks below are all forks
+            let main_1 = from_prev_with_target(1, genesis.hash, easy_target);
+            let main_2 = from_prev_with_target(2, main_1.hash, easy_target);
+            store_header_and_check_invariants(&main_1);
+            store_header_and_check_invariants(&main_2);
+
+            // two forks of the same height, the one with more work is submitted first
+            let hard_fork = from_prev_with_target(10, genesis.hash, hard_target);
+            let easy_fork = from_prev_with_target(11, genesis.hash, easy_target);
+            store_header_and_check_invariants(&hard_fork);
+            store_header_and_check_invariants(&easy_fork);
+
+            let hard_fork_chain_id = BTCRelay::get_block_header_from_hash(hard_fork.hash).unwrap().chain_id;
+            let easy_fork_chain_id = BTCRelay::get_block_header_from_hash(easy_fork.hash).unwrap().chain_id;
+            assert_eq!(Chains::<Test>::get(1), Some(hard_fork_chain_id));
+            assert_eq!(Chains::<Test>::get(2), Some(easy_fork_chain_id));
+            assert!(chain_work_at_position(1) > chain_work_at_position(2));
+        })
+    }
+
+    #[test]
+    fn store_block_header_equal_height_fork_with_more_work_becomes_main_chain() {
+        run_test(|| {
+            BTCRelay::verify_block_header.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+            let easy_target = U256::from(2).pow(240.into());
+            let hard_target = U256::from(2).pow(230.into());
+
+            let genesis = from_prev_with_target(0, H256Le::zero(), easy_target);
+            assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+
+            let main_1 = from_prev_with_target(1, genesis.hash, easy_target);
+            store_header_and_check_invariants(&main_1);
+            assert_best_block(&main_1, 1);
+
+            // a single block of the fork is worth more than CONFIRMATIONS blocks of the main chain
+            let fork_1 = from_prev_with_target(10, genesis.hash, hard_target);
+            store_header_and_check_invariants(&fork_1);
+
+            assert_best_block(&fork_1, 1);
+            assert_eq!(
+                BTCRelay::get_block_header_from_hash(fork_1.hash).unwrap().chain_work,
+                easy_target.get_block_proof() + hard_target.get_block_proof()
+            );
+            let reorg_event = TestEvent::BTCRelay(Event::ChainReorg {
+                new_chain_tip_hash: fork_1.hash,
+                new_chain_tip_height: 1,
+                fork_depth: 0,
+            });
+            assert!(System::events().iter().any(|a| a.event == reorg_event));
+        })
+    }
+
+    #[test]
+    fn store_block_header_longer_fork_with_less_work_does_not_become_main_chain() {
+        run_test(|| {
+            BTCRelay::verify_block_header.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+            let easy_target = U256::from(2).pow(240.into());
+            let hard_target = U256::from(2).pow(230.into());
+
+            let genesis = from_prev_with_target(0, H256Le::zero(), easy_target);
+            assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+
+            let main_1 = from_prev_with_target(1, genesis.hash, hard_target);
+            store_header_and_check_invariants(&main_1);
+
+            // the fork gets CONFIRMATIONS blocks ahead of the main chain, but at a lower difficulty
+            let fork_blocks = successors(Some(from_prev_with_target(10, genesis.hash, easy_target)), |prev| {
+                Some(from_prev_with_target(prev.nonce + 1, prev.hash, easy_target))
+            })
+            .take(BTCRelay::get_stable_transaction_confirmations() as usize + 1)
+            .collect::<Vec<_>>();
+            for block in fork_blocks.iter() {
+                store_header_and_check_invariants(block);
+            }
+
+            let fork_tip = fork_blocks.last().unwrap();
+            let fork_header = BTCRelay::get_block_header_from_hash(fork_tip.hash).unwrap();
+            assert!(fork_header.block_height >= 1 + BTCRelay::get_stable_transaction_confirmations());
+            assert_best_block(&main_1, 1);
+            assert_eq!(Chains::<Test>::get(1), Some(fork_header.chain_id));
+        })
+    }
+
+    #[test]
+    fn longer_fork_with_less_work_is_not_an_ongoing_fork() {
+        run_test(|| {
+            BTCRelay::verify_block_header.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+            let easy_target = U256::from(2).pow(240.into());
+            let hard_target = U256::from(2).pow(230.into());
+
+            let genesis = from_prev_with_target(0, H256Le::zero(), easy_target);
+            assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+
+            let main_1 = from_prev_with_target(1, genesis.hash, hard_target);
+            store_header_and_check_invariants(&main_1);
+
+            // a long fork of easy blocks that is far ahead of the main chain in height only
+            let fork_blocks = successors(Some(from_prev_with_target(10, genesis.hash, easy_target)), |prev| {
+                Some(from_prev_with_target(prev.nonce + 1, prev.hash, easy_target))
+            })
+            .take(3 * BTCRelay::get_stable_transaction_confirmations() as usize)
+            .collect::<Vec<_>>();
+            for block in fork_blocks.iter() {
+                store_header_and_check_invariants(block);
+            }
+            let fork = BTCRelay::get_block_chain_from_id(Chains::<Test>::get(1).unwrap()).unwrap();
+            assert_eq!(fork.max_height, 3 * BTCRelay::get_stable_transaction_confirmations());
+
+            assert_ok!(BTCRelay::ensure_no_ongoing_fork());
+            assert_ok!(BTCRelay::verify_block_header_inclusion(main_1.hash, Some(0)));
+        })
+    }
+
+    #[test]
+    fn prune_block_headers_removes_headers_below_pruning_height() {
+        run_test(|| {
+            BTCRelay::verify_block_header.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+            let genesis = sample_block_header();
+            assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+
+            let main_chain = successors(Some(genesis), |prev| Some(from_prev(prev.nonce + 1, prev.hash)))
+                .take(11)
+                .collect::<Vec<_>>();
+            for block in main_chain.iter().skip(1) {
+                store_header_and_check_invariants(block);
+            }
+
+            // fork at heights 2 and 3, entirely below the pruning height
+            let dead_fork_1 = from_prev(100, main_chain[1].hash);
+            let dead_fork_2 = from_prev(101, dead_fork_1.hash);
+            // fork at height 8, above the pruning height
+            let live_fork = from_prev(200, main_chain[7].hash);
+            for block in [dead_fork_1, dead_fork_2, live_fork].iter() {
+                store_header_and_check_invariants(block);
+            }
+            let dead_fork_id = BTCRelay::get_block_header_from_hash(dead_fork_1.hash).unwrap().chain_id;
+            let live_fork_id = BTCRelay::get_block_header_from_hash(live_fork.hash).unwrap().chain_id;
+
+            BTCRelay::get_pruning_height.mock_safe(|| MockResult::Return(Some(5)));
+
+            // the number of pruned headers is bounded
+            assert_ok!(BTCRelay::prune_block_headers(3).map(|(num_pruned, _)| num_pruned), 3);
+            assert_ok!(BTCRelay::prune_block_headers(100).map(|(num_pruned, _)| num_pruned), 4);
+            // the remaining main chain and live fork are still visited
+            assert_ok!(BTCRelay::prune_block_headers(100), (0, 2));
+
+            for block in main_chain.iter().take(5).chain([dead_fork_1, dead_fork_2].iter()) {
+                assert!(!BTCRelay::block_header_exists(block.hash));
+            }
+            for block in main_chain.iter().skip(5).chain([live_fork].iter()) {
+                assert!(BTCRelay::block_header_exists(block.hash));
+            }
+
+            let main = BTCRelay::get_block_chain_from_id(MAIN_CHAIN_ID).unwrap();
+            assert_eq!(main.start_height, 5);
+            assert_eq!(main.max_height, 10);
+            assert_eq!(crate::ChainsHashes::<Test>::iter_prefix(MAIN_CHAIN_ID).count(), 6);
+
+            // the dead fork is removed and the remaining chains are moved up
+            assert_err!(
+                BTCRelay::get_block_chain_from_id(dead_fork_id),
+                TestError::InvalidChainID
+            );
+            assert_eq!(Chains::<Test>::iter().count(), 2);
+            assert_eq!(Chains::<Test>::get(0), Some(MAIN_CHAIN_ID));
+            assert_eq!(Chains::<Test>::get(1), Some(live_fork_id));
+
+            // we can still extend the main chain and the remaining fork
+            store_header_and_check_invariants(&from_prev(300, main_chain[10].hash));
+            store_header_and_check_invariants(&from_prev(201, live_fork.hash));
+            // but not build on pruned blocks
+            assert_err!(
+                BTCRelay::_store_block_header(&3, from_prev(400, main_chain[3].hash)),
+                TestError::BlockNotFound
+            );
+        })
+    }
+
+    #[test]
+    fn prune_block_headers_does_nothing_when_disabled() {
+        run_test(|| {
+            BTCRelay::verify_block_header.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+            let genesis = sample_block_header();
+            assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+            store_header_and_check_invariants(&from_prev(1, genesis.hash));
+
+            assert_eq!(BTCRelay::pruning_window(), 0);
+            assert_ok!(BTCRelay::prune_block_headers(100), (0, 0));
+            assert!(BTCRelay::block_header_exists(genesis.hash));
+        })
+    }
+
+    #[test]
+    fn prune_block_headers_with_weight_limit_respects_remaining_weight() {
+        run_test(|| {
+            BTCRelay::verify_block_header.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+            let genesis = sample_block_header();
+            assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+            let block_1 = from_prev(1, genesis.hash);
+            store_header_and_check_invariants(&block_1);
+
+            BTCRelay::get_pruning_height.mock_safe(|| MockResult::Return(Some(2)));
+
+            // not enough weight to prune a single header
+            let weight = <() as crate::WeightInfo>::prune_block_headers(1).saturating_sub(Weight::from_ref_time(1));
+            assert_eq!(
+                BTCRelay::prune_block_headers_with_weight_limit(weight),
+                Weight::from_ref_time(0)
+            );
+            assert!(BTCRelay::block_header_exists(genesis.hash));
+
+            // enough weight to prune exactly one header
+            let weight = <() as crate::WeightInfo>::prune_block_headers(1);
+            assert_eq!(BTCRelay::prune_block_headers_with_weight_limit(weight), weight);
+            assert!(!BTCRelay::block_header_exists(genesis.hash));
+            assert!(BTCRelay::block_header_exists(block_1.hash));
+        })
+    }
+
     fn parse_from_hex(hex_string: &str) -> BlockHeader {
         let raw = RawBlockHeader::from_hex(hex_string).unwrap();
         parse_block_header_lenient(&raw).unwrap()
@@ -588,6 +846,56 @@ mod store_block_header_tests {
     }
 }
 
+#[test]
+fn set_pruning_window_succeeds() {
+    run_test(|| {
+        assert_ok!(BTCRelay::set_pruning_window(
+            RuntimeOrigin::root(),
+            DIFFICULTY_ADJUSTMENT_INTERVAL
+        ));
+        assert_eq!(BTCRelay::pruning_window(), DIFFICULTY_ADJUSTMENT_INTERVAL);
+        let event = TestEvent::BTCRelay(Event::PruningWindowChange {
+            pruning_window: DIFFICULTY_ADJUSTMENT_INTERVAL,
+        });
+        assert!(System::events().iter().any(|a| a.event == event));
+
+        // zero disables pruning
+        assert_ok!(BTCRelay::set_pruning_window(RuntimeOrigin::root(), 0));
+        assert_eq!(BTCRelay::get_pruning_height(), None);
+    })
+}
+
+#[test]
+fn set_pruning_window_fails() {
+    run_test(|| {
+        assert_noop!(
+            BTCRelay::set_pruning_window(RuntimeOrigin::signed(3), DIFFICULTY_ADJUSTMENT_INTERVAL),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            BTCRelay::set_pruning_window(RuntimeOrigin::root(), DIFFICULTY_ADJUSTMENT_INTERVAL - 1),
+            TestError::InvalidPruningWindow
+        );
+    })
+}
+
+#[test]
+fn get_pruning_height_retains_difficulty_adjustment_interval() {
+    run_test(|| {
+        BTCRelay::set_best_block_height(10_000);
+
+        assert_ok!(BTCRelay::_set_pruning_window(5_000));
+        assert_eq!(BTCRelay::get_pruning_height(), Some(5_000));
+
+        // the window can only be shortened by writing to storage directly
+        crate::PruningWindow::<Test>::put(10);
+        assert_eq!(
+            BTCRelay::get_pruning_height(),
+            Some(10_000 - DIFFICULTY_ADJUSTMENT_INTERVAL)
+        );
+    })
+}
+
 #[test]
 fn store_block_header_no_prev_block_fails() {
     run_test(|| {
@@ -646,6 +954,8 @@ fn check_and_do_reorg_swap_fork_position() {
 
         assert_eq!(current_position, fork_position);
 
+        mock_chain_work_proportional_to_height();
+
         assert_ok!(BTCRelay::reorganize_chains(&fork));
         // assert that positions have been swapped
         let new_position = BTCRelay::get_chain_position_from_chain_id(fork_chain_id).unwrap();
@@ -688,6 +998,7 @@ fn check_and_do_reorg_new_fork_is_main_chain() {
         assert_eq!(current_position, fork_position);
 
         BTCRelay::swap_main_blockchain.mock_safe(move |_| MockResult::Return(Ok((best_block_hash, fork_block_height))));
+        mock_chain_work_proportional_to_height();
 
         assert_ok!(BTCRelay::reorganize_chains(&fork));
         // assert that the new main chain is set
@@ -730,6 +1041,7 @@ fn check_and_do_reorg_new_fork_below_stable_transaction_confirmations() {
         assert_eq!(current_position, fork_position);
 
         BTCRelay::swap_main_blockchain.mock_safe(move |_| MockResult::Return(Ok((best_block_hash, fork_block_height))));
+        mock_chain_work_proportional_to_height();
 
         assert_ok!(BTCRelay::reorganize_chains(&fork));
         // assert that the fork has not overtaken the main chain
@@ -753,6 +1065,7 @@ fn insert_sorted_succeeds() {
         let main_position: u32 = 0;
         let main = get_empty_block_chain_from_chain_id_and_height(main_chain_id, main_start_height, main_block_height);
         BTCRelay::set_block_chain_from_id(main_chain_id, &main);
+        mock_chain_work_proportional_to_height();
         assert_eq!(Ok(()), BTCRelay::insert_sorted(&main));
 
         let curr_main_pos = BTCRelay::get_chain_position_from_chain_id(main_chain_id).unwrap();
@@ -824,6 +1137,7 @@ fn test_verify_block_header_correct_retarget_increase_succeeds() {
             chain_id,
             block_height,
             Default::default(),
+            Default::default(),
         );
 
         let curr_block_header = parse_block_header_lenient(&retarget_headers[2]).unwrap();
@@ -857,6 +1171,7 @@ fn test_verify_block_header_correct_retarget_decrease_succeeds() {
             chain_id,
             block_height,
             Default::default(),
+            Default::default(),
         );
 
         let curr_block_header = parse_block_header(&retarget_headers[2]).unwrap();
@@ -887,6 +1202,7 @@ fn test_verify_block_header_missing_retarget_succeeds() {
             chain_id,
             block_height,
             Default::default(),
+            Default::default(),
         );
 
         let curr_block_header = parse_block_header_lenient(&retarget_headers[2]).unwrap();
@@ -920,6 +1236,7 @@ fn test_compute_new_target() {
         chain_id,
         block_height,
         Default::default(),
+        Default::default(),
     );
 
     let curr_block_header = parse_block_header_lenient(&retarget_headers[2]).unwrap();
@@ -1026,6 +1343,31 @@ fn test_validate_transaction_succeeds_with_payment_and_op_return() {
     });
 }
 
+#[test]
+fn test_validate_transaction_succeeds_with_taproot_payment_and_op_return() {
+    run_test(|| {
+        let raw_tx = hex::decode(sample_accepted_transaction()).unwrap();
+        let minimum_btc: i64 = 2500200000;
+        let recipient_btc_address = BtcAddress::P2TRv1(
+            H256::from_str(&"53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343").unwrap(),
+        );
+        let op_return_id =
+            hex::decode("e5c17d15b8b1fa2811b7e6da66ffa5e1aaa05922c69068bf90cd585b95bb4675".to_owned()).unwrap();
+
+        let outputs = vec![sample_valid_taproot_payment_output(), sample_valid_data_output()];
+
+        BTCRelay::parse_transaction.mock_safe(move |_| MockResult::Return(Ok(sample_transaction_parsed(&outputs))));
+
+        assert_ok!(BTCRelay::validate_transaction(
+            RuntimeOrigin::signed(3),
+            raw_tx,
+            minimum_btc,
+            recipient_btc_address,
+            Some(H256::from_slice(&op_return_id))
+        ));
+    });
+}
+
 #[test]
 fn test_validate_transaction_succeeds_with_op_return_and_payment() {
     run_test(|| {
@@ -1253,12 +1595,14 @@ fn test_verify_transaction_inclusion_succeeds() {
 
         let proof = sample_merkle_proof();
         let proof_result = sample_valid_proof_result();
+        let tx_id = proof_result.matched_transactions[0].transaction_hash;
 
         let main = get_empty_block_chain_from_chain_id_and_height(chain_id, start, main_chain_height);
 
         let fork = get_empty_block_chain_from_chain_id_and_height(fork_ref, start, fork_chain_height);
 
         BTCRelay::get_chain_id_from_position.mock_safe(move |_| MockResult::Return(Ok(fork_ref)));
+        mock_chain_work_proportional_to_height();
         BTCRelay::get_block_chain_from_id.mock_safe(move |id| {
             if id == chain_id {
                 MockResult::Return(Ok(main.clone()))
@@ -1270,7 +1614,7 @@ fn test_verify_transaction_inclusion_succeeds() {
         BTCRelay::get_best_block_height.mock_safe(move || MockResult::Return(main_chain_height));
 
         BTCRelay::parse_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof.clone())));
-        BTCRelay::verify_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof_result)));
+        BTCRelay::verify_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof_result.clone())));
 
         BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_block_header)));
 
@@ -1280,7 +1624,7 @@ fn test_verify_transaction_inclusion_succeeds() {
 
         assert_ok!(BTCRelay::verify_transaction_inclusion(
             RuntimeOrigin::signed(3),
-            proof_result.transaction_hash,
+            tx_id,
             raw_merkle_proof,
             confirmations
         ));
@@ -1300,6 +1644,7 @@ fn test_verify_transaction_inclusion_empty_fork_succeeds() {
 
         let proof = sample_merkle_proof();
         let proof_result = sample_valid_proof_result();
+        let tx_id = proof_result.matched_transactions[0].transaction_hash;
 
         let main = get_empty_block_chain_from_chain_id_and_height(chain_id, start, main_chain_height);
 
@@ -1314,7 +1659,7 @@ fn test_verify_transaction_inclusion_empty_fork_succeeds() {
         BTCRelay::get_best_block_height.mock_safe(move || MockResult::Return(main_chain_height));
 
         BTCRelay::parse_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof.clone())));
-        BTCRelay::verify_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof_result)));
+        BTCRelay::verify_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof_result.clone())));
 
         BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_block_header)));
 
@@ -1324,7 +1669,7 @@ fn test_verify_transaction_inclusion_empty_fork_succeeds() {
 
         assert_ok!(BTCRelay::verify_transaction_inclusion(
             RuntimeOrigin::signed(3),
-            proof_result.transaction_hash,
+            tx_id,
             raw_merkle_proof,
             confirmations,
         ));
@@ -1357,6 +1702,7 @@ fn test_verify_transaction_inclusion_invalid_tx_id_fails() {
         let fork = get_empty_block_chain_from_chain_id_and_height(fork_ref, start, fork_chain_height);
 
         BTCRelay::get_chain_id_from_position.mock_safe(move |_| MockResult::Return(Ok(fork_ref)));
+        mock_chain_work_proportional_to_height();
         BTCRelay::get_block_chain_from_id.mock_safe(move |id| {
             if id == chain_id {
                 MockResult::Return(Ok(main.clone()))
@@ -1368,7 +1714,7 @@ fn test_verify_transaction_inclusion_invalid_tx_id_fails() {
         BTCRelay::get_best_block_height.mock_safe(move || MockResult::Return(main_chain_height));
 
         BTCRelay::parse_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof.clone())));
-        BTCRelay::verify_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof_result)));
+        BTCRelay::verify_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof_result.clone())));
 
         BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_block_header)));
 
@@ -1409,12 +1755,14 @@ fn test_verify_transaction_inclusion_invalid_merkle_root_fails() {
 
         let proof = sample_merkle_proof();
         let proof_result = sample_valid_proof_result();
+        let tx_id = proof_result.matched_transactions[0].transaction_hash;
 
         let main = get_empty_block_chain_from_chain_id_and_height(chain_id, start, main_chain_height);
 
         let fork = get_empty_block_chain_from_chain_id_and_height(fork_ref, start, fork_chain_height);
 
         BTCRelay::get_chain_id_from_position.mock_safe(move |_| MockResult::Return(Ok(fork_ref)));
+        mock_chain_work_proportional_to_height();
         BTCRelay::get_block_chain_from_id.mock_safe(move |id| {
             if id == chain_id {
                 MockResult::Return(Ok(main.clone()))
@@ -1434,12 +1782,7 @@ fn test_verify_transaction_inclusion_invalid_merkle_root_fails() {
         BTCRelay::check_parachain_confirmations.mock_safe(|_| MockResult::Return(Ok(())));
 
         assert_err!(
-            BTCRelay::verify_transaction_inclusion(
-                RuntimeOrigin::signed(3),
-                proof_result.transaction_hash,
-                raw_merkle_proof,
-                confirmations,
-            ),
+            BTCRelay::verify_transaction_inclusion(RuntimeOrigin::signed(3), tx_id, raw_merkle_proof, confirmations,),
             TestError::InvalidMerkleProof
         );
     });
@@ -1450,10 +1793,11 @@ fn test_verify_transaction_inclusion_fails_with_ongoing_fork() {
     run_test(|| {
         BTCRelay::get_chain_id_from_position.mock_safe(|_| MockResult::Return(Ok(1)));
         BTCRelay::get_block_chain_from_id.mock_safe(|_| MockResult::Return(Ok(BlockChain::default())));
+        mock_chain_work_proportional_to_height();
         BTCRelay::parse_merkle_proof.mock_safe(|_| MockResult::Return(Ok(sample_merkle_proof())));
         BTCRelay::verify_merkle_proof.mock_safe(|_| MockResult::Return(Ok(sample_valid_proof_result())));
 
-        let tx_id = sample_valid_proof_result().transaction_hash;
+        let tx_id = sample_valid_proof_result().matched_transactions[0].transaction_hash;
         let raw_merkle_proof = vec![0u8; 100];
         let confirmations = None;
 
@@ -1464,6 +1808,96 @@ fn test_verify_transaction_inclusion_fails_with_ongoing_fork() {
     });
 }
 
+fn mock_verify_transactions_inclusion(proof_result: ProofResult) {
+    let chain_id = 0;
+    let start = 10;
+    let main_chain_height = 300;
+    let rich_block_header = sample_rich_tx_block_header(chain_id, main_chain_height);
+    let main = get_empty_block_chain_from_chain_id_and_height(chain_id, start, main_chain_height);
+
+    BTCRelay::get_block_chain_from_id.mock_safe(move |id| {
+        if id == chain_id {
+            MockResult::Return(Ok(main.clone()))
+        } else {
+            MockResult::Return(Err(TestError::InvalidChainID.into()))
+        }
+    });
+    BTCRelay::get_best_block_height.mock_safe(move || MockResult::Return(main_chain_height));
+    BTCRelay::verify_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof_result.clone())));
+    BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_block_header)));
+    BTCRelay::check_bitcoin_confirmations.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+    BTCRelay::check_parachain_confirmations.mock_safe(|_| MockResult::Return(Ok(())));
+}
+
+fn sample_proof_result_with_two_transactions() -> ProofResult {
+    let mut proof_result = sample_valid_proof_result();
+    proof_result.matched_transactions.push(MatchedTransaction {
+        transaction_hash: H256Le::from_hex_be(&sample_example_real_txid()),
+        transaction_position: 1,
+    });
+    proof_result
+}
+
+#[test]
+fn test_verify_transactions_inclusion_succeeds() {
+    run_test(|| {
+        let proof_result = sample_proof_result_with_two_transactions();
+        let tx_ids = proof_result.transaction_hashes();
+        mock_verify_transactions_inclusion(proof_result);
+
+        assert_ok!(BTCRelay::_verify_transactions_inclusion(
+            &tx_ids,
+            sample_merkle_proof(),
+            None
+        ));
+        // a subset of the matched transactions is also accepted
+        assert_ok!(BTCRelay::_verify_transactions_inclusion(
+            &tx_ids[1..],
+            sample_merkle_proof(),
+            None
+        ));
+    });
+}
+
+#[test]
+fn test_verify_transactions_inclusion_fails_if_any_transaction_is_not_matched() {
+    run_test(|| {
+        let proof_result = sample_proof_result_with_two_transactions();
+        let mut tx_ids = proof_result.transaction_hashes();
+        tx_ids.push(H256Le::zero());
+        mock_verify_transactions_inclusion(proof_result);
+
+        assert_err!(
+            BTCRelay::_verify_transactions_inclusion(&tx_ids, sample_merkle_proof(), None),
+            TestError::InvalidTxid
+        );
+    });
+}
+
+#[test]
+fn test_verify_transactions_inclusion_fails_with_invalid_batch_size() {
+    run_test(|| {
+        let raw_merkle_proof = vec![0u8; 100];
+        let raw_tx = hex::decode(sample_example_real_rawtx()).unwrap();
+
+        assert_err!(
+            BTCRelay::verify_transactions_inclusion(
+                RuntimeOrigin::signed(3),
+                raw_merkle_proof,
+                Default::default(),
+                None
+            ),
+            TestError::InvalidBatchSize
+        );
+
+        // oversized batches and transactions are rejected when decoding the call
+        type RawTxs =
+            BoundedVec<BoundedVec<u8, ConstU32<MAX_RAW_TRANSACTION_SIZE>>, ConstU32<MAX_TRANSACTIONS_PER_BATCH>>;
+        assert!(RawTxs::decode(&mut &vec![raw_tx; MAX_TRANSACTIONS_PER_BATCH as usize + 1].encode()[..]).is_err());
+        assert!(RawTxs::decode(&mut &vec![vec![0u8; MAX_RAW_TRANSACTION_SIZE as usize + 1]].encode()[..]).is_err());
+    });
+}
+
 #[test]
 fn test_get_and_verify_issue_payment_with_tx_containing_taproot() {
     run_test(|| {
@@ -1490,6 +1924,32 @@ fn test_get_and_verify_issue_payment_with_tx_containing_taproot() {
     })
 }
 
+#[test]
+fn test_get_and_verify_issue_payment_to_taproot_address() {
+    run_test(|| {
+        let raw_tx = "010000000001013413e41f47eecad702082578c35a2925217056fd0a837b22f1a205fe178a010d0500000000ffffffff19771000000000000017a91415f691c1905082c300362d48540846c30855162d877a1000000000000022512038234fa3e3ca718dfadfb540c320180e68798e67e0a9d4f10d98ea33d37caf047a100000000000001976a914d73838271ee26471aa3640915ed7274b49435b6688acee2000000000000016001470eab26ae0074a58802acc7c38cd9941619c408d14250000000000001976a91479ef95650e8284c3be439d888cf2ee2d1d8ef63088ac3129000000000000160014a558dd2db8167e069f580da2482a9b73dc4f5960217f0000000000001976a91409f3607112083fb1ffe3718214a8e5d5eb0da46188ac04a50000000000001600149215c14609d581aacaa54f629e823cc8abd17ee6c7cd00000000000017a9146da59c9a54a5465402884712bbbe140bc68a4f218728f700000000000017a914ed99cbd06b43b4e3741d1457f7af7b24c2e8d12487ae380100000000001976a91448296f6f29c497f59193ab4e7def5f2e03ef2f9988ac654901000000000017a914ba997376b5daaa3707aefdf30cc09745b579df2187a6a301000000000017a914ffed3c6e71adc2b73939d6951f4655ed1432909b87ec9202000000000017a9147759a1bffe2acca168afdb5b106250b02a703b2887d63603000000000016001439fef3095e8a3bce11ce471aa602bf3e3609d8ddae3703000000000017a914ea0d18bbd804d17a1f2f07ed9aa1670721777d2287cd370300000000001976a914bcc6bcffe584761176d8f510896e882f838208d988ac1d3803000000000016001470eb59ad925fdec71ca0ec50cf7c6b9bbe8dc7592f380300000000001976a91447eb6c94d7b2ac0c11eb3957c0844d333e21d02e88ac724803000000000016001470eb59ad925fdec71ca0ec50cf7c6b9bbe8dc759692e050000000000160014ae26178c1a9b4adb6f24f047fa119e034205900c381b10000000000017a914c9e20b0d7e46d07a878585955ca377db833d181587d32b20000000000017a914bbfcd0b601046e1656ba9b74a98ee8d362d5b63687402f200000000000160014ca146a720a30ca404e979df59d3ddca039e8fd58f22fea0000000000220020935f3eb059cd94bd307e6378bd590724f361f0316fd0964eb5952f274dfb7b4f0400483045022100c9fc44a423e31fc792f5d255ae09ffdc0b224cb70fcebacd52183ce2813ba11d022046c8530230f644be4a05f25bd6a2264b99afc7e3e38531d4bde12d477d03f18001473044022027f50b14154123b173286db76e189a32973a13b0b4ca425329533229cf7f8d9a02202cea81a657ee654c63ab4a01a741931378abae036435a1d695622216596d9e27016952210257bf4070df9735de32305f3bc25320d331edb10c662423e06cd1e50bc58d8fa7210246454540c4e36ba6a481347d0194ffe476640289aecfd2d3f3db1328415b9a5c210248e0a3385d6f744ae81779e10f8ccafbbed7d44debf08a2b0d5250e2f0a0e84853aef0210b00";
+        let tx_bytes = hex::decode(&raw_tx).unwrap();
+        let transaction = BTCRelay::parse_transaction(&tx_bytes).unwrap();
+
+        let raw_proof = "0000402007abe6919ca547e5a9ffe0a11936feef61cb59e7b1f703000000000000000000a53fd3336aa8a18ea00d4127ddb4f4c8d602eee44e271191ee957c257d6b28fc104c4362c0400a17783168d59f0a00000d20a74fa5c909996400a1eaf8ccb08a1fe93f125d260bdbe85f6c46a8ceb0135825c9767aea6bd8d7534a4dcb550332a174a3532aca52665e621c23504d020547dbaa46c7c8fd72b89d3b7dbe1f4f7ca977f3227ad9ce47fc725eb166324662ffdf6b1c856c7a4ec042017fd6c4b10b7b7405d35d2334389b1ea7455f3d94153b3ecfcbaea201e40fdfde250f6a810857bf3ce25af03521a417f44f038e48d7443c46d8574331f1e393ac0c47700544235de90786fca8b6f6d6ce4dce28eabfe82afdf11f4b31ae5209384e56cfc2e103a28f62cd5a269323966a3e29210276fd3fad24c0a2a832ba276dd036b0f50d1d24b12ad239812ffd64cc318f6c28a1a98295da3e28cc22959235808a432225dc101b3c5d545067c8c6553ea89675c7652d914146f9851d78c52802e5dffcbb77ae2f90478f507811c2cece1c2e7b08e5978b8384e6bdf73573b0033a6c2da1494abb5e0b760a00583c106cfdb9b658cecd0d11f35385dbbeb5546bda1144978c674a589e1991e8610aa5ef7480abff3e82bd5bcb91174fd1e896bab2746c9f5fed3faad55d043c1822e7a98f8b8862a104d7ae0500";
+        let proof_bytes = hex::decode(&raw_proof).unwrap();
+        let merkle_proof = BTCRelay::parse_merkle_proof(&proof_bytes).unwrap();
+
+        // check the second output address
+        let raw_address = "38234fa3e3ca718dfadfb540c320180e68798e67e0a9d4f10d98ea33d37caf04";
+        let address_bytes = hex::decode(&raw_address).unwrap();
+        let output_key = H256::from_slice(&address_bytes);
+        let recipient_btc_address = BtcAddress::P2TRv1(output_key);
+
+        BTCRelay::_verify_transaction_inclusion.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+
+        assert_ok!(
+            BTCRelay::get_and_verify_issue_payment::<i64>(merkle_proof, transaction, recipient_btc_address),
+            4218
+        );
+    })
+}
+
 #[test]
 fn test_check_bitcoin_confirmations_insecure_succeeds() {
     run_test(|| {
@@ -1644,6 +2104,93 @@ fn store_generated_block_headers() {
     })
 }
 
+#[test]
+fn store_block_headers_batch_succeeds() {
+    let target = U256::from(2).pow(254.into());
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+    let get_header = |block: &Block| RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
+
+    run_test(|| {
+        let mut last_block = BlockBuilder::new().with_coinbase(&miner, 50, 0).mine(target).unwrap();
+        let last_block_header = BTCRelay::parse_raw_block_header(&get_header(&last_block)).unwrap();
+        assert_ok!(BTCRelay::_initialize(3, last_block_header, 0));
+
+        let mut raw_block_headers = vec![];
+        for i in 1..20 {
+            last_block = BlockBuilder::new()
+                .with_coinbase(&miner, 50, i)
+                .with_previous_hash(last_block.header.hash)
+                .mine(target)
+                .unwrap();
+            raw_block_headers.push(get_header(&last_block));
+        }
+
+        assert_ok!(BTCRelay::store_block_headers(
+            RuntimeOrigin::signed(3),
+            raw_block_headers.try_into().unwrap()
+        ));
+
+        let main_chain: BlockChain = BTCRelay::get_block_chain_from_id(crate::MAIN_CHAIN_ID).unwrap();
+        assert_eq!(main_chain.max_height, 19);
+        assert_eq!(BTCRelay::get_best_block(), last_block.header.hash);
+    })
+}
+
+#[test]
+fn store_block_headers_non_contiguous_batch_fails() {
+    let target = U256::from(2).pow(254.into());
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+    let get_header = |block: &Block| RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
+
+    run_test(|| {
+        let genesis = BlockBuilder::new().with_coinbase(&miner, 50, 0).mine(target).unwrap();
+        let genesis_header = BTCRelay::parse_raw_block_header(&get_header(&genesis)).unwrap();
+        assert_ok!(BTCRelay::_initialize(3, genesis_header, 0));
+
+        // both blocks build on genesis, so the second does not extend the first
+        let block_1 = BlockBuilder::new()
+            .with_coinbase(&miner, 50, 1)
+            .with_previous_hash(genesis.header.hash)
+            .mine(target)
+            .unwrap();
+        let block_2 = BlockBuilder::new()
+            .with_coinbase(&miner, 50, 2)
+            .with_previous_hash(genesis.header.hash)
+            .mine(target)
+            .unwrap();
+
+        assert_noop!(
+            BTCRelay::store_block_headers(
+                RuntimeOrigin::signed(3),
+                vec![get_header(&block_1), get_header(&block_2)].try_into().unwrap()
+            ),
+            TestError::NonContiguousBatch
+        );
+    })
+}
+
+#[test]
+fn store_block_headers_invalid_batch_size_fails() {
+    run_test(|| {
+        assert_noop!(
+            BTCRelay::store_block_headers(RuntimeOrigin::signed(3), Default::default()),
+            TestError::InvalidBatchSize
+        );
+
+        // oversized batches are rejected when decoding the call
+        let raw_block_headers = vec![
+            RawBlockHeader::from_hex(sample_raw_genesis_header()).unwrap();
+            MAX_BLOCK_HEADERS_PER_BATCH as usize + 1
+        ];
+        assert!(
+            BoundedVec::<RawBlockHeader, ConstU32<MAX_BLOCK_HEADERS_PER_BATCH>>::decode(
+                &mut &raw_block_headers.encode()[..]
+            )
+            .is_err()
+        );
+    })
+}
+
 mod op_return_payment_data_tests {
     use super::*;
     use itertools::Itertools;
@@ -1935,6 +2482,170 @@ mod op_return_payment_data_tests {
             }
         })
     }
+
+    #[test]
+    fn test_get_payment_to_returns_paid_amount() {
+        run_test(|| {
+            let amount = 12345;
+            let op_return = H256::from_slice(&[5; 32]);
+            let transaction = TransactionBuilder::new()
+                .with_version(2)
+                .add_output(TransactionOutput::payment(amount, &dummy_address1()))
+                .add_output(TransactionOutput::payment(123, &dummy_address2()))
+                .add_output(TransactionOutput::op_return(0, op_return.as_bytes()))
+                .build();
+
+            for transaction in permutations(transaction) {
+                let payment_data = OpReturnPaymentData::<Test>::try_from(transaction).unwrap();
+                assert_eq!(
+                    payment_data.get_payment_to(dummy_address1(), Some(op_return)),
+                    Ok(amount)
+                );
+                assert_err!(
+                    payment_data.get_payment_to(dummy_address1(), Some(H256::from_slice(&[6; 32]))),
+                    Error::<Test>::InvalidPayment
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_ensure_valid_batch_payment_to_succeeds() {
+        run_test(|| {
+            let op_return_ids = [H256::from_slice(&[5; 32]), H256::from_slice(&[6; 32])];
+            let op_return = OpReturnPaymentData::<Test>::batch_op_return(&op_return_ids);
+            let transaction = TransactionBuilder::new()
+                .with_version(2)
+                .add_output(TransactionOutput::payment(100, &dummy_address1()))
+                .add_output(TransactionOutput::payment(200, &dummy_address2()))
+                .add_output(TransactionOutput::payment(123, &dummy_address3()))
+                .add_output(TransactionOutput::op_return(0, op_return.as_bytes()))
+                .build();
+
+            for transaction in permutations(transaction) {
+                let payment_data = OpReturnPaymentData::<Test>::try_from_batch(transaction, 2).unwrap();
+                assert_ok!(
+                    payment_data
+                        .ensure_valid_batch_payment_to(&[(100, dummy_address1()), (200, dummy_address2())], op_return),
+                    Some(dummy_address3())
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_ensure_valid_batch_payment_to_same_recipient_succeeds() {
+        run_test(|| {
+            let op_return_ids = [H256::from_slice(&[5; 32]), H256::from_slice(&[6; 32])];
+            let op_return = OpReturnPaymentData::<Test>::batch_op_return(&op_return_ids);
+            let transaction = TransactionBuilder::new()
+                .with_version(2)
+                .add_output(TransactionOutput::payment(300, &dummy_address1()))
+                .add_output(TransactionOutput::op_return(0, op_return.as_bytes()))
+                .build();
+
+            for transaction in permutations(transaction) {
+                let payment_data = OpReturnPaymentData::<Test>::try_from_batch(transaction, 2).unwrap();
+                // amounts to the same recipient are paid in a single output
+                assert_ok!(
+                    payment_data
+                        .ensure_valid_batch_payment_to(&[(100, dummy_address1()), (200, dummy_address1())], op_return),
+                    None
+                );
+                assert_err!(
+                    payment_data.ensure_valid_batch_payment_to(&[(100, dummy_address1())], op_return),
+                    Error::<Test>::InvalidPaymentAmount
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_ensure_valid_batch_payment_to_wrong_op_return_fails() {
+        run_test(|| {
+            let op_return_ids = [H256::from_slice(&[5; 32]), H256::from_slice(&[6; 32])];
+            let op_return = OpReturnPaymentData::<Test>::batch_op_return(&op_return_ids);
+            let transaction = TransactionBuilder::new()
+                .with_version(2)
+                .add_output(TransactionOutput::payment(100, &dummy_address1()))
+                .add_output(TransactionOutput::payment(200, &dummy_address2()))
+                .add_output(TransactionOutput::op_return(0, op_return.as_bytes()))
+                .build();
+
+            let payment_data = OpReturnPaymentData::<Test>::try_from_batch(transaction, 2).unwrap();
+            let expected_payments = [(100, dummy_address1()), (200, dummy_address2())];
+            // the commitment depends on the order of the ids
+            let reversed_op_return =
+                OpReturnPaymentData::<Test>::batch_op_return(&[op_return_ids[1], op_return_ids[0]]);
+            assert_err!(
+                payment_data.ensure_valid_batch_payment_to(&expected_payments, reversed_op_return),
+                Error::<Test>::InvalidPayment
+            );
+            // a single request id is not accepted as commitment
+            assert_err!(
+                payment_data.ensure_valid_batch_payment_to(&expected_payments, op_return_ids[0]),
+                Error::<Test>::InvalidPayment
+            );
+        })
+    }
+
+    #[test]
+    fn test_ensure_valid_batch_payment_to_with_two_return_to_self_fails() {
+        run_test(|| {
+            let op_return = H256::from_slice(&[5; 32]);
+            let transaction = TransactionBuilder::new()
+                .with_version(2)
+                .add_output(TransactionOutput::payment(100, &dummy_address1()))
+                .add_output(TransactionOutput::payment(200, &dummy_address2()))
+                .add_output(TransactionOutput::payment(123, &dummy_address3()))
+                .add_output(TransactionOutput::op_return(0, op_return.as_bytes()))
+                .build();
+
+            let payment_data = OpReturnPaymentData::<Test>::try_from_batch(transaction, 2).unwrap();
+            assert_err!(
+                payment_data.ensure_valid_batch_payment_to(&[(100, dummy_address1())], op_return),
+                Error::<Test>::InvalidOpReturnTransaction
+            );
+        })
+    }
+
+    #[test]
+    fn test_constructing_batch_payment_data_with_identical_outputs_fails() {
+        run_test(|| {
+            let transaction = TransactionBuilder::new()
+                .with_version(2)
+                .add_output(TransactionOutput::payment(100, &dummy_address1()))
+                .add_output(TransactionOutput::payment(200, &dummy_address2()))
+                .add_output(TransactionOutput::payment(300, &dummy_address1()))
+                .add_output(TransactionOutput::op_return(0, &[0; 32]))
+                .build();
+
+            for transaction in permutations(transaction) {
+                assert_err!(
+                    OpReturnPaymentData::<Test>::try_from_batch(transaction, 2),
+                    Error::<Test>::InvalidOpReturnTransaction
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_constructing_batch_payment_data_with_too_many_outputs_fails() {
+        run_test(|| {
+            let transaction = TransactionBuilder::new()
+                .with_version(2)
+                .add_output(TransactionOutput::payment(100, &dummy_address1()))
+                .add_output(TransactionOutput::payment(200, &dummy_address2()))
+                .add_output(TransactionOutput::payment(300, &dummy_address3()))
+                .add_output(TransactionOutput::op_return(0, &[0; 32]))
+                .build();
+
+            assert_err!(
+                OpReturnPaymentData::<Test>::try_from_batch(transaction, 1),
+                Error::<Test>::InvalidOpReturnTransaction
+            );
+        })
+    }
 }
 
 #[test]
@@ -1993,6 +2704,7 @@ fn test_check_and_do_reorg() {
         );
 
         BTCRelay::swap_main_blockchain.mock_safe(|_| MockResult::Return(Ok((Default::default(), Default::default()))));
+        mock_chain_work_proportional_to_height();
 
         // we should skip empty `Chains`, this can occur if the
         // previous index is accidentally deleted
@@ -2071,11 +2783,28 @@ fn sample_valid_proof_result() -> ProofResult {
 
     ProofResult {
         extracted_root: merkle_root,
-        transaction_hash: tx_id,
-        transaction_position: 0,
+        matched_transactions: vec![MatchedTransaction {
+            transaction_hash: tx_id,
+            transaction_position: 0,
+        }],
     }
 }
 
+/// Mocks the chain tips such that every block carries the same amount of work, in which
+/// case ordering by chainwork is equivalent to ordering by height
+fn mock_chain_work_proportional_to_height() {
+    BTCRelay::get_chain_tip.mock_safe(|blockchain: &BlockChain| {
+        let block_header = sample_block_header();
+        MockResult::Return(Ok(RichBlockHeader::<BlockNumber> {
+            block_header,
+            block_height: blockchain.max_height,
+            chain_id: blockchain.chain_id,
+            para_height: Default::default(),
+            chain_work: block_header.target.get_block_proof() * blockchain.max_height,
+        }))
+    });
+}
+
 fn get_empty_block_chain_from_chain_id_and_height(chain_id: u32, start_height: u32, block_height: u32) -> BlockChain {
     let blockchain = BlockChain {
         chain_id,
@@ -2097,6 +2826,7 @@ fn sample_parsed_genesis_header(chain_id: u32, block_height: u32) -> RichBlockHe
         block_height,
         chain_id,
         para_height: Default::default(),
+        chain_work: Default::default(),
     }
 }
 
@@ -2120,6 +2850,7 @@ fn sample_parsed_first_block(chain_id: u32, block_height: u32) -> RichBlockHeade
         block_height,
         chain_id,
         para_height: Default::default(),
+        chain_work: Default::default(),
     }
 }
 
@@ -2156,6 +2887,7 @@ fn sample_rich_tx_block_header(chain_id: u32, block_height: u32) -> RichBlockHea
         block_height,
         chain_id,
         para_height: Default::default(),
+        chain_work: Default::default(),
     }
 }
 
@@ -2166,6 +2898,15 @@ fn sample_valid_payment_output() -> TransactionOutput {
     }
 }
 
+fn sample_valid_taproot_payment_output() -> TransactionOutput {
+    TransactionOutput {
+        value: 2500200000,
+        script: "512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"
+            .try_into()
+            .unwrap(),
+    }
+}
+
 fn sample_insufficient_value_payment_output() -> TransactionOutput {
     TransactionOutput {
         value: 100,
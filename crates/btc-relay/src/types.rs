@@ -1,10 +1,13 @@
 use crate::{Error, ACCEPTED_MAX_TRANSACTION_OUTPUTS};
-use bitcoin::types::{BlockHeader, H256Le, Transaction, Value};
 pub use bitcoin::Address as BtcAddress;
+use bitcoin::{
+    types::{BlockHeader, H256Le, Transaction, Value},
+    utils::sha256d_be,
+};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{dispatch::DispatchError, ensure};
 use scale_info::TypeInfo;
-use sp_core::H256;
+use sp_core::{H256, U256};
 use sp_std::{convert::TryFrom, vec::Vec};
 
 /// Bitcoin Enriched Block Headers
@@ -17,6 +20,9 @@ pub struct RichBlockHeader<BlockNumber> {
     pub chain_id: u32,
     /// active_block_number of the parachain at the time this block was submitted
     pub para_height: BlockNumber,
+    /// cumulative proof-of-work of all blocks up to and including this one, counted from the
+    /// block the relay was initialized with
+    pub chain_work: U256,
 }
 
 impl<BlockNumber> RichBlockHeader<BlockNumber> {
@@ -29,12 +35,20 @@ impl<BlockNumber> RichBlockHeader<BlockNumber> {
     /// * `block_height` - chain height
     /// * `account_id` - submitter
     /// * `para_height` - height of the parachain at submission
-    pub fn new(block_header: BlockHeader, chain_id: u32, block_height: u32, para_height: BlockNumber) -> Self {
+    /// * `chain_work` - cumulative chainwork up to and including this block
+    pub fn new(
+        block_header: BlockHeader,
+        chain_id: u32,
+        block_height: u32,
+        para_height: BlockNumber,
+        chain_work: U256,
+    ) -> Self {
         RichBlockHeader {
             block_header,
             block_height,
             chain_id,
             para_height,
+            chain_work,
         }
     }
 
@@ -55,10 +69,70 @@ impl<T: crate::Config> TryFrom<Transaction> for OpReturnPaymentData<T> {
     type Error = DispatchError;
 
     fn try_from(transaction: Transaction) -> Result<Self, Self::Error> {
+        let payment_data = Self::parse_outputs(transaction, ACCEPTED_MAX_TRANSACTION_OUTPUTS)?;
+
+        // Check that we have either 1 payment, or 2 payments to different addresses. Enforcing the
+        // payments to be unique helps to prevent the vault from paying more than is allowed
+        match payment_data.payments.len() {
+            1 => (),
+            2 => {
+                // ensure that the addresses are not identical
+                ensure!(
+                    payment_data.payments[0].1 != payment_data.payments[1].1,
+                    Error::<T>::InvalidOpReturnTransaction
+                );
+            }
+            _ => return Err(Error::<T>::InvalidOpReturnTransaction.into()),
+        }
+
+        Ok(payment_data)
+    }
+}
+
+impl<T: crate::Config> OpReturnPaymentData<T> {
+    /// Parses a transaction that settles multiple requests at once. The transaction must contain a
+    /// single OP_RETURN output committing to all request ids (see [`batch_op_return`](Self::batch_op_return)),
+    /// at most one payment per recipient and at most one additional return-to-self payment.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - the transaction to parse
+    /// * `max_recipients` - the maximum number of distinct recipients that are paid in the batch
+    pub fn try_from_batch(transaction: Transaction, max_recipients: usize) -> Result<Self, DispatchError> {
+        // allow one payment per recipient, plus one return-to-self and the op_return
+        let max_outputs = max_recipients.saturating_add(2);
+        let payment_data = Self::parse_outputs(transaction, max_outputs)?;
+
+        ensure!(
+            !payment_data.payments.is_empty() && payment_data.payments.len() < max_outputs,
+            Error::<T>::InvalidOpReturnTransaction
+        );
+
+        // enforce all addresses to be unique, such that every recipient receives exactly one payment
+        for (index, (_, address)) in payment_data.payments.iter().enumerate() {
+            ensure!(
+                !payment_data.payments[index + 1..]
+                    .iter()
+                    .any(|(_, other)| other == address),
+                Error::<T>::InvalidOpReturnTransaction
+            );
+        }
+
+        Ok(payment_data)
+    }
+
+    /// Computes the OP_RETURN value committing to all ids of the requests settled in a batch, which
+    /// is the double sha256 of the concatenation of the ids, in the given order.
+    pub fn batch_op_return(op_return_ids: &[H256]) -> H256 {
+        let bytes: Vec<u8> = op_return_ids.iter().flat_map(|id| id.as_bytes().to_vec()).collect();
+        sha256d_be(&bytes)
+    }
+
+    fn parse_outputs(transaction: Transaction, max_outputs: usize) -> Result<Self, DispatchError> {
         // check the number of outputs - this check is redundant due to the checks below, but
         // this serves to put an upperbound to the number of iterations
         ensure!(
-            transaction.outputs.len() <= ACCEPTED_MAX_TRANSACTION_OUTPUTS,
+            transaction.outputs.len() <= max_outputs,
             Error::<T>::InvalidOpReturnTransaction
         );
 
@@ -81,45 +155,39 @@ impl<T: crate::Config> TryFrom<Transaction> for OpReturnPaymentData<T> {
         // check we have exactly 1 op-return
         ensure!(op_returns.len() == 1, Error::<T>::InvalidOpReturnTransaction);
 
-        // Check that we have either 1 payment, or 2 payments to different addresses. Enforcing the
-        // payments to be unique helps to prevent the vault from paying more than is allowed
-        match payments.len() {
-            1 => (),
-            2 => {
-                // ensure that the addresses are not identical
-                ensure!(payments[0].1 != payments[1].1, Error::<T>::InvalidOpReturnTransaction);
-            }
-            _ => return Err(Error::<T>::InvalidOpReturnTransaction.into()),
-        }
-
         Ok(Self {
             op_return: op_returns.remove(0),
             payments,
             _marker: Default::default(),
         })
     }
-}
 
-impl<T: crate::Config> OpReturnPaymentData<T> {
-    // ensures this is a valid payment. If it is, it returns the return-to-self address
-    pub fn ensure_valid_payment_to(
-        &self,
-        expected_amount: Value,
-        recipient: BtcAddress,
-        op_return: Option<H256>,
-    ) -> Result<Option<BtcAddress>, DispatchError> {
+    // returns the amount paid to the recipient, provided that the op_return matches
+    pub fn get_payment_to(&self, recipient: BtcAddress, op_return: Option<H256>) -> Result<Value, DispatchError> {
         // make sure the op_return matches
         if let Some(op_return) = op_return {
             ensure!(op_return == self.op_return, Error::<T>::InvalidPayment);
         }
 
-        // ensure we have a correct payment to the recipient
         let paid_amount = self
             .payments
             .iter()
             .find_map(|&(amount, address)| if address == recipient { Some(amount) } else { None })
             .ok_or(Error::<T>::InvalidPayment)?;
 
+        Ok(paid_amount)
+    }
+
+    // ensures this is a valid payment. If it is, it returns the return-to-self address
+    pub fn ensure_valid_payment_to(
+        &self,
+        expected_amount: Value,
+        recipient: BtcAddress,
+        op_return: Option<H256>,
+    ) -> Result<Option<BtcAddress>, DispatchError> {
+        // ensure we have a correct payment to the recipient
+        let paid_amount = self.get_payment_to(recipient, op_return)?;
+
         ensure!(paid_amount == expected_amount, Error::<T>::InvalidPaymentAmount);
 
         // return the return-to-self if it exists, otherwise None
@@ -128,4 +196,56 @@ impl<T: crate::Config> OpReturnPaymentData<T> {
             .iter()
             .find_map(|&(_, address)| if address != recipient { Some(address) } else { None }))
     }
+
+    // ensures this is a valid payment for all given requests. Amounts to the same recipient are
+    // expected in a single output. If valid, it returns the return-to-self address
+    pub fn ensure_valid_batch_payment_to(
+        &self,
+        expected_payments: &[(Value, BtcAddress)],
+        op_return: H256,
+    ) -> Result<Option<BtcAddress>, DispatchError> {
+        // make sure the op_return matches
+        ensure!(op_return == self.op_return, Error::<T>::InvalidPayment);
+
+        // sum up the expected amounts per recipient
+        let mut expected_per_recipient: Vec<(Value, BtcAddress)> = Vec::new();
+        for &(amount, recipient) in expected_payments {
+            match expected_per_recipient
+                .iter_mut()
+                .find(|(_, address)| *address == recipient)
+            {
+                Some((total, _)) => {
+                    *total = total.checked_add(amount).ok_or(Error::<T>::InvalidPaymentAmount)?;
+                }
+                None => expected_per_recipient.push((amount, recipient)),
+            }
+        }
+
+        // ensure we have a correct payment to every recipient
+        for &(expected_amount, recipient) in expected_per_recipient.iter() {
+            let paid_amount = self
+                .payments
+                .iter()
+                .find_map(|&(amount, address)| if address == recipient { Some(amount) } else { None })
+                .ok_or(Error::<T>::InvalidPayment)?;
+
+            ensure!(paid_amount == expected_amount, Error::<T>::InvalidPaymentAmount);
+        }
+
+        // all remaining payments must be return-to-self, of which there can be at most one
+        let mut return_to_self = self.payments.iter().filter_map(|&(_, address)| {
+            if expected_per_recipient
+                .iter()
+                .any(|(_, recipient)| *recipient == address)
+            {
+                None
+            } else {
+                Some(address)
+            }
+        });
+        let return_to_self_address = return_to_self.next();
+        ensure!(return_to_self.next().is_none(), Error::<T>::InvalidOpReturnTransaction);
+
+        Ok(return_to_self_address)
+    }
 }
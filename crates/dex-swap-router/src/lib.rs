@@ -131,33 +131,47 @@ pub mod pallet {
             let now = frame_system::Pallet::<T>::block_number();
             ensure!(deadline > now, Error::<T>::Deadline);
 
-            let mut amount_out = amount_in;
-            let mut receiver = who.clone();
+            Self::inner_swap_exact_token_for_tokens_through_stable_pool(&who, amount_in, amount_out_min, &routes, &to)?;
 
-            for (i, route) in routes.iter().enumerate() {
-                if i == routes.len() - 1 {
-                    receiver = to.clone();
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Swaps exactly `amount_in` held by `who` along the `routes`, sending the output to `to`.
+    /// Returns the amount received.
+    pub fn inner_swap_exact_token_for_tokens_through_stable_pool(
+        who: &T::AccountId,
+        amount_in: T::Balance,
+        amount_out_min: T::Balance,
+        routes: &[Route<T::StablePoolId, T::StableCurrencyId, T::NormalCurrencyId>],
+        to: &T::AccountId,
+    ) -> Result<T::Balance, DispatchError> {
+        let mut amount_out = amount_in;
+        let mut receiver = who.clone();
+
+        for (i, route) in routes.iter().enumerate() {
+            if i == routes.len() - 1 {
+                receiver = to.clone();
+            }
+            match route {
+                Route::Stable(stable_path) => {
+                    (amount_out) = Self::stable_swap(who, stable_path, amount_out, &receiver)?;
                 }
-                match route {
-                    Route::Stable(stable_path) => {
-                        (amount_out) = Self::stable_swap(&who, stable_path, amount_out, &receiver)?;
-                    }
-                    Route::Normal(path) => {
-                        let amounts = T::NormalAmm::get_amount_out_by_path(amount_out.into(), path)?;
-                        Self::swap(&who, amount_out, path, &receiver)?;
-                        amount_out = T::Balance::from(*amounts.last().ok_or(Error::<T>::InvalidPath)?);
-                    }
+                Route::Normal(path) => {
+                    let amounts = T::NormalAmm::get_amount_out_by_path(amount_out.into(), path)?;
+                    Self::swap(who, amount_out, path, &receiver)?;
+                    amount_out = T::Balance::from(*amounts.last().ok_or(Error::<T>::InvalidPath)?);
                 }
             }
+        }
 
-            ensure!(amount_out >= amount_out_min, Error::<T>::AmountSlippage);
+        ensure!(amount_out >= amount_out_min, Error::<T>::AmountSlippage);
 
-            Ok(())
-        }
+        Ok(amount_out)
     }
-}
 
-impl<T: Config> Pallet<T> {
     fn stable_swap(
         who: &T::AccountId,
         path: &StablePath<T::StablePoolId, T::StableCurrencyId>,
@@ -1,9 +1,9 @@
 use super::*;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::BoundedVec;
 use frame_system::RawOrigin;
 use primitives::VaultId;
 
-#[cfg(test)]
 use crate::Pallet as Fee;
 
 const SEED: u32 = 0;
@@ -13,6 +13,66 @@ benchmarks! {
         let nominator: T::AccountId = account("recipient", 0, SEED);
         let vault_id = VaultId::new(nominator.clone(), T::GetWrappedCurrencyId::get(), T::GetWrappedCurrencyId::get());
     }: _(RawOrigin::Signed(nominator), vault_id, None)
+
+    set_commission {
+        let vault_id: DefaultVaultId<T> = VaultId::new(account("Vault", 0, SEED), T::GetWrappedCurrencyId::get(), T::GetWrappedCurrencyId::get());
+        CommissionChangeDelay::<T>::put(T::BlockNumber::from(100u32));
+    }: _(RawOrigin::Signed(vault_id.account_id.clone()), vault_id.currencies.clone(), UnsignedFixedPoint::<T>::checked_from_rational(1, 10).unwrap())
+
+    set_max_commission {
+    }: _(RawOrigin::Root, UnsignedFixedPoint::<T>::checked_from_rational(1, 2).unwrap())
+
+    set_max_commission_step {
+    }: _(RawOrigin::Root, UnsignedFixedPoint::<T>::checked_from_rational(1, 10).unwrap())
+
+    set_commission_change_delay {
+    }: _(RawOrigin::Root, 100u32.into())
+
+    set_auto_compound {
+        let vault_id: DefaultVaultId<T> = VaultId::new(account("Vault", 0, SEED), T::GetWrappedCurrencyId::get(), T::GetWrappedCurrencyId::get());
+        MaxAutoCompoundSlippage::<T>::put(UnsignedFixedPoint::<T>::checked_from_rational(1, 10).unwrap());
+    }: _(RawOrigin::Signed(vault_id.account_id.clone()), vault_id.currencies.clone(), Some(UnsignedFixedPoint::<T>::checked_from_rational(1, 100).unwrap()))
+
+    set_max_auto_compound_slippage {
+    }: _(RawOrigin::Root, UnsignedFixedPoint::<T>::checked_from_rational(1, 10).unwrap())
+
+    set_nominator_auto_compound {
+        let n in 0 .. T::MaxCompoundingNominators::get();
+
+        let vault_id: DefaultVaultId<T> = VaultId::new(account("Vault", 0, SEED), T::GetWrappedCurrencyId::get(), T::GetWrappedCurrencyId::get());
+        let nominator: T::AccountId = account("Nominator", 0, SEED);
+        T::VaultStaking::deposit_stake(&(None, vault_id.clone()), &nominator, 100u32.into()).unwrap();
+
+        // nominators that have withdrawn their stake are dropped to make room
+        let nominators: Vec<T::AccountId> = (0..n).map(|i| account("Withdrawn", i, SEED)).collect();
+        CompoundingNominators::<T>::insert(&vault_id, BoundedVec::try_from(nominators).unwrap());
+    }: _(RawOrigin::Signed(nominator.clone()), vault_id.clone(), true)
+    verify {
+        assert!(CompoundingNominators::<T>::get(&vault_id).contains(&nominator));
+    }
+
+    compound_vault_rewards {
+        let n in 0 .. T::MaxCompoundingNominators::get();
+
+        let vault_id: DefaultVaultId<T> = VaultId::new(account("Vault", 0, SEED), T::GetWrappedCurrencyId::get(), T::GetWrappedCurrencyId::get());
+        let collateral_id = vault_id.collateral_currency();
+        let origin: T::AccountId = account("Origin", 0, SEED);
+        T::CapacityRewards::deposit_stake(&(), &collateral_id, 100u32.into()).unwrap();
+        T::VaultRewards::deposit_stake(&collateral_id, &vault_id, 100u32.into()).unwrap();
+        T::VaultStaking::deposit_stake(&(None, vault_id.clone()), &vault_id.account_id, 100u32.into()).unwrap();
+
+        let nominators: Vec<T::AccountId> = (0..n).map(|i| account("Nominator", i, SEED)).collect();
+        for nominator in nominators.iter() {
+            T::VaultStaking::deposit_stake(&(None, vault_id.clone()), nominator, 100u32.into()).unwrap();
+        }
+        CompoundingNominators::<T>::insert(&vault_id, BoundedVec::try_from(nominators).unwrap());
+        MaxAutoCompoundSlippage::<T>::put(UnsignedFixedPoint::<T>::checked_from_rational(1, 10).unwrap());
+        AutoCompound::<T>::insert(&vault_id, UnsignedFixedPoint::<T>::checked_from_rational(1, 100).unwrap());
+
+        let rewards = Amount::<T>::new(1_000_000u32.into(), T::GetWrappedCurrencyId::get());
+        rewards.mint_to(&Fee::<T>::fee_pool_account_id()).unwrap();
+        Fee::<T>::distribute_rewards(&rewards).unwrap();
+    }: _(RawOrigin::Signed(origin), vault_id.clone())
 }
 
 impl_benchmark_test_suite!(Fee, crate::mock::ExtBuilder::build(), crate::mock::Test);
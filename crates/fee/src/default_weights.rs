@@ -42,6 +42,14 @@ pub trait WeightInfo {
 	fn set_punishment_fee() -> Weight;
 	fn set_replace_griefing_collateral() -> Weight;
 	fn set_commission() -> Weight;
+	fn set_keeper_reward_fee() -> Weight;
+	fn set_max_commission() -> Weight;
+	fn set_max_commission_step() -> Weight;
+	fn set_commission_change_delay() -> Weight;
+	fn set_auto_compound() -> Weight;
+	fn set_max_auto_compound_slippage() -> Weight;
+	fn set_nominator_auto_compound(n: u32, ) -> Weight;
+	fn compound_vault_rewards(n: u32, ) -> Weight;
 }
 
 /// Weights for fee using the Substrate node and recommended hardware.
@@ -102,11 +110,105 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
 	
-	// Storage: Commission (r:0 w:1)
+	// Storage: Fee MaxCommission (r:1 w:0)
+	// Storage: Rewards Stake (r:2 w:0)
+	// Storage: Rewards RewardPerToken (r:2 w:0)
+	// Storage: Rewards RewardTally (r:2 w:2)
+	// Storage: Rewards TotalRewards (r:2 w:2)
+	// Storage: Fee PendingCommission (r:1 w:1)
+	// Storage: Fee Commission (r:1 w:1)
+	// Storage: Staking Nonce (r:1 w:0)
+	// Storage: Staking TotalCurrentStake (r:1 w:0)
+	// Storage: Fee MaxCommissionStep (r:1 w:0)
+	// Storage: Fee CommissionChangeDelay (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
 	fn set_commission() -> Weight {
+		Weight::from_ref_time(58_319_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+
+	// Storage: Fee KeeperRewardFee (r:0 w:1)
+	fn set_keeper_reward_fee() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: Fee MaxCommission (r:0 w:1)
+	fn set_max_commission() -> Weight {
 		Weight::from_ref_time(2_835_000 as u64)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+
+	// Storage: Fee MaxCommissionStep (r:0 w:1)
+	fn set_max_commission_step() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: Fee CommissionChangeDelay (r:0 w:1)
+	fn set_commission_change_delay() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: Fee MaxAutoCompoundSlippage (r:1 w:0)
+	// Storage: Fee AutoCompound (r:0 w:1)
+	fn set_auto_compound() -> Weight {
+		Weight::from_ref_time(12_904_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64)) // Dummy until we rerun benchmarks
+	}
+
+	// Storage: Fee MaxAutoCompoundSlippage (r:0 w:1)
+	fn set_max_auto_compound_slippage() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64)) // Dummy until we rerun benchmarks
+	}
+
+	// Storage: Staking Nonce (r:1 w:0)
+	// Storage: Staking Stake (r:1 w:0)
+	// Storage: Staking SlashPerToken (r:1 w:0)
+	// Storage: Staking SlashTally (r:1 w:0)
+	// Storage: Fee CompoundingNominators (r:1 w:1)
+	fn set_nominator_auto_compound(n: u32, ) -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(10_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64)) // Dummy until we rerun benchmarks
+	}
+
+	// Storage: Fee AutoCompound (r:1 w:0)
+	// Storage: Fee PendingCommission (r:1 w:0)
+	// Storage: Fee Commission (r:1 w:0)
+	// Storage: Fee MaxAutoCompoundSlippage (r:1 w:0)
+	// Storage: Fee CompoundingNominators (r:1 w:0)
+	// Storage: CapacityRewards Stake (r:2 w:0)
+	// Storage: CapacityRewards RewardPerToken (r:2 w:0)
+	// Storage: CapacityRewards RewardTally (r:2 w:2)
+	// Storage: VaultRewards Stake (r:2 w:0)
+	// Storage: VaultRewards RewardPerToken (r:2 w:2)
+	// Storage: VaultRewards RewardTally (r:2 w:2)
+	// Storage: VaultRewards TotalRewards (r:2 w:2)
+	// Storage: VaultStaking Nonce (r:1 w:0)
+	// Storage: VaultStaking TotalCurrentStake (r:2 w:0)
+	// Storage: VaultStaking RewardPerToken (r:2 w:2)
+	// Storage: VaultStaking TotalRewards (r:2 w:2)
+	// Storage: VaultStaking Stake (r:1 w:0)
+	// Storage: VaultStaking SlashPerToken (r:1 w:0)
+	// Storage: VaultStaking SlashTally (r:1 w:0)
+	// Storage: VaultStaking RewardTally (r:2 w:2)
+	// Storage: DexGeneral PairStatuses (r:2 w:0)
+	// Storage: Tokens Accounts (r:6 w:6)
+	fn compound_vault_rewards(n: u32, ) -> Weight {
+		Weight::from_ref_time(250_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(120_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(38 as u64))
+			.saturating_add(T::DbWeight::get().reads((12 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(24 as u64))
+			.saturating_add(T::DbWeight::get().writes((10 as u64).saturating_mul(n as u64))) // Dummy until we rerun benchmarks
+	}
 }
 
 // For backwards compatibility and tests
@@ -166,10 +268,104 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
 
-	// Storage: Fee Commission (r:0 w:1)
+	// Storage: Fee MaxCommission (r:1 w:0)
+	// Storage: Rewards Stake (r:2 w:0)
+	// Storage: Rewards RewardPerToken (r:2 w:0)
+	// Storage: Rewards RewardTally (r:2 w:2)
+	// Storage: Rewards TotalRewards (r:2 w:2)
+	// Storage: Fee PendingCommission (r:1 w:1)
+	// Storage: Fee Commission (r:1 w:1)
+	// Storage: Staking Nonce (r:1 w:0)
+	// Storage: Staking TotalCurrentStake (r:1 w:0)
+	// Storage: Fee MaxCommissionStep (r:1 w:0)
+	// Storage: Fee CommissionChangeDelay (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
 	fn set_commission() -> Weight {
+		Weight::from_ref_time(58_319_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(16 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+
+	// Storage: Fee KeeperRewardFee (r:0 w:1)
+	fn set_keeper_reward_fee() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: Fee MaxCommission (r:0 w:1)
+	fn set_max_commission() -> Weight {
 		Weight::from_ref_time(2_835_000 as u64)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+
+	// Storage: Fee MaxCommissionStep (r:0 w:1)
+	fn set_max_commission_step() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: Fee CommissionChangeDelay (r:0 w:1)
+	fn set_commission_change_delay() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: Fee MaxAutoCompoundSlippage (r:1 w:0)
+	// Storage: Fee AutoCompound (r:0 w:1)
+	fn set_auto_compound() -> Weight {
+		Weight::from_ref_time(12_904_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64)) // dummy until we rerun benchmarks
+	}
+
+	// Storage: Fee MaxAutoCompoundSlippage (r:0 w:1)
+	fn set_max_auto_compound_slippage() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64)) // dummy until we rerun benchmarks
+	}
+
+	// Storage: Staking Nonce (r:1 w:0)
+	// Storage: Staking Stake (r:1 w:0)
+	// Storage: Staking SlashPerToken (r:1 w:0)
+	// Storage: Staking SlashTally (r:1 w:0)
+	// Storage: Fee CompoundingNominators (r:1 w:1)
+	fn set_nominator_auto_compound(n: u32, ) -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(10_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64)) // dummy until we rerun benchmarks
+	}
+
+	// Storage: Fee AutoCompound (r:1 w:0)
+	// Storage: Fee PendingCommission (r:1 w:0)
+	// Storage: Fee Commission (r:1 w:0)
+	// Storage: Fee MaxAutoCompoundSlippage (r:1 w:0)
+	// Storage: Fee CompoundingNominators (r:1 w:0)
+	// Storage: CapacityRewards Stake (r:2 w:0)
+	// Storage: CapacityRewards RewardPerToken (r:2 w:0)
+	// Storage: CapacityRewards RewardTally (r:2 w:2)
+	// Storage: VaultRewards Stake (r:2 w:0)
+	// Storage: VaultRewards RewardPerToken (r:2 w:2)
+	// Storage: VaultRewards RewardTally (r:2 w:2)
+	// Storage: VaultRewards TotalRewards (r:2 w:2)
+	// Storage: VaultStaking Nonce (r:1 w:0)
+	// Storage: VaultStaking TotalCurrentStake (r:2 w:0)
+	// Storage: VaultStaking RewardPerToken (r:2 w:2)
+	// Storage: VaultStaking TotalRewards (r:2 w:2)
+	// Storage: VaultStaking Stake (r:1 w:0)
+	// Storage: VaultStaking SlashPerToken (r:1 w:0)
+	// Storage: VaultStaking SlashTally (r:1 w:0)
+	// Storage: VaultStaking RewardTally (r:2 w:2)
+	// Storage: DexGeneral PairStatuses (r:2 w:0)
+	// Storage: Tokens Accounts (r:6 w:6)
+	fn compound_vault_rewards(n: u32, ) -> Weight {
+		Weight::from_ref_time(250_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(120_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(38 as u64))
+			.saturating_add(RocksDbWeight::get().reads((12 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(24 as u64))
+			.saturating_add(RocksDbWeight::get().writes((10 as u64).saturating_mul(n as u64))) // dummy until we rerun benchmarks
+	}
 }
 
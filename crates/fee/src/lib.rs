@@ -46,8 +46,10 @@ use sp_runtime::{
 use sp_std::{
     convert::{TryFrom, TryInto},
     fmt::Debug,
+    vec::Vec,
 };
 use staking::StakingApi;
+use traits::{DexSwap, NominationApi};
 use types::{BalanceOf, DefaultVaultCurrencyPair, DefaultVaultId, SignedFixedPoint, UnsignedFixedPoint, Version};
 
 #[frame_support::pallet]
@@ -64,6 +66,9 @@ pub mod pallet {
         + security::Config
         + currency::Config<UnsignedFixedPoint = UnsignedFixedPoint<Self>, SignedFixedPoint = SignedFixedPoint<Self>>
     {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
         /// The fee module id, used for deriving its sovereign account ID.
         #[pallet::constant]
         type FeePalletId: Get<PalletId>;
@@ -116,7 +121,7 @@ pub mod pallet {
         >;
 
         /// Vault staking pool.
-        type VaultStaking: StakingApi<DefaultVaultId<Self>, Self::Index, BalanceOf<Self>>
+        type VaultStaking: StakingApi<DefaultVaultId<Self>, Self::Index, Self::AccountId, BalanceOf<Self>>
             + RewardsApi<
                 (Option<Self::Index>, DefaultVaultId<Self>),
                 Self::AccountId,
@@ -127,17 +132,69 @@ pub mod pallet {
         /// Handler to transfer undistributed rewards.
         type OnSweep: OnSweep<Self::AccountId, Amount<Self>>;
 
+        /// Swaps auto-compounded rewards into the vault's collateral currency.
+        type DexSwap: DexSwap<Self::AccountId, CurrencyId<Self>, BalanceOf<Self>>;
+
+        /// Deposits auto-compounded rewards as collateral on behalf of the stakeholders.
+        type Nomination: NominationApi<DefaultVaultId<Self>, Self::AccountId, Amount<Self>>;
+
+        /// The maximum number of nominators per vault that can opt in to auto-compounding.
+        #[pallet::constant]
+        type MaxCompoundingNominators: Get<u32>;
+
         /// Maximum expected value to set the storage fields to.
         #[pallet::constant]
         type MaxExpectedValue: Get<UnsignedFixedPoint<Self>>;
     }
 
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        CommissionChangeAnnounced {
+            vault_id: DefaultVaultId<T>,
+            commission: UnsignedFixedPoint<T>,
+            apply_height: T::BlockNumber,
+        },
+        CommissionChanged {
+            vault_id: DefaultVaultId<T>,
+            commission: UnsignedFixedPoint<T>,
+        },
+        AutoCompoundSet {
+            vault_id: DefaultVaultId<T>,
+            max_slippage: Option<UnsignedFixedPoint<T>>,
+        },
+        NominatorAutoCompoundSet {
+            vault_id: DefaultVaultId<T>,
+            nominator_id: T::AccountId,
+            enabled: bool,
+        },
+        CompoundRewards {
+            vault_id: DefaultVaultId<T>,
+            nominator_id: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+    }
+
     #[pallet::error]
     pub enum Error<T> {
         /// Unable to convert value.
         TryIntoIntError,
         /// Value exceeds the expected upper bound for storage fields in this pallet.
         AboveMaxExpectedValue,
+        /// Commission exceeds the maximum allowed by governance.
+        AboveMaxCommission,
+        /// Commission increases by more than the maximum allowed step.
+        AboveMaxCommissionStep,
+        /// Slippage exceeds the maximum allowed by governance.
+        AboveMaxSlippage,
+        /// No DEX is available to swap rewards into collateral.
+        AutoCompoundUnavailable,
+        /// Only accounts with stake in the vault can opt in to auto-compounding.
+        NoStakeInVault,
+        /// The maximum number of nominators that opted in to auto-compounding has been reached.
+        TooManyCompoundingNominators,
+        /// The vault has not opted in to auto-compounding.
+        AutoCompoundNotEnabled,
     }
 
     #[pallet::hooks]
@@ -178,6 +235,13 @@ pub mod pallet {
     #[pallet::getter(fn punishment_fee)]
     pub type PunishmentFee<T: Config> = StorageValue<_, UnsignedFixedPoint<T>, ValueQuery>;
 
+    /// Fee that a Vault has to pay, on top of the punishment fee, to the account
+    /// cancelling an expired redeem request on behalf of the redeemer. The fee is
+    /// paid in collateral based on the token amount at the current exchange rate.
+    #[pallet::storage]
+    #[pallet::getter(fn keeper_reward_fee)]
+    pub type KeeperRewardFee<T: Config> = StorageValue<_, UnsignedFixedPoint<T>, ValueQuery>;
+
     /// # Replace
 
     /// Default griefing collateral (e.g. DOT/KSM) as a percentage of the to-be-locked collateral
@@ -202,6 +266,55 @@ pub mod pallet {
     pub(super) type Commission<T: Config> =
         StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, UnsignedFixedPoint<T>, OptionQuery>;
 
+    /// Commission changes that were announced but not yet applied, along with the
+    /// (active) block height from which they apply.
+    #[pallet::storage]
+    pub(super) type PendingCommission<T: Config> =
+        StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, (UnsignedFixedPoint<T>, T::BlockNumber), OptionQuery>;
+
+    #[pallet::type_value]
+    pub(super) fn DefaultForMaxCommission<T: Config>() -> UnsignedFixedPoint<T> {
+        UnsignedFixedPoint::<T>::one()
+    }
+
+    /// The maximum commission any vault can charge.
+    #[pallet::storage]
+    #[pallet::getter(fn max_commission)]
+    pub type MaxCommission<T: Config> = StorageValue<_, UnsignedFixedPoint<T>, ValueQuery, DefaultForMaxCommission<T>>;
+
+    /// The maximum increase of a vault's commission in a single change.
+    #[pallet::storage]
+    #[pallet::getter(fn max_commission_step)]
+    pub type MaxCommissionStep<T: Config> =
+        StorageValue<_, UnsignedFixedPoint<T>, ValueQuery, DefaultForMaxCommission<T>>;
+
+    /// The number of (active) blocks between announcing a commission change and applying it.
+    #[pallet::storage]
+    #[pallet::getter(fn commission_change_delay)]
+    pub type CommissionChangeDelay<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Vaults whose rewards are swapped into collateral and deposited on behalf of their
+    /// stakeholders, along with the maximum slippage allowed relative to the oracle price.
+    #[pallet::storage]
+    #[pallet::getter(fn auto_compound)]
+    pub type AutoCompound<T: Config> =
+        StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, UnsignedFixedPoint<T>, OptionQuery>;
+
+    /// The maximum slippage vaults may allow when auto-compounding.
+    #[pallet::storage]
+    #[pallet::getter(fn max_auto_compound_slippage)]
+    pub type MaxAutoCompoundSlippage<T: Config> = StorageValue<_, UnsignedFixedPoint<T>, ValueQuery>;
+
+    /// Nominators that opted in to having their rewards from the vault auto-compounded.
+    #[pallet::storage]
+    pub type CompoundingNominators<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultId<T>,
+        BoundedVec<T::AccountId, T::MaxCompoundingNominators>,
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub issue_fee: UnsignedFixedPoint<T>,
@@ -373,7 +486,13 @@ pub mod pallet {
             Ok(().into())
         }
 
-        /// todo: proper weight
+        /// Announce a new commission for the vault, which applies after the commission change delay.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the vault operator
+        /// * `currencies` - the currency pair of the vault
+        /// * `commission` - the new commission
         #[pallet::call_index(7)]
         #[pallet::weight(<T as Config>::WeightInfo::set_commission())]
         #[transactional]
@@ -384,11 +503,206 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let account_id = ensure_signed(origin)?;
             let vault_id = VaultId::from_pair(account_id, currencies);
+            Self::_set_commission(&vault_id, commission)?;
+            Ok(().into())
+        }
+
+        /// Changes the keeper reward fee percentage (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - signing account
+        /// * `fee` - the new fee
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_keeper_reward_fee())]
+        #[transactional]
+        pub fn set_keeper_reward_fee(origin: OriginFor<T>, fee: UnsignedFixedPoint<T>) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(fee <= Self::get_max_expected_value(), Error::<T>::AboveMaxExpectedValue);
+            KeeperRewardFee::<T>::put(fee);
+            Ok(().into())
+        }
+
+        /// Changes the maximum commission of vaults (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - signing account
+        /// * `commission` - the new maximum commission
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_commission())]
+        #[transactional]
+        pub fn set_max_commission(
+            origin: OriginFor<T>,
+            commission: UnsignedFixedPoint<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
             ensure!(
                 commission <= UnsignedFixedPoint::<T>::one(),
                 Error::<T>::AboveMaxExpectedValue
             );
-            Commission::<T>::insert(vault_id, commission);
+            MaxCommission::<T>::put(commission);
+            Ok(().into())
+        }
+
+        /// Changes the maximum commission increase per change (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - signing account
+        /// * `step` - the new maximum step
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_commission_step())]
+        #[transactional]
+        pub fn set_max_commission_step(
+            origin: OriginFor<T>,
+            step: UnsignedFixedPoint<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                step <= UnsignedFixedPoint::<T>::one(),
+                Error::<T>::AboveMaxExpectedValue
+            );
+            MaxCommissionStep::<T>::put(step);
+            Ok(().into())
+        }
+
+        /// Changes the delay between announcing and applying commission changes (only executable by the Root
+        /// account)
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - signing account
+        /// * `delay` - the new delay in (active) blocks
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_commission_change_delay())]
+        #[transactional]
+        pub fn set_commission_change_delay(origin: OriginFor<T>, delay: T::BlockNumber) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            CommissionChangeDelay::<T>::put(delay);
+            Ok(().into())
+        }
+
+        /// Opt the vault in to or out of auto-compounding its rewards into collateral. The rewards
+        /// are compounded whenever `compound_vault_rewards` is called for the vault.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the vault operator
+        /// * `currencies` - the currency pair of the vault
+        /// * `max_slippage` - the maximum slippage of the swaps, or `None` to opt out
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_auto_compound())]
+        #[transactional]
+        pub fn set_auto_compound(
+            origin: OriginFor<T>,
+            currencies: DefaultVaultCurrencyPair<T>,
+            max_slippage: Option<UnsignedFixedPoint<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::from_pair(account_id, currencies);
+            if let Some(max_slippage) = max_slippage {
+                ensure!(T::DexSwap::is_available(), Error::<T>::AutoCompoundUnavailable);
+                ensure!(
+                    max_slippage <= Self::max_auto_compound_slippage(),
+                    Error::<T>::AboveMaxSlippage
+                );
+                AutoCompound::<T>::insert(&vault_id, max_slippage);
+            } else {
+                AutoCompound::<T>::remove(&vault_id);
+            }
+            Self::deposit_event(Event::<T>::AutoCompoundSet { vault_id, max_slippage });
+            Ok(().into())
+        }
+
+        /// Changes the maximum slippage vaults may allow when auto-compounding (only executable by the Root
+        /// account)
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - signing account
+        /// * `max_slippage` - the new maximum slippage
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_auto_compound_slippage())]
+        #[transactional]
+        pub fn set_max_auto_compound_slippage(
+            origin: OriginFor<T>,
+            max_slippage: UnsignedFixedPoint<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                max_slippage <= UnsignedFixedPoint::<T>::one(),
+                Error::<T>::AboveMaxExpectedValue
+            );
+            MaxAutoCompoundSlippage::<T>::put(max_slippage);
+            Ok(().into())
+        }
+
+        /// Opt the nominator in to or out of auto-compounding its rewards from the vault, which only
+        /// takes effect while the vault itself auto-compounds.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the nominator
+        /// * `vault_id` - the vault the nominator has stake in
+        /// * `enabled` - whether to auto-compound the rewards
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_nominator_auto_compound(T::MaxCompoundingNominators::get()))]
+        #[transactional]
+        pub fn set_nominator_auto_compound(
+            origin: OriginFor<T>,
+            vault_id: DefaultVaultId<T>,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            let nominator_id = ensure_signed(origin)?;
+            if enabled {
+                ensure!(T::DexSwap::is_available(), Error::<T>::AutoCompoundUnavailable);
+                ensure!(
+                    !Self::get_stake(&vault_id, &nominator_id)?.is_zero(),
+                    Error::<T>::NoStakeInVault
+                );
+                CompoundingNominators::<T>::try_mutate(&vault_id, |nominators| {
+                    if nominators.contains(&nominator_id) {
+                        return Ok(());
+                    }
+                    if nominators.len() as u32 >= T::MaxCompoundingNominators::get() {
+                        // make room by dropping nominators that have since withdrawn their stake
+                        nominators.retain(|account_id| {
+                            Self::get_stake(&vault_id, account_id).map_or(true, |stake| !stake.is_zero())
+                        });
+                    }
+                    nominators
+                        .try_push(nominator_id.clone())
+                        .map_err(|_| Error::<T>::TooManyCompoundingNominators)
+                })?;
+            } else {
+                CompoundingNominators::<T>::mutate(&vault_id, |nominators| {
+                    nominators.retain(|account_id| account_id != &nominator_id)
+                });
+            }
+            Self::deposit_event(Event::<T>::NominatorAutoCompoundSet {
+                vault_id,
+                nominator_id,
+                enabled,
+            });
+            Ok(().into())
+        }
+
+        /// Swap the rewards of a vault that opted in to auto-compounding, and of its nominators that
+        /// opted in, into collateral and deposit it on their behalf. Callable by anyone.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - signing account
+        /// * `vault_id` - the vault whose rewards to compound
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::compound_vault_rewards(T::MaxCompoundingNominators::get()))]
+        #[transactional]
+        pub fn compound_vault_rewards(origin: OriginFor<T>, vault_id: DefaultVaultId<T>) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let max_slippage = Self::auto_compound(&vault_id).ok_or(Error::<T>::AutoCompoundNotEnabled)?;
+            Self::apply_pending_commission(&vault_id)?;
+            Self::_compound_vault_rewards(&vault_id, max_slippage)?;
             Ok(().into())
         }
     }
@@ -397,9 +711,83 @@ pub mod pallet {
 // "Internal" functions, callable by code.
 #[cfg_attr(test, mockable)]
 impl<T: Config> Pallet<T> {
-    fn get_commission_rate(vault_id: &DefaultVaultId<T>) -> UnsignedFixedPoint<T> {
-        Commission::<T>::get(vault_id).unwrap_or(<UnsignedFixedPoint<T>>::zero())
+    fn _set_commission(vault_id: &DefaultVaultId<T>, commission: UnsignedFixedPoint<T>) -> DispatchResult {
+        ensure!(commission <= Self::max_commission(), Error::<T>::AboveMaxCommission);
+
+        // outstanding rewards are paid out at the commission that was in effect while they accrued
+        Self::distribute_all_vault_rewards(vault_id)?;
+
+        let current = Self::get_commission_rate(vault_id);
+        ensure!(
+            commission <= current.saturating_add(Self::max_commission_step()),
+            Error::<T>::AboveMaxCommissionStep
+        );
+
+        let delay = Self::commission_change_delay();
+        if delay.is_zero() {
+            PendingCommission::<T>::remove(vault_id);
+            Self::apply_commission(vault_id, commission);
+        } else {
+            // replaces any previously announced change
+            let apply_height = <security::Pallet<T>>::active_block_number().saturating_add(delay);
+            PendingCommission::<T>::insert(vault_id, (commission, apply_height));
+            Self::deposit_event(Event::<T>::CommissionChangeAnnounced {
+                vault_id: vault_id.clone(),
+                commission,
+                apply_height,
+            });
+        }
+        Ok(())
     }
+
+    fn apply_commission(vault_id: &DefaultVaultId<T>, commission: UnsignedFixedPoint<T>) {
+        Commission::<T>::insert(vault_id, commission);
+        Self::deposit_event(Event::<T>::CommissionChanged {
+            vault_id: vault_id.clone(),
+            commission,
+        });
+    }
+
+    /// Apply the announced commission change of the vault if its delay has passed. Rewards that
+    /// are still outstanding accrued under the previous commission, so they are distributed first.
+    fn apply_pending_commission(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        match PendingCommission::<T>::get(vault_id) {
+            Some((commission, apply_height)) if apply_height <= <security::Pallet<T>>::active_block_number() => {
+                // remove the change before distributing so that it is not applied again
+                PendingCommission::<T>::remove(vault_id);
+                Self::distribute_all_vault_rewards(vault_id)?;
+                Self::apply_commission(vault_id, commission);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Get the commission currently charged by the vault.
+    pub fn get_commission_rate(vault_id: &DefaultVaultId<T>) -> UnsignedFixedPoint<T> {
+        let commission = Commission::<T>::get(vault_id).unwrap_or(<UnsignedFixedPoint<T>>::zero());
+        // a lowered maximum applies to existing commissions as well
+        commission.min(Self::max_commission())
+    }
+
+    /// Get the commission in effect for the vault, including an announced change whose delay has
+    /// passed but which has not been applied yet.
+    pub fn get_effective_commission_rate(vault_id: &DefaultVaultId<T>) -> UnsignedFixedPoint<T> {
+        match PendingCommission::<T>::get(vault_id) {
+            Some((commission, apply_height)) if apply_height <= <security::Pallet<T>>::active_block_number() => {
+                commission.min(Self::max_commission())
+            }
+            _ => Self::get_commission_rate(vault_id),
+        }
+    }
+
+    /// Get the announced commission change of the vault and the (active) block height from which it applies,
+    /// unless that height has already been reached.
+    pub fn get_pending_commission(vault_id: &DefaultVaultId<T>) -> Option<(UnsignedFixedPoint<T>, T::BlockNumber)> {
+        PendingCommission::<T>::get(vault_id)
+            .filter(|(_, apply_height)| *apply_height > <security::Pallet<T>>::active_block_number())
+    }
+
     /// The account ID of the fee pool.
     ///
     /// This actually does computation. If you need to keep using it, then make sure you cache the
@@ -477,6 +865,16 @@ impl<T: Config> Pallet<T> {
         amount.checked_rounded_mul(&<PunishmentFee<T>>::get(), Rounding::NearestPrefUp)
     }
 
+    /// Calculate the reward for cancelling an expired redeem request on behalf
+    /// of the redeemer.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - amount in collateral (at current exchange rate)
+    pub fn get_keeper_reward_fee(amount: &Amount<T>) -> Result<Amount<T>, DispatchError> {
+        amount.checked_rounded_mul(&<KeeperRewardFee<T>>::get(), Rounding::Down)
+    }
+
     /// Calculate the required replace griefing collateral.
     ///
     /// # Arguments
@@ -513,6 +911,11 @@ impl<T: Config> Pallet<T> {
 
     // Private functions internal to this pallet
 
+    /// Get the stake of the `nominator_id` in the current staking pool of the vault.
+    fn get_stake(vault_id: &DefaultVaultId<T>, nominator_id: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+        T::VaultStaking::get_stake(&(None, vault_id.clone()), nominator_id)
+    }
+
     /// Withdraw rewards from a pool and transfer to `account_id`.
     fn withdraw_vault_rewards(
         vault_id: &DefaultVaultId<T>,
@@ -542,6 +945,20 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn distribute_vault_rewards(vault_id: &DefaultVaultId<T>, currency_id: CurrencyId<T>) -> DispatchResult {
+        Self::apply_pending_commission(vault_id)?;
+
+        let commission = Self::push_vault_rewards(vault_id, currency_id)?;
+        commission.transfer(&Self::fee_pool_account_id(), &vault_id.account_id)?;
+
+        Ok(())
+    }
+
+    /// Push the rewards of the vault into its staking pool. Returns the commission, which is left
+    /// in the fee pool.
+    fn push_vault_rewards(
+        vault_id: &DefaultVaultId<T>,
+        currency_id: CurrencyId<T>,
+    ) -> Result<Amount<T>, DispatchError> {
         let collateral_id = vault_id.collateral_currency();
 
         // push rewards based on collateral capacity
@@ -555,12 +972,122 @@ impl<T: Config> Pallet<T> {
 
         let commission_rate = Self::get_commission_rate(vault_id);
         let commission = full_amount.checked_mul(&commission_rate)?;
-        commission.transfer(&Self::fee_pool_account_id(), &vault_id.account_id)?;
 
         let remainder = full_amount.checked_sub(&commission)?;
 
         T::VaultStaking::distribute_reward(&(None, vault_id.clone()), currency_id, remainder.amount())?;
 
+        Ok(commission)
+    }
+
+    /// Swap the rewards of the vault operator and of the nominators that opted in into collateral and deposit
+    /// it on their behalf.
+    fn _compound_vault_rewards(vault_id: &DefaultVaultId<T>, max_slippage: UnsignedFixedPoint<T>) -> DispatchResult {
+        let collateral_id = vault_id.collateral_currency();
+        let fee_pool_account_id = Self::fee_pool_account_id();
+
+        // pull the rewards of all currencies first: depositing collateral distributes the vault
+        // rewards again, which must then find no commission left to pay out
+        let mut commissions = Vec::new();
+        for currency_id in [vault_id.wrapped_currency(), T::GetNativeCurrencyId::get()] {
+            let commission = Self::push_vault_rewards(vault_id, currency_id)?;
+            commissions.push(commission);
+        }
+
+        // a lowered maximum applies to vaults that already opted in as well
+        let max_slippage = max_slippage.min(Self::max_auto_compound_slippage());
+
+        // the vault operator comes first since it receives the commission, the rewards of nominators
+        // that did not opt in are left to be withdrawn as usual
+        let mut stakeholders = CompoundingNominators::<T>::get(vault_id).into_inner();
+        stakeholders.retain(|stakeholder| stakeholder != &vault_id.account_id);
+        stakeholders.insert(0, vault_id.account_id.clone());
+        let mut compounded: Vec<Amount<T>> = stakeholders.iter().map(|_| Amount::zero(collateral_id)).collect();
+
+        for commission in commissions {
+            // rewards that cannot be swapped are left to be withdrawn as usual
+            match storage::with_storage_layer(|| {
+                Self::swap_vault_rewards(vault_id, &stakeholders, &commission, max_slippage)
+            }) {
+                Ok(shares) => {
+                    for (total, share) in compounded.iter_mut().zip(shares.iter()) {
+                        *total = total.checked_add(share)?;
+                    }
+                }
+                Err(_) => commission.transfer(&fee_pool_account_id, &vault_id.account_id)?,
+            }
+        }
+
+        for (nominator_id, amount) in stakeholders.iter().zip(compounded.iter()) {
+            if amount.is_zero() {
+                continue;
+            }
+            amount.transfer(&fee_pool_account_id, nominator_id)?;
+            // a failed deposit, e.g. due to the nomination limit, leaves the collateral with the stakeholder
+            if storage::with_storage_layer(|| {
+                T::Nomination::deposit_nominator_collateral(vault_id, nominator_id, amount)
+            })
+            .is_ok()
+            {
+                Self::deposit_event(Event::<T>::CompoundRewards {
+                    vault_id: vault_id.clone(),
+                    nominator_id: nominator_id.clone(),
+                    amount: amount.amount(),
+                });
+            }
+        }
+
         Ok(())
     }
+
+    /// Withdraw the rewards of the stakeholders in the currency of the `commission` and swap them
+    /// into collateral. Returns the collateral owed to each stakeholder, in order.
+    fn swap_vault_rewards(
+        vault_id: &DefaultVaultId<T>,
+        stakeholders: &[T::AccountId],
+        commission: &Amount<T>,
+        max_slippage: UnsignedFixedPoint<T>,
+    ) -> Result<Vec<Amount<T>>, DispatchError> {
+        let currency_id = commission.currency();
+        let collateral_id = vault_id.collateral_currency();
+
+        let mut rewards = Vec::with_capacity(stakeholders.len());
+        for stakeholder in stakeholders.iter() {
+            let reward = T::VaultStaking::withdraw_reward(&(None, vault_id.clone()), stakeholder, currency_id)?;
+            rewards.push(Amount::<T>::new(reward, currency_id));
+        }
+        rewards[0] = rewards[0].checked_add(commission)?;
+
+        let total = rewards
+            .iter()
+            .try_fold(Amount::zero(currency_id), |total, reward| total.checked_add(reward))?;
+        if total.is_zero() {
+            return Ok(stakeholders.iter().map(|_| Amount::zero(collateral_id)).collect());
+        }
+
+        let collateral = if currency_id == collateral_id {
+            total.clone()
+        } else {
+            let expected = total.convert_to(collateral_id)?;
+            let min_out = expected.checked_mul(&UnsignedFixedPoint::<T>::one().saturating_sub(max_slippage))?;
+            let amount_out = T::DexSwap::swap_exact_amount_in(
+                &Self::fee_pool_account_id(),
+                total.amount(),
+                min_out.amount(),
+                &[currency_id, collateral_id],
+            )?;
+            Amount::new(amount_out, collateral_id)
+        };
+
+        // split pro rata, rounding down, with the dust going to the vault operator
+        let mut shares = Vec::with_capacity(stakeholders.len());
+        let mut remaining = collateral.clone();
+        for reward in rewards.iter().skip(1) {
+            let share = collateral.checked_mul(&reward.ratio(&total)?)?;
+            remaining = remaining.checked_sub(&share)?;
+            shares.push(share);
+        }
+        shares.insert(0, remaining);
+        Ok(shares)
+    }
 }
@@ -1,7 +1,7 @@
 use crate as fee;
 use crate::{Config, Error};
 use frame_support::{
-    parameter_types,
+    ensure, parameter_types,
     traits::{ConstU32, Everything},
     PalletId,
 };
@@ -14,7 +14,7 @@ use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup, Zero},
-    FixedPointNumber,
+    DispatchError, FixedPointNumber,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -39,7 +39,7 @@ frame_support::construct_runtime!(
 
         // Operational
         Security: security::{Pallet, Call, Storage, Event<T>},
-        Fee: fee::{Pallet, Call, Config<T>, Storage},
+        Fee: fee::{Pallet, Call, Config<T>, Storage, Event<T>},
     }
 );
 
@@ -163,10 +163,10 @@ impl security::Config for Test {
 pub struct CurrencyConvert;
 impl currency::CurrencyConversion<currency::Amount<Test>, CurrencyId> for CurrencyConvert {
     fn convert(
-        _amount: &currency::Amount<Test>,
-        _to: CurrencyId,
+        amount: &currency::Amount<Test>,
+        to: CurrencyId,
     ) -> Result<currency::Amount<Test>, sp_runtime::DispatchError> {
-        unimplemented!()
+        Ok(currency::Amount::new(amount.amount(), to))
     }
 }
 
@@ -187,6 +187,7 @@ parameter_types! {
 }
 
 impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type FeePalletId = FeePalletId;
     type WeightInfo = ();
     type SignedFixedPoint = SignedFixedPoint;
@@ -197,8 +198,56 @@ impl Config for Test {
     type VaultRewards = VaultRewards;
     type VaultStaking = VaultStaking;
     type OnSweep = ();
+    type DexSwap = MockSwap;
+    type Nomination = MockNomination;
+    type MaxCompoundingNominators = ConstU32<2>;
     type MaxExpectedValue = MaxExpectedValue;
 }
+pub struct MockSwap;
+impl traits::DexSwap<AccountId, CurrencyId, Balance> for MockSwap {
+    fn is_available() -> bool {
+        true
+    }
+
+    fn swap_exact_amount_in(
+        who: &AccountId,
+        amount_in: Balance,
+        amount_out_min: Balance,
+        path: &[CurrencyId],
+    ) -> Result<Balance, DispatchError> {
+        // swaps at 90% of the (1:1) oracle price
+        let amount_out = amount_in * 9 / 10;
+        ensure!(
+            amount_out >= amount_out_min,
+            DispatchError::Other("InsufficientTargetAmount")
+        );
+        currency::Amount::<Test>::new(amount_in, path[0]).burn_from(who)?;
+        currency::Amount::<Test>::new(amount_out, path[path.len() - 1]).mint_to(who)?;
+        Ok(amount_out)
+    }
+}
+
+pub struct MockNomination;
+impl traits::NominationApi<VaultId<AccountId, CurrencyId>, AccountId, currency::Amount<Test>> for MockNomination {
+    fn deposit_vault_collateral(
+        _vault_id: &VaultId<AccountId, CurrencyId>,
+        _amount: &currency::Amount<Test>,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
+    fn deposit_nominator_collateral(
+        _vault_id: &VaultId<AccountId, CurrencyId>,
+        _nominator_id: &AccountId,
+        _amount: &currency::Amount<Test>,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
+    fn get_total_unbonding(vault_id: &VaultId<AccountId, CurrencyId>) -> currency::Amount<Test> {
+        currency::Amount::new(0, vault_id.collateral_currency())
+    }
+}
 
 #[allow(dead_code)]
 pub type TestError = Error<Test>;
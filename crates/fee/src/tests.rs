@@ -1,7 +1,9 @@
 use crate::{mock::*, IssueFee};
 use currency::Amount;
 use frame_support::{assert_noop, assert_ok, dispatch::DispatchResultWithPostInfo};
-use sp_runtime::{DispatchError, FixedPointNumber};
+use primitives::VaultId;
+use reward::RewardsApi;
+use sp_runtime::{traits::Zero, DispatchError, FixedPointNumber};
 
 fn test_setter<F1, F2>(f: F1, get_storage_value: F2)
 where
@@ -59,3 +61,287 @@ fn should_set_punishment_fee() {
 fn should_set_replace_griefing_collateral() {
     test_setter(Fee::set_replace_griefing_collateral, Fee::replace_griefing_collateral);
 }
+
+#[test]
+fn should_set_max_commission() {
+    test_setter(Fee::set_max_commission, Fee::max_commission);
+}
+
+#[test]
+fn should_set_max_commission_step() {
+    test_setter(Fee::set_max_commission_step, Fee::max_commission_step);
+}
+
+fn vault_id() -> VaultId<AccountId, CurrencyId> {
+    VaultId::new(1, Token(DOT), Token(IBTC))
+}
+
+fn set_commission(commission: UnsignedFixedPoint) -> DispatchResultWithPostInfo {
+    Fee::set_commission(RuntimeOrigin::signed(1), vault_id().currencies, commission)
+}
+
+#[test]
+fn should_apply_commission_immediately_without_delay() {
+    run_test(|| {
+        let commission = UnsignedFixedPoint::checked_from_rational(10, 100).unwrap();
+        assert_ok!(set_commission(commission));
+        assert_eq!(Fee::get_commission_rate(&vault_id()), commission);
+        assert_eq!(Fee::get_pending_commission(&vault_id()), None);
+    })
+}
+
+#[test]
+fn should_apply_announced_commission_after_delay() {
+    run_test(|| {
+        assert_ok!(Fee::set_commission_change_delay(RuntimeOrigin::root(), 10));
+
+        let commission = UnsignedFixedPoint::checked_from_rational(10, 100).unwrap();
+        assert_ok!(set_commission(commission));
+        assert_eq!(Fee::get_commission_rate(&vault_id()), UnsignedFixedPoint::zero());
+        assert_eq!(Fee::get_pending_commission(&vault_id()), Some((commission, 11)));
+
+        Security::set_active_block_number(10);
+        assert_ok!(Fee::distribute_all_vault_rewards(&vault_id()));
+        assert_eq!(Fee::get_commission_rate(&vault_id()), UnsignedFixedPoint::zero());
+
+        Security::set_active_block_number(11);
+        assert_ok!(Fee::distribute_all_vault_rewards(&vault_id()));
+        assert_eq!(Fee::get_commission_rate(&vault_id()), commission);
+        assert_eq!(Fee::get_pending_commission(&vault_id()), None);
+    })
+}
+
+#[test]
+fn should_distribute_outstanding_rewards_at_previous_commission() {
+    run_test(|| {
+        assert_ok!(Fee::set_commission_change_delay(RuntimeOrigin::root(), 10));
+
+        let commission = UnsignedFixedPoint::checked_from_rational(10, 100).unwrap();
+        assert_ok!(set_commission(commission));
+        setup_vault_rewards();
+
+        // the change is in effect but has not been applied yet
+        Security::set_active_block_number(11);
+        assert_eq!(Fee::get_commission_rate(&vault_id()), UnsignedFixedPoint::zero());
+        assert_eq!(Fee::get_effective_commission_rate(&vault_id()), commission);
+        assert_eq!(Fee::get_pending_commission(&vault_id()), None);
+
+        // the rewards accrued before the change are not subject to the new commission
+        assert_ok!(Fee::distribute_all_vault_rewards(&vault_id()));
+        assert_eq!(
+            Fee::compute_vault_rewards(&vault_id(), &2, Token(IBTC)).unwrap(),
+            Amount::new(400, Token(IBTC))
+        );
+        assert_eq!(
+            currency::get_free_balance::<Test>(Token(IBTC), &1),
+            Amount::new(0, Token(IBTC))
+        );
+        assert_eq!(Fee::get_commission_rate(&vault_id()), commission);
+        System::assert_has_event(RuntimeEvent::Fee(crate::Event::CommissionChanged {
+            vault_id: vault_id(),
+            commission,
+        }));
+    })
+}
+
+#[test]
+fn should_not_exceed_commission_limits() {
+    run_test(|| {
+        assert_ok!(Fee::set_max_commission(
+            RuntimeOrigin::root(),
+            UnsignedFixedPoint::checked_from_rational(50, 100).unwrap()
+        ));
+        assert_ok!(Fee::set_max_commission_step(
+            RuntimeOrigin::root(),
+            UnsignedFixedPoint::checked_from_rational(10, 100).unwrap()
+        ));
+
+        assert_noop!(
+            set_commission(UnsignedFixedPoint::checked_from_rational(60, 100).unwrap()),
+            TestError::AboveMaxCommission
+        );
+        assert_noop!(
+            set_commission(UnsignedFixedPoint::checked_from_rational(20, 100).unwrap()),
+            TestError::AboveMaxCommissionStep
+        );
+        assert_ok!(set_commission(
+            UnsignedFixedPoint::checked_from_rational(10, 100).unwrap()
+        ));
+        assert_ok!(set_commission(
+            UnsignedFixedPoint::checked_from_rational(20, 100).unwrap()
+        ));
+
+        // lowering the maximum also caps existing commissions
+        assert_ok!(Fee::set_max_commission(
+            RuntimeOrigin::root(),
+            UnsignedFixedPoint::checked_from_rational(15, 100).unwrap()
+        ));
+        assert_eq!(
+            Fee::get_commission_rate(&vault_id()),
+            UnsignedFixedPoint::checked_from_rational(15, 100).unwrap()
+        );
+    })
+}
+
+fn setup_vault_rewards() {
+    use sp_arithmetic::FixedI128;
+
+    assert_ok!(CapacityRewards::deposit_stake(&(), &Token(DOT), FixedI128::from(100)));
+    assert_ok!(VaultRewards::deposit_stake(
+        &Token(DOT),
+        &vault_id(),
+        FixedI128::from(100)
+    ));
+    assert_ok!(VaultStaking::deposit_stake(&vault_id(), &1, FixedI128::from(60)));
+    assert_ok!(VaultStaking::deposit_stake(&vault_id(), &2, FixedI128::from(40)));
+
+    let rewards = Amount::<Test>::new(1000, Token(IBTC));
+    assert_ok!(rewards.mint_to(&Fee::fee_pool_account_id()));
+    assert_ok!(Fee::distribute_rewards(&rewards));
+}
+
+fn set_auto_compound(max_slippage: Option<UnsignedFixedPoint>) -> DispatchResultWithPostInfo {
+    Fee::set_auto_compound(RuntimeOrigin::signed(1), vault_id().currencies, max_slippage)
+}
+
+fn set_nominator_auto_compound(nominator_id: AccountId, enabled: bool) -> DispatchResultWithPostInfo {
+    Fee::set_nominator_auto_compound(RuntimeOrigin::signed(nominator_id), vault_id(), enabled)
+}
+
+fn compound_vault_rewards() -> DispatchResultWithPostInfo {
+    Fee::compound_vault_rewards(RuntimeOrigin::signed(3), vault_id())
+}
+
+fn percent(value: u128) -> UnsignedFixedPoint {
+    UnsignedFixedPoint::checked_from_rational(value, 100).unwrap()
+}
+
+#[test]
+fn should_compound_vault_rewards_into_collateral() {
+    run_test(|| {
+        setup_vault_rewards();
+        assert_ok!(Fee::set_max_auto_compound_slippage(RuntimeOrigin::root(), percent(20)));
+        assert_noop!(set_auto_compound(Some(percent(30))), TestError::AboveMaxSlippage);
+        assert_ok!(set_auto_compound(Some(percent(20))));
+        assert_ok!(set_nominator_auto_compound(2, true));
+
+        // rewards are only compounded on request
+        assert_ok!(Fee::distribute_all_vault_rewards(&vault_id()));
+        assert_eq!(
+            currency::get_free_balance::<Test>(Token(DOT), &2),
+            Amount::new(0, Token(DOT))
+        );
+
+        // the swap loses 10%, which is within the slippage limit
+        assert_ok!(compound_vault_rewards());
+        assert_eq!(
+            currency::get_free_balance::<Test>(Token(DOT), &2),
+            Amount::new(360, Token(DOT))
+        );
+        assert_eq!(
+            currency::get_free_balance::<Test>(Token(DOT), &1),
+            Amount::new(540, Token(DOT))
+        );
+        assert_eq!(
+            Fee::compute_vault_rewards(&vault_id(), &2, Token(IBTC)).unwrap(),
+            Amount::new(0, Token(IBTC))
+        );
+        System::assert_has_event(RuntimeEvent::Fee(crate::Event::CompoundRewards {
+            vault_id: vault_id(),
+            nominator_id: 2,
+            amount: 360,
+        }));
+    })
+}
+
+#[test]
+fn should_only_compound_rewards_of_nominators_that_opted_in() {
+    run_test(|| {
+        setup_vault_rewards();
+        assert_ok!(Fee::set_max_auto_compound_slippage(RuntimeOrigin::root(), percent(20)));
+        assert_ok!(set_auto_compound(Some(percent(20))));
+
+        assert_ok!(compound_vault_rewards());
+        assert_eq!(
+            currency::get_free_balance::<Test>(Token(DOT), &1),
+            Amount::new(540, Token(DOT))
+        );
+        assert_eq!(
+            currency::get_free_balance::<Test>(Token(DOT), &2),
+            Amount::new(0, Token(DOT))
+        );
+        assert_eq!(
+            Fee::compute_vault_rewards(&vault_id(), &2, Token(IBTC)).unwrap(),
+            Amount::new(400, Token(IBTC))
+        );
+    })
+}
+
+#[test]
+fn should_bound_nominators_that_opt_in() {
+    use sp_arithmetic::FixedI128;
+
+    run_test(|| {
+        assert_noop!(set_nominator_auto_compound(3, true), TestError::NoStakeInVault);
+
+        for nominator_id in 2..=4 {
+            assert_ok!(VaultStaking::deposit_stake(
+                &vault_id(),
+                &nominator_id,
+                FixedI128::from(10)
+            ));
+        }
+        assert_ok!(set_nominator_auto_compound(2, true));
+        assert_ok!(set_nominator_auto_compound(3, true));
+        assert_noop!(
+            set_nominator_auto_compound(4, true),
+            TestError::TooManyCompoundingNominators
+        );
+
+        // nominators that withdrew their stake make room for others
+        assert_ok!(<VaultStaking as RewardsApi<_, _, Balance>>::withdraw_stake(
+            &(None, vault_id()),
+            &3,
+            10
+        ));
+        assert_ok!(set_nominator_auto_compound(4, true));
+        assert_eq!(
+            crate::CompoundingNominators::<Test>::get(vault_id()).into_inner(),
+            vec![2, 4]
+        );
+
+        assert_ok!(set_nominator_auto_compound(2, false));
+        assert_eq!(
+            crate::CompoundingNominators::<Test>::get(vault_id()).into_inner(),
+            vec![4]
+        );
+    })
+}
+
+#[test]
+fn should_not_compound_vault_rewards_above_max_slippage() {
+    run_test(|| {
+        setup_vault_rewards();
+        assert_ok!(Fee::set_max_auto_compound_slippage(RuntimeOrigin::root(), percent(20)));
+        assert_ok!(set_auto_compound(Some(percent(20))));
+        assert_ok!(set_nominator_auto_compound(2, true));
+
+        // lowering the maximum also caps the slippage of vaults that already opted in
+        assert_ok!(Fee::set_max_auto_compound_slippage(RuntimeOrigin::root(), percent(5)));
+
+        // the swap loses 10%, so the rewards are left to be withdrawn as usual
+        assert_ok!(compound_vault_rewards());
+        assert_eq!(
+            currency::get_free_balance::<Test>(Token(DOT), &2),
+            Amount::new(0, Token(DOT))
+        );
+        assert_eq!(
+            Fee::compute_vault_rewards(&vault_id(), &2, Token(IBTC)).unwrap(),
+            Amount::new(400, Token(IBTC))
+        );
+
+        assert_ok!(set_auto_compound(None));
+        assert_eq!(Fee::auto_compound(&vault_id()), None);
+        assert_noop!(compound_vault_rewards(), TestError::AutoCompoundNotEnabled);
+    })
+}
@@ -16,5 +16,9 @@ sp_api::decl_runtime_apis! {
 
         /// Get all issue requests for a particular vault
         fn get_vault_issue_requests(vault_id: AccountId) -> Vec<H256>;
+
+        /// Get all issue requests, including their deposit addresses, linked by a split issue.
+        /// Only covers groups that still have a pending request.
+        fn get_issue_group_requests(group_id: H256) -> Vec<IssueRequest>;
     }
 }
@@ -20,6 +20,9 @@ pub trait IssueApi<BlockHash, AccountId, H256, IssueRequest> {
 
     #[method(name = "issue_getVaultIssueRequests")]
     fn get_vault_issue_requests(&self, vault_id: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<H256>>;
+
+    #[method(name = "issue_getIssueGroupRequests")]
+    fn get_issue_group_requests(&self, group_id: H256, at: Option<BlockHash>) -> RpcResult<Vec<IssueRequest>>;
 }
 
 fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
@@ -76,4 +79,16 @@ where
         api.get_vault_issue_requests(&at, vault_id)
             .map_err(|e| internal_err(format!("Unable to fetch issue requests: {:?}", e)))
     }
+
+    fn get_issue_group_requests(
+        &self,
+        group_id: H256,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<IssueRequest>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_issue_group_requests(&at, group_id)
+            .map_err(|e| internal_err(format!("Unable to fetch issue requests: {:?}", e)))
+    }
 }
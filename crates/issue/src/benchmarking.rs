@@ -26,7 +26,10 @@ use btc_relay::Pallet as BtcRelay;
 use oracle::Pallet as Oracle;
 use primitives::VaultCurrencyPair;
 use security::Pallet as Security;
-use vault_registry::{types::DefaultVaultCurrencyPair, Pallet as VaultRegistry};
+use vault_registry::{
+    types::{CandidateVaults, DefaultVaultCurrencyPair},
+    Pallet as VaultRegistry,
+};
 
 fn deposit_tokens<T: crate::Config>(currency_id: CurrencyId, account_id: &T::AccountId, amount: BalanceOf<T>) {
     assert_ok!(<orml_tokens::Pallet<T>>::deposit(currency_id, account_id, amount));
@@ -331,6 +334,76 @@ benchmarks! {
     set_issue_period {
     }: _(RawOrigin::Root, 1u32.into())
 
+    request_issue_auto {
+        // the amount is split across the maximum number of vaults, so there must be at least that many candidates
+        let n in vault_registry::MAX_VAULTS_PER_REQUEST .. vault_registry::MAX_CANDIDATE_VAULTS;
+
+        let origin: T::AccountId = account("Origin", 0, 0);
+        let relayer_id: T::AccountId = account("Relayer", 0, 0);
+        let vault_collateral: u32 = 100000000;
+
+        mint_collateral::<T>(&origin, (1u32 << 31).into());
+        mint_collateral::<T>(&relayer_id, (1u32 << 31).into());
+
+        Oracle::<T>::_set_exchange_rate(get_collateral_currency_id::<T>(), <T as currency::Config>::UnsignedFixedPoint::one()).unwrap();
+        // every vault can issue exactly as much as it has collateral
+        VaultRegistry::<T>::_set_secure_collateral_threshold(get_currency_pair::<T>(), <T as currency::Config>::UnsignedFixedPoint::one());
+        VaultRegistry::<T>::_set_system_collateral_ceiling(get_currency_pair::<T>(), u32::MAX.into());
+
+        let candidates: Vec<DefaultVaultId<T>> = (0..n).map(|i| {
+            let vault_id = VaultId::new(
+                account("Vault", i, 0),
+                get_collateral_currency_id::<T>(),
+                get_wrapped_currency_id::<T>(),
+            );
+            mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+            register_vault::<T>(vault_id.clone());
+            vault_id
+        }).collect();
+        let candidates: CandidateVaults<T> = candidates.try_into().unwrap();
+
+        mine_blocks::<T>(BtcRelay::<T>::bitcoin_confirmations() + 1);
+        Security::<T>::set_active_block_number(Security::<T>::active_block_number() + BtcRelay::<T>::parachain_confirmations());
+
+        // no single vault can issue the amount, so it is split across the maximum number of vaults
+        let amount = (vault_registry::MAX_VAULTS_PER_REQUEST - 1) * vault_collateral + vault_collateral / 2;
+    }: _(RawOrigin::Signed(origin), amount.into(), get_currency_pair::<T>(), candidates)
+    verify {
+        assert_eq!(IssueRequests::<T>::iter().count(), vault_registry::MAX_VAULTS_PER_REQUEST as usize);
+    }
+
+    remove_issue_group {
+        let origin: T::AccountId = account("Origin", 0, 0);
+        let vault_id = get_vault_id::<T>();
+
+        let issue_ids: Vec<H256> = (0..vault_registry::MAX_VAULTS_PER_REQUEST).map(|i| {
+            let issue_id = H256::from_low_u64_be(i as u64);
+            let issue_request = IssueRequest {
+                requester: origin.clone(),
+                vault: vault_id.clone(),
+                btc_address: BtcAddress::dummy(),
+                amount: Default::default(),
+                btc_height: Default::default(),
+                btc_public_key: Default::default(),
+                fee: Default::default(),
+                griefing_collateral: Default::default(),
+                opentime: Default::default(),
+                period: Default::default(),
+                status: IssueRequestStatus::Completed,
+            };
+            Issue::<T>::insert_issue_request(&issue_id, &issue_request);
+            issue_id
+        }).collect();
+
+        let group_id = H256::repeat_byte(1);
+        Issue::<T>::insert_issue_group(group_id, issue_ids.try_into().unwrap());
+    }: {
+        Issue::<T>::remove_issue_group_if_settled(group_id);
+    }
+    verify {
+        assert!(!IssueRequestGroups::<T>::contains_key(group_id));
+    }
+
 }
 
 impl_benchmark_test_suite!(
@@ -38,6 +38,8 @@ pub trait WeightInfo {
 	fn execute_issue() -> Weight;
 	fn cancel_issue() -> Weight;
 	fn set_issue_period() -> Weight;
+	fn request_issue_auto(n: u32, ) -> Weight;
+	fn remove_issue_group() -> Weight;
 }
 
 /// Weights for issue using the Substrate node and recommended hardware.
@@ -106,6 +108,42 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_ref_time(3_071_000 as u64)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: BTCRelay StartBlockHeight (r:1 w:0)
+	// Storage: BTCRelay StableBitcoinConfirmations (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: Issue IssueBtcDustValue (r:1 w:0)
+	// Storage: VaultRegistry Vaults (r:20 w:5)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: Staking Nonce (r:20 w:0)
+	// Storage: Staking TotalCurrentStake (r:20 w:0)
+	// Storage: Fee IssueGriefingCollateral (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Fee IssueFee (r:1 w:0)
+	// Storage: Security Nonce (r:1 w:1)
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: Issue IssuePeriod (r:1 w:0)
+	// Storage: Issue IssueRequests (r:0 w:5)
+	// Storage: Issue IssueRequestGroupOf (r:0 w:5)
+	// Storage: Issue IssueRequestGroups (r:0 w:1)
+	fn request_issue_auto(n: u32, ) -> Weight {
+		Weight::from_ref_time(2_214_918_000 as u64)
+			// Standard Error: 96_000
+			.saturating_add(Weight::from_ref_time(31_870_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(18 as u64))
+	}
+	// Storage: Issue IssueRequestGroups (r:1 w:1)
+	// Storage: Issue IssueRequests (r:5 w:0)
+	// Storage: Issue IssueRequestGroupOf (r:0 w:5)
+	fn remove_issue_group() -> Weight {
+		Weight::from_ref_time(24_306_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -173,5 +211,41 @@ impl WeightInfo for () {
 		Weight::from_ref_time(3_071_000 as u64)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: BTCRelay StartBlockHeight (r:1 w:0)
+	// Storage: BTCRelay StableBitcoinConfirmations (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: Issue IssueBtcDustValue (r:1 w:0)
+	// Storage: VaultRegistry Vaults (r:20 w:5)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: Staking Nonce (r:20 w:0)
+	// Storage: Staking TotalCurrentStake (r:20 w:0)
+	// Storage: Fee IssueGriefingCollateral (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Fee IssueFee (r:1 w:0)
+	// Storage: Security Nonce (r:1 w:1)
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: Issue IssuePeriod (r:1 w:0)
+	// Storage: Issue IssueRequests (r:0 w:5)
+	// Storage: Issue IssueRequestGroupOf (r:0 w:5)
+	// Storage: Issue IssueRequestGroups (r:0 w:1)
+	fn request_issue_auto(n: u32, ) -> Weight {
+		Weight::from_ref_time(2_214_918_000 as u64)
+			// Standard Error: 96_000
+			.saturating_add(Weight::from_ref_time(31_870_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(18 as u64))
+	}
+	// Storage: Issue IssueRequestGroups (r:1 w:1)
+	// Storage: Issue IssueRequests (r:5 w:0)
+	// Storage: Issue IssueRequestGroupOf (r:0 w:5)
+	fn remove_issue_group() -> Weight {
+		Weight::from_ref_time(24_306_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
 }
 
@@ -47,8 +47,9 @@ pub(crate) mod vault_registry {
     use btc_relay::BtcAddress;
     use frame_support::dispatch::{DispatchError, DispatchResult};
     use sp_core::H256;
+    use sp_std::vec::Vec;
     use vault_registry::{
-        types::{CurrencySource, DefaultVault},
+        types::{CurrencySource, DefaultVault, DefaultVaultCurrencyPair},
         Amount, BtcPublicKey,
     };
 
@@ -70,6 +71,15 @@ pub(crate) mod vault_registry {
         <vault_registry::Pallet<T>>::get_active_vault_from_id(vault_id)
     }
 
+    pub fn select_vaults_for_issue<T: crate::Config>(
+        currency_pair: &DefaultVaultCurrencyPair<T>,
+        candidates: &[DefaultVaultId<T>],
+        amount: &Amount<T>,
+        min_amount: &Amount<T>,
+    ) -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
+        <vault_registry::Pallet<T>>::select_vaults_for_issue(currency_pair, candidates, amount, min_amount)
+    }
+
     pub fn try_increase_to_be_issued_tokens<T: crate::Config>(
         vault_id: &DefaultVaultId<T>,
         amount: &Amount<T>,
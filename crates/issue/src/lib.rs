@@ -29,7 +29,7 @@ pub mod types;
 #[doc(inline)]
 pub use crate::types::{DefaultIssueRequest, IssueRequest, IssueRequestStatus};
 
-use crate::types::{BalanceOf, DefaultVaultId, Version};
+use crate::types::{BalanceOf, DefaultVaultId, IssueGroup, Version};
 use btc_relay::{BtcAddress, BtcPublicKey};
 use currency::Amount;
 use frame_support::{dispatch::DispatchError, ensure, traits::Get, transactional, PalletId};
@@ -39,7 +39,10 @@ use sp_core::H256;
 use sp_runtime::traits::{AccountIdConversion, Convert, Saturating};
 use sp_std::vec::Vec;
 use types::IssueRequestExt;
-use vault_registry::{types::CurrencyId, CurrencySource, VaultStatus};
+use vault_registry::{
+    types::{CandidateVaults, CurrencyId, DefaultVaultCurrencyPair},
+    CurrencySource, VaultStatus,
+};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -105,6 +108,11 @@ pub mod pallet {
         IssuePeriodChange {
             period: T::BlockNumber,
         },
+        RequestIssueGroup {
+            group_id: H256,
+            requester: T::AccountId,
+            issue_ids: Vec<H256>,
+        },
     }
 
     #[pallet::error]
@@ -127,6 +135,8 @@ pub mod pallet {
         InvalidExecutor,
         /// Issue amount is too small.
         AmountBelowDustAmount,
+        /// Too many issue requests to link in a single group.
+        TooManyIssueRequests,
     }
 
     /// Users create issue requests to issue tokens. This mapping provides access
@@ -136,6 +146,17 @@ pub mod pallet {
     pub(super) type IssueRequests<T: Config> =
         StorageMap<_, Blake2_128Concat, H256, DefaultIssueRequest<T>, OptionQuery>;
 
+    /// Issue requests created together by `request_issue_auto` or `request_issue_split`. This
+    /// mapping provides access from a unique `GroupId` to the IDs of all linked issue requests.
+    /// Groups are removed once none of their requests are pending anymore, so only open groups
+    /// can be queried.
+    #[pallet::storage]
+    pub(super) type IssueRequestGroups<T: Config> = StorageMap<_, Blake2_128Concat, H256, IssueGroup, ValueQuery>;
+
+    /// The group that an issue request created by `request_issue_auto` belongs to.
+    #[pallet::storage]
+    pub(super) type IssueRequestGroupOf<T: Config> = StorageMap<_, Blake2_128Concat, H256, H256, OptionQuery>;
+
     /// The time difference in number of blocks between an issue request is created
     /// and required completion time by a user. The issue period has an upper limit
     /// to prevent griefing of vault collateral.
@@ -220,7 +241,9 @@ pub mod pallet {
         /// * `merkle_proof` - raw bytes
         /// * `raw_tx` - raw bytes
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::execute_issue())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute_issue().saturating_add(<T as Config>::WeightInfo::remove_issue_group())
+        )]
         #[transactional]
         pub fn execute_issue(
             origin: OriginFor<T>,
@@ -240,7 +263,9 @@ pub mod pallet {
         /// * `origin` - sender of the transaction
         /// * `issue_id` - identifier of issue request as output from request_issue
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::cancel_issue())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::cancel_issue().saturating_add(<T as Config>::WeightInfo::remove_issue_group())
+        )]
         #[transactional]
         pub fn cancel_issue(origin: OriginFor<T>, issue_id: H256) -> DispatchResultWithPostInfo {
             let requester = ensure_signed(origin)?;
@@ -265,6 +290,60 @@ pub mod pallet {
             Self::deposit_event(Event::IssuePeriodChange { period });
             Ok(().into())
         }
+
+        /// Request the issuance of tokens without choosing a single vault. Out of the given candidates,
+        /// the vault with the most issuable tokens is used if it can issue the full amount; otherwise the
+        /// amount is split across up to [`vault_registry::MAX_VAULTS_PER_REQUEST`] vaults, in descending
+        /// order of issuable tokens. A separate issue request is created for every selected vault, and
+        /// all of them are linked by a common group id, such that the deposit addresses of all vaults
+        /// can be queried at once.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - sender of the transaction
+        /// * `amount` - amount of BTC the user wants to convert to issued tokens. Note that the
+        /// amount of issued tokens received will be less, because a fee is subtracted.
+        /// * `currency_pair` - the collateral and wrapped currency of the vaults to issue with
+        /// * `candidates` - the vaults to choose from
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_issue_auto(candidates.len() as u32))]
+        #[transactional]
+        pub fn request_issue_auto(
+            origin: OriginFor<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            candidates: CandidateVaults<T>,
+        ) -> DispatchResultWithPostInfo {
+            let requester = ensure_signed(origin)?;
+            Self::_request_issue_auto(requester, amount, currency_pair, &candidates)?;
+            Ok(().into())
+        }
+
+        /// Request the issuance of tokens split across multiple vaults. Capacity is reserved on every
+        /// selected vault atomically, and the created requests are linked by a common group id, such
+        /// that the deposit addresses of all vaults can be queried at once. Vaults are selected as in
+        /// `request_issue_auto`.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - sender of the transaction
+        /// * `amount` - amount of BTC the user wants to convert to issued tokens. Note that the
+        /// amount of issued tokens received will be less, because a fee is subtracted.
+        /// * `currency_pair` - the collateral and wrapped currency of the vaults to issue with
+        /// * `candidates` - the vaults to choose from
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_issue_auto(candidates.len() as u32))]
+        #[transactional]
+        pub fn request_issue_split(
+            origin: OriginFor<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            candidates: CandidateVaults<T>,
+        ) -> DispatchResultWithPostInfo {
+            let requester = ensure_signed(origin)?;
+            Self::_request_issue_auto(requester, amount, currency_pair, &candidates)?;
+            Ok(().into())
+        }
     }
 }
 
@@ -275,6 +354,44 @@ impl<T: Config> Pallet<T> {
         T::TreasuryPalletId::get().into_account_truncating()
     }
 
+    /// Requests CBA issuance from automatically selected vaults, returns the ID of the group linking
+    /// all created requests.
+    fn _request_issue_auto(
+        requester: T::AccountId,
+        amount_requested: BalanceOf<T>,
+        currency_pair: DefaultVaultCurrencyPair<T>,
+        candidates: &[DefaultVaultId<T>],
+    ) -> Result<H256, DispatchError> {
+        let amount_requested = Amount::new(amount_requested, currency_pair.wrapped);
+        let dust_value = Self::issue_btc_dust_value(currency_pair.wrapped);
+
+        let issue_ids = ext::vault_registry::select_vaults_for_issue::<T>(
+            &currency_pair,
+            candidates,
+            &amount_requested,
+            &dust_value,
+        )?
+        .into_iter()
+        .map(|(vault_id, amount)| Self::_request_issue(requester.clone(), amount.amount(), vault_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let group_id = ext::security::get_secure_id::<T>(&requester);
+        Self::insert_issue_group(
+            group_id,
+            issue_ids
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyIssueRequests)?,
+        );
+
+        Self::deposit_event(Event::RequestIssueGroup {
+            group_id,
+            requester,
+            issue_ids,
+        });
+        Ok(group_id)
+    }
+
     /// Requests CBA issuance, returns unique tracking ID.
     fn _request_issue(
         requester: T::AccountId,
@@ -585,6 +702,21 @@ impl<T: Config> Pallet<T> {
             .collect()
     }
 
+    /// Fetch all issue requests linked by the specified group, which includes
+    /// the deposit address of every vault in the group. Only groups with at least one
+    /// pending request are kept, so nothing is returned once all requests of a group
+    /// have been executed or cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - id emitted in the `RequestIssueGroup` event
+    pub fn get_issue_group_requests(group_id: H256) -> Vec<DefaultIssueRequest<T>> {
+        IssueRequestGroups::<T>::get(group_id)
+            .iter()
+            .filter_map(|issue_id| <IssueRequests<T>>::get(issue_id))
+            .collect()
+    }
+
     pub fn get_issue_request_from_id(issue_id: &H256) -> Result<DefaultIssueRequest<T>, DispatchError> {
         let request = IssueRequests::<T>::try_get(issue_id).or(Err(Error::<T>::IssueIdNotFound))?;
 
@@ -647,6 +779,39 @@ impl<T: Config> Pallet<T> {
                 .clone()
                 .map(|request| DefaultIssueRequest::<T> { status, ..request });
         });
+
+        if let Some(group_id) = IssueRequestGroupOf::<T>::get(id) {
+            Self::remove_issue_group_if_settled(group_id);
+        }
+    }
+
+    fn insert_issue_group(group_id: H256, issue_ids: IssueGroup) {
+        for issue_id in issue_ids.iter() {
+            IssueRequestGroupOf::<T>::insert(issue_id, group_id);
+        }
+        IssueRequestGroups::<T>::insert(group_id, issue_ids);
+    }
+
+    /// Removes the group once none of its requests are pending anymore.
+    fn remove_issue_group_if_settled(group_id: H256) {
+        let issue_ids = IssueRequestGroups::<T>::get(group_id);
+        let is_pending = |issue_id: &H256| {
+            matches!(
+                <IssueRequests<T>>::get(issue_id),
+                Some(IssueRequest {
+                    status: IssueRequestStatus::Pending,
+                    ..
+                })
+            )
+        };
+        if issue_ids.iter().any(is_pending) {
+            return;
+        }
+
+        for issue_id in issue_ids.iter() {
+            IssueRequestGroupOf::<T>::remove(issue_id);
+        }
+        IssueRequestGroups::<T>::remove(group_id);
     }
 
     fn issue_btc_dust_value(currency_id: CurrencyId<T>) -> Amount<T> {
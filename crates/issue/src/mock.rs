@@ -44,7 +44,7 @@ frame_support::construct_runtime!(
         VaultRegistry: vault_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
         Oracle: oracle::{Pallet, Call, Config<T>, Storage, Event<T>},
         Issue: issue::{Pallet, Call, Config<T>, Storage, Event<T>},
-        Fee: fee::{Pallet, Call, Config<T>, Storage},
+        Fee: fee::{Pallet, Call, Config<T>, Storage, Event<T>},
         Currency: currency::{Pallet},
         Nomination: nomination::{Pallet, Call, Config, Storage, Event<T>},
     }
@@ -175,11 +175,13 @@ impl vault_registry::Config for Test {
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
     type NominationApi = Nomination;
+    type LendTokens = ();
 }
 
 impl nomination::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type MaxUnbondingChunks = ConstU32<32>;
 }
 
 pub struct CurrencyConvert;
@@ -237,6 +239,8 @@ impl pallet_timestamp::Config for Test {
 impl oracle::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OnExchangeRateChange = ();
+    type TreasuryPalletId = TreasuryPalletId;
+    type DexPriceSource = ();
     type WeightInfo = ();
 }
 
@@ -246,6 +250,7 @@ parameter_types! {
 }
 
 impl fee::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type FeePalletId = FeePalletId;
     type WeightInfo = ();
     type SignedFixedPoint = SignedFixedPoint;
@@ -256,6 +261,9 @@ impl fee::Config for Test {
     type VaultRewards = VaultRewards;
     type VaultStaking = VaultStaking;
     type OnSweep = ();
+    type DexSwap = ();
+    type Nomination = Nomination;
+    type MaxCompoundingNominators = ConstU32<32>;
     type MaxExpectedValue = MaxExpectedValue;
 }
 
@@ -140,6 +140,118 @@ fn test_request_issue_succeeds() {
     })
 }
 
+#[test]
+fn test_request_issue_auto_requests_from_selected_vaults() {
+    run_test(|| {
+        let vault2 = DefaultVaultId::<Test>::new(
+            VAULT.account_id + 1,
+            VAULT.collateral_currency(),
+            VAULT.wrapped_currency(),
+        );
+        let selected_vaults = vec![(VAULT, wrapped(2)), (vault2, wrapped(1))];
+        let expected_requests = selected_vaults.clone();
+        let group_id = H256::repeat_byte(1);
+
+        ext::vault_registry::select_vaults_for_issue::<Test>.mock_safe(
+            move |currency_pair, candidates, amount, min_amount| {
+                assert_eq!(currency_pair, &VAULT.currencies);
+                assert_eq!(candidates, &[VAULT]);
+                assert_eq!(amount, &wrapped(3));
+                assert_eq!(min_amount, &Issue::issue_btc_dust_value(VAULT.wrapped_currency()));
+                MockResult::Return(Ok(selected_vaults.clone()))
+            },
+        );
+        Issue::_request_issue.mock_safe(move |requester, amount, vault_id| {
+            assert_eq!(requester, USER);
+            assert!(expected_requests.contains(&(vault_id, wrapped(amount))));
+            MockResult::Return(Ok(H256::from_low_u64_be(amount as u64)))
+        });
+        ext::security::get_secure_id::<Test>.mock_safe(move |_| MockResult::Return(group_id));
+
+        assert_eq!(
+            Issue::_request_issue_auto(USER, 3, VAULT.currencies.clone(), &[VAULT]),
+            Ok(group_id)
+        );
+        let request_issue_group_event = TestEvent::Issue(Event::RequestIssueGroup {
+            group_id,
+            requester: USER,
+            issue_ids: vec![H256::from_low_u64_be(2), H256::from_low_u64_be(1)],
+        });
+        assert!(System::events().iter().any(|a| a.event == request_issue_group_event));
+    })
+}
+
+#[test]
+fn test_request_issue_auto_links_requests_by_group() {
+    run_test(|| {
+        let address = BtcAddress::random();
+        ext::vault_registry::get_active_vault_from_id::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(init_zero_vault(VAULT))));
+        ext::fee::get_issue_fee::<Test>.mock_safe(|_| MockResult::Return(Ok(wrapped(1))));
+        ext::fee::get_issue_griefing_collateral::<Test>.mock_safe(|_| MockResult::Return(Ok(griefing(20))));
+        let issue_id = request_issue_ok_with_address(USER, 3, VAULT, address);
+
+        let group_id = H256::repeat_byte(1);
+        ext::vault_registry::select_vaults_for_issue::<Test>
+            .mock_safe(|_, _, _, _| MockResult::Return(Ok(vec![(VAULT, wrapped(3))])));
+        Issue::_request_issue.mock_safe(move |_, _, _| MockResult::Return(Ok(issue_id)));
+        ext::security::get_secure_id::<Test>.mock_safe(move |_| MockResult::Return(group_id));
+
+        assert_ok!(Issue::_request_issue_auto(USER, 3, VAULT.currencies.clone(), &[VAULT]));
+
+        let requests = Issue::get_issue_group_requests(group_id);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].btc_address, address);
+        assert_eq!(requests[0].vault, VAULT);
+    })
+}
+
+#[test]
+fn test_request_issue_split_links_requests_by_group() {
+    run_test(|| {
+        let group_id = H256::repeat_byte(1);
+        Issue::_request_issue_auto.mock_safe(move |requester, amount, currency_pair, candidates| {
+            assert_eq!(requester, USER);
+            assert_eq!(amount, 3);
+            assert_eq!(currency_pair, VAULT.currencies);
+            assert_eq!(candidates, &[VAULT]);
+            MockResult::Return(Ok(group_id))
+        });
+
+        assert_ok!(Issue::request_issue_split(
+            RuntimeOrigin::signed(USER),
+            3,
+            VAULT.currencies.clone(),
+            vec![VAULT].try_into().unwrap()
+        ));
+    })
+}
+
+#[test]
+fn test_issue_group_is_removed_once_no_request_is_pending() {
+    run_test(|| {
+        ext::vault_registry::get_active_vault_from_id::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(init_zero_vault(VAULT))));
+        ext::fee::get_issue_fee::<Test>.mock_safe(|_| MockResult::Return(Ok(wrapped(1))));
+        ext::fee::get_issue_griefing_collateral::<Test>.mock_safe(|_| MockResult::Return(Ok(griefing(20))));
+        let issue_id1 = request_issue_ok(USER, 3, VAULT);
+        let issue_id2 = H256::repeat_byte(2);
+        Issue::insert_issue_request(&issue_id2, &Issue::issue_requests(issue_id1).unwrap());
+
+        let group_id = H256::repeat_byte(1);
+        Issue::insert_issue_group(group_id, vec![issue_id1, issue_id2].try_into().unwrap());
+
+        Issue::set_issue_status(issue_id1, IssueRequestStatus::Completed);
+        assert_eq!(Issue::get_issue_group_requests(group_id).len(), 2);
+
+        Issue::set_issue_status(issue_id2, IssueRequestStatus::Cancelled);
+        assert!(Issue::get_issue_group_requests(group_id).is_empty());
+        assert!(!crate::IssueRequestGroups::<Test>::contains_key(group_id));
+        assert!(!crate::IssueRequestGroupOf::<Test>::contains_key(issue_id1));
+        assert!(!crate::IssueRequestGroupOf::<Test>::contains_key(issue_id2));
+    })
+}
+
 #[test]
 fn test_execute_issue_not_found_fails() {
     run_test(|| {
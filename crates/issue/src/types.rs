@@ -1,9 +1,13 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use currency::Amount;
-use frame_support::traits::Get;
+use frame_support::{
+    traits::{ConstU32, Get},
+    BoundedVec,
+};
 pub use primitives::issue::{IssueRequest, IssueRequestStatus};
 use primitives::VaultId;
 use scale_info::TypeInfo;
+use sp_core::H256;
 use vault_registry::types::CurrencyId;
 
 use crate::Config;
@@ -27,6 +31,8 @@ pub(crate) type BalanceOf<T> = <T as vault_registry::Config>::Balance;
 
 pub(crate) type DefaultVaultId<T> = VaultId<<T as frame_system::Config>::AccountId, CurrencyId<T>>;
 
+pub(crate) type IssueGroup = BoundedVec<H256, ConstU32<{ vault_registry::MAX_VAULTS_PER_REQUEST }>>;
+
 pub type DefaultIssueRequest<T> = IssueRequest<
     <T as frame_system::Config>::AccountId,
     <T as frame_system::Config>::BlockNumber,
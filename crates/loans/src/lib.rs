@@ -52,7 +52,8 @@ use sp_runtime::{
 use sp_std::{marker, result::Result};
 
 use traits::{
-    ConvertToBigUint, LoansApi as LoansTrait, LoansMarketDataProvider, MarketInfo, MarketStatus, OnExchangeRateChange,
+    ConvertToBigUint, LendTokenRedeemer, LiquidationGuard, LoansApi as LoansTrait, LoansMarketDataProvider, MarketInfo,
+    MarketStatus, OnExchangeRateChange,
 };
 
 pub use default_weights::WeightInfo;
@@ -247,6 +248,9 @@ pub mod pallet {
 
         /// Hook for exchangerate changes.
         type OnExchangeRateChange: OnExchangeRateChange<CurrencyId<Self>>;
+
+        /// Guard that can pause liquidations, e.g. after a sudden price move.
+        type LiquidationGuard: LiquidationGuard;
     }
 
     #[pallet::error]
@@ -1201,6 +1205,7 @@ pub mod pallet {
             collateral_asset_id: CurrencyId<T>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            T::LiquidationGuard::ensure_liquidations_enabled()?;
             Self::accrue_interest(liquidation_asset_id)?;
             Self::accrue_interest(collateral_asset_id)?;
             ensure!(!repay_amount.is_zero(), Error::<T>::InvalidAmount);
@@ -2139,6 +2144,12 @@ impl<T: Config> LoansTrait<CurrencyId<T>, AccountIdOf<T>, Amount<T>> for Pallet<
     }
 }
 
+impl<T: Config> LendTokenRedeemer<AccountIdOf<T>, Amount<T>> for Pallet<T> {
+    fn redeem_underlying(supplier: &AccountIdOf<T>, underlying_amount: &Amount<T>) -> Result<(), DispatchError> {
+        <Self as LoansTrait<CurrencyId<T>, AccountIdOf<T>, Amount<T>>>::do_redeem(supplier, underlying_amount)
+    }
+}
+
 impl<T: Config> LoansMarketDataProvider<CurrencyId<T>, BalanceOf<T>> for Pallet<T> {
     fn get_market_info(asset_id: CurrencyId<T>) -> Result<MarketInfo, DispatchError> {
         let market = Self::market(asset_id)?;
@@ -32,7 +32,7 @@ use primitives::{
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32, FixedI128};
 use sp_std::vec::Vec;
-use traits::OracleApi;
+use traits::{LiquidationGuard, OracleApi};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -153,10 +153,23 @@ impl OracleApi<Amount<Test>, CurrencyId> for CurrencyConvert {
     fn convert(amount: &Amount<Test>, to: CurrencyId) -> Result<Amount<Test>, DispatchError> {
         Ok(amount.clone()) // exchange rate simulated to 1:1
     }
+
+    fn convert_twap(amount: &Amount<Test>, to: CurrencyId, _window: u64) -> Result<Amount<Test>, DispatchError> {
+        Self::convert(amount, to)
+    }
 }
 
 type Conversion = currency::CurrencyConvert<Test, CurrencyConvert, Loans>;
 
+pub struct MockLiquidationGuard;
+
+#[cfg_attr(test, mockable)]
+impl LiquidationGuard for MockLiquidationGuard {
+    fn ensure_liquidations_enabled() -> Result<(), DispatchError> {
+        Ok(())
+    }
+}
+
 pub const DEFAULT_COLLATERAL_CURRENCY: CurrencyId = Token(DOT);
 pub const DEFAULT_NATIVE_CURRENCY: CurrencyId = Token(INTR);
 pub const DEFAULT_WRAPPED_CURRENCY: CurrencyId = Token(IBTC);
@@ -203,6 +216,7 @@ impl Config for Test {
     type RewardAssetId = GetNativeCurrencyId;
     type ReferenceAssetId = GetWrappedCurrencyId;
     type OnExchangeRateChange = ();
+    type LiquidationGuard = MockLiquidationGuard;
 }
 
 pub const LEND_DOT: CurrencyId = LendToken(1);
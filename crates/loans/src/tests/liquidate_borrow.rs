@@ -1,20 +1,20 @@
 use crate::{
     mock::{
-        new_test_ext, with_price, CurrencyConvert, Loans, RuntimeOrigin, Test, Tokens, _run_to_block, market_mock,
-        new_test_ext_no_markets, ALICE, BOB, DEFAULT_WRAPPED_CURRENCY, LEND_KBTC, LEND_KSM,
+        new_test_ext, with_price, CurrencyConvert, Loans, MockLiquidationGuard, RuntimeOrigin, Test, Tokens,
+        _run_to_block, market_mock, new_test_ext_no_markets, ALICE, BOB, DEFAULT_WRAPPED_CURRENCY, LEND_KBTC, LEND_KSM,
     },
     tests::unit,
     Amount, Error, Market, MarketState,
 };
-use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
-use mocktopus::mocking::Mockable;
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError, traits::fungibles::Inspect};
+use mocktopus::mocking::{MockResult, Mockable};
 use primitives::{
     Balance,
     CurrencyId::{self, Token},
     Rate, Ratio, DOT as DOT_CURRENCY, KBTC as KBTC_CURRENCY, KSM as KSM_CURRENCY,
 };
 use sp_runtime::FixedPointNumber;
-use traits::{LoansApi, OracleApi};
+use traits::{LiquidationGuard, LoansApi, OracleApi};
 
 const DOT: CurrencyId = Token(DOT_CURRENCY);
 const KSM: CurrencyId = Token(KSM_CURRENCY);
@@ -413,6 +413,22 @@ fn liquidator_must_not_be_borrower() {
     })
 }
 
+#[test]
+fn liquidations_can_be_paused() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+        alice_borrows_100_ksm();
+        // Adjust KSM price to make shortfall
+        CurrencyConvert::convert.mock_safe(with_price(Some((KSM, 2.into()))));
+        MockLiquidationGuard::ensure_liquidations_enabled
+            .mock_safe(|| MockResult::Return(Err(DispatchError::Other("paused"))));
+        assert_noop!(
+            Loans::liquidate_borrow(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(50), DOT),
+            DispatchError::Other("paused")
+        );
+    })
+}
+
 fn alice_borrows_100_ksm() {
     assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)));
 }
@@ -0,0 +1,21 @@
+//! Runtime API definition for the Nomination Module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use frame_support::dispatch::DispatchError;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait NominationApi<AccountId, UnbondingRequest, NominatorPosition> where
+        AccountId: Codec,
+        UnbondingRequest: Codec,
+        NominatorPosition: Codec,
+    {
+        /// Get all pending unbonding requests of a particular nominator
+        fn get_unbonding_requests(account_id: AccountId) -> Vec<UnbondingRequest>;
+
+        /// Get the stake, slashed amount and claimable rewards in every vault nominated by a particular nominator
+        fn get_nominator_positions(account_id: AccountId) -> Result<Vec<NominatorPosition>, DispatchError>;
+    }
+}
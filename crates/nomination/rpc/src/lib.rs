@@ -0,0 +1,96 @@
+//! RPC interface for the Nomination Module.
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorCode, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, DispatchError};
+use std::sync::Arc;
+
+pub use nomination_rpc_runtime_api::NominationApi as NominationRuntimeApi;
+
+#[rpc(client, server)]
+pub trait NominationApi<BlockHash, AccountId, UnbondingRequest, NominatorPosition> {
+    #[method(name = "nomination_getUnbondingRequests")]
+    fn get_unbonding_requests(&self, account_id: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<UnbondingRequest>>;
+
+    #[method(name = "nomination_getNominatorPositions")]
+    fn get_nominator_positions(
+        &self,
+        account_id: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<NominatorPosition>>;
+}
+
+fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        ErrorCode::InternalError.code(),
+        message.to_string(),
+        None::<()>,
+    )))
+}
+
+fn handle_response<T, E: std::fmt::Debug>(result: Result<Result<T, DispatchError>, E>, msg: String) -> RpcResult<T> {
+    result
+        .map_err(|err| internal_err(format!("Runtime error: {:?}: {:?}", msg, err)))?
+        .map_err(|err| internal_err(format!("Execution error: {:?}: {:?}", msg, err)))
+}
+
+/// A struct that implements the [`NominationApi`].
+pub struct Nomination<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Nomination<C, B> {
+    /// Create new `Nomination` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Nomination {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, AccountId, UnbondingRequest, NominatorPosition>
+    NominationApiServer<<Block as BlockT>::Hash, AccountId, UnbondingRequest, NominatorPosition>
+    for Nomination<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: NominationRuntimeApi<Block, AccountId, UnbondingRequest, NominatorPosition>,
+    AccountId: Codec,
+    UnbondingRequest: Codec,
+    NominatorPosition: Codec,
+{
+    fn get_unbonding_requests(
+        &self,
+        account_id: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<UnbondingRequest>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_unbonding_requests(&at, account_id)
+            .map_err(|e| internal_err(format!("Unable to fetch unbonding requests: {:?}", e)))
+    }
+
+    fn get_nominator_positions(
+        &self,
+        account_id: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<NominatorPosition>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        handle_response(
+            api.get_nominator_positions(&at, account_id),
+            "Unable to fetch nominator positions.".into(),
+        )
+    }
+}
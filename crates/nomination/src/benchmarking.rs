@@ -127,6 +127,37 @@ benchmarks! {
         assert_ok!(Nomination::<T>::_deposit_collateral(&vault_id, &nominator, amount));
 
     }: _(RawOrigin::Signed(nominator), vault_id, amount, None)
+
+    set_unbonding_period {
+    }: _(RawOrigin::Root, 100u32.into())
+
+    withdraw_unbonded {
+        setup_exchange_rate::<T>();
+        <NominationEnabled<T>>::set(true);
+
+        let vault_id = get_vault_id::<T>();
+        mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+        register_vault::<T>(vault_id.clone());
+
+        <Vaults<T>>::insert(&vault_id, true);
+
+        Nomination::<T>::set_nomination_limit(
+            RawOrigin::Signed(vault_id.account_id.clone()).into(),
+            vault_id.currencies.clone(),
+            (1u32 << 31).into()
+        ).unwrap();
+
+        let nominator: T::AccountId = account("Nominator", 0, 0);
+        mint_collateral::<T>(&nominator, (1u32 << 31).into());
+        let amount = 100u32.into();
+
+        assert_ok!(Nomination::<T>::_deposit_collateral(&vault_id, &nominator, amount));
+
+        <UnbondingPeriod<T>>::set(1u32.into());
+        assert_ok!(Nomination::<T>::_withdraw_collateral(&vault_id, &nominator, amount, Default::default()));
+        <security::Pallet<T>>::set_active_block_number(100u32.into());
+
+    }: _(RawOrigin::Signed(nominator), vault_id)
 }
 
 impl_benchmark_test_suite!(
@@ -40,6 +40,8 @@ pub trait WeightInfo {
 	fn deposit_collateral() -> Weight;
 	fn withdraw_collateral() -> Weight;
 	fn set_nomination_limit() -> Weight;
+	fn set_unbonding_period() -> Weight;
+	fn withdraw_unbonded() -> Weight;
 }
 
 /// Weights for nomination using the Substrate node and recommended hardware.
@@ -133,6 +135,37 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_ref_time(2_835_000 as u64)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: Nomination UnbondingPeriod (r:0 w:1)
+	fn set_unbonding_period() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: Nomination Unbonding (r:1 w:1)
+	// Storage: Nomination TotalUnbonding (r:1 w:1)
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: Staking Nonce (r:1 w:0)
+	// Storage: Staking TotalCurrentStake (r:1 w:1)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: Rewards Stake (r:1 w:0)
+	// Storage: Rewards RewardPerToken (r:1 w:0)
+	// Storage: Rewards RewardTally (r:1 w:1)
+	// Storage: Rewards TotalRewards (r:1 w:1)
+	// Storage: Staking RewardPerToken (r:1 w:1)
+	// Storage: Staking Stake (r:1 w:1)
+	// Storage: Staking SlashPerToken (r:1 w:0)
+	// Storage: Staking SlashTally (r:1 w:1)
+	// Storage: Staking TotalStake (r:1 w:1)
+	// Storage: Staking RewardTally (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_ref_time(271_512_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(23 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -225,5 +258,36 @@ impl WeightInfo for () {
 		Weight::from_ref_time(2_835_000 as u64)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	// Storage: Nomination UnbondingPeriod (r:0 w:1)
+	fn set_unbonding_period() -> Weight {
+		Weight::from_ref_time(2_835_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: Nomination Unbonding (r:1 w:1)
+	// Storage: Nomination TotalUnbonding (r:1 w:1)
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: Staking Nonce (r:1 w:0)
+	// Storage: Staking TotalCurrentStake (r:1 w:1)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: Rewards Stake (r:1 w:0)
+	// Storage: Rewards RewardPerToken (r:1 w:0)
+	// Storage: Rewards RewardTally (r:1 w:1)
+	// Storage: Rewards TotalRewards (r:1 w:1)
+	// Storage: Staking RewardPerToken (r:1 w:1)
+	// Storage: Staking Stake (r:1 w:1)
+	// Storage: Staking SlashPerToken (r:1 w:0)
+	// Storage: Staking SlashTally (r:1 w:1)
+	// Storage: Staking TotalStake (r:1 w:1)
+	// Storage: Staking RewardTally (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_ref_time(271_512_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(23 as u64))
+			.saturating_add(RocksDbWeight::get().writes(12 as u64))
+	}
 }
 
@@ -8,6 +8,10 @@ pub(crate) mod security {
     pub fn ensure_parachain_status_running<T: crate::Config>() -> DispatchResult {
         <security::Pallet<T>>::ensure_parachain_status_running()
     }
+
+    pub fn active_block_number<T: crate::Config>() -> T::BlockNumber {
+        <security::Pallet<T>>::active_block_number()
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -21,6 +25,12 @@ pub(crate) mod vault_registry {
         <vault_registry::Pallet<T>>::get_backing_collateral(vault_id)
     }
 
+    pub fn get_active_vault_from_id<T: crate::Config>(
+        vault_id: &DefaultVaultId<T>,
+    ) -> Result<DefaultVault<T>, DispatchError> {
+        <vault_registry::Pallet<T>>::get_active_vault_from_id(vault_id)
+    }
+
     pub fn vault_exists<T: crate::Config>(vault_id: &DefaultVaultId<T>) -> bool {
         <vault_registry::Pallet<T>>::vault_exists(vault_id)
     }
@@ -73,19 +83,40 @@ pub(crate) mod vault_registry {
         pub fn kick_nominators<T: crate::Config>(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
             <vault_registry::PoolManager<T>>::kick_nominators(vault_id)
         }
+
+        pub fn on_vault_settings_change<T: crate::Config>(vault_id: &DefaultVaultId<T>) -> Result<(), DispatchError> {
+            <vault_registry::PoolManager<T>>::on_vault_settings_change(vault_id)
+        }
     }
 }
 
 #[cfg_attr(test, mockable)]
 pub(crate) mod staking {
     use crate::BalanceOf;
+    use currency::CurrencyId;
     use frame_support::dispatch::DispatchError;
+    use sp_std::vec::Vec;
     use staking::{RewardsApi, StakingApi};
     use vault_registry::DefaultVaultId;
 
     pub fn nonce<T: crate::Config>(vault_id: &DefaultVaultId<T>) -> T::Index {
         T::VaultStaking::nonce(vault_id)
     }
+    pub fn get_nominations<T: crate::Config>(
+        nominator_id: &T::AccountId,
+    ) -> Result<Vec<(T::Index, DefaultVaultId<T>, BalanceOf<T>, BalanceOf<T>)>, DispatchError> {
+        T::VaultStaking::get_nominations(nominator_id)
+    }
+
+    pub fn compute_reward<T: crate::Config>(
+        nonce: T::Index,
+        vault_id: &DefaultVaultId<T>,
+        nominator_id: &T::AccountId,
+        currency_id: CurrencyId<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        T::VaultStaking::compute_reward(&(Some(nonce), vault_id.clone()), nominator_id, currency_id)
+    }
+
     pub fn compute_stake<T: vault_registry::Config>(
         vault_id: &DefaultVaultId<T>,
         nominator_id: &T::AccountId,
@@ -93,3 +124,18 @@ pub(crate) mod staking {
         T::VaultStaking::get_stake(&(None, vault_id.clone()), nominator_id)
     }
 }
+
+#[cfg_attr(test, mockable)]
+pub(crate) mod fee {
+    use currency::{Amount, CurrencyId};
+    use frame_support::dispatch::DispatchError;
+    use vault_registry::DefaultVaultId;
+
+    pub fn compute_vault_rewards<T: crate::Config>(
+        vault_id: &DefaultVaultId<T>,
+        nominator_id: &T::AccountId,
+        currency_id: CurrencyId<T>,
+    ) -> Result<Amount<T>, DispatchError> {
+        <fee::Pallet<T>>::compute_vault_rewards(vault_id, nominator_id, currency_id)
+    }
+}
@@ -31,12 +31,39 @@ use frame_support::{
 };
 use frame_system::{ensure_root, ensure_signed};
 pub use pallet::*;
+pub use primitives::nomination::{NominatorPosition, NominatorReward, UnbondingRequest};
 use primitives::VaultId;
+use sp_runtime::{
+    traits::{CheckedAdd, Saturating, Zero},
+    ArithmeticError,
+};
+use sp_std::vec::Vec;
 
 pub(crate) type BalanceOf<T> = <T as vault_registry::Config>::Balance;
 
+/// Unbonding chunks of a nominator, as the (active) block height from which the chunk can be
+/// released and its amount.
+pub(crate) type UnbondingChunks<T> = frame_support::BoundedVec<
+    (<T as frame_system::Config>::BlockNumber, BalanceOf<T>),
+    <T as Config>::MaxUnbondingChunks,
+>;
+
 pub(crate) type DefaultVaultId<T> = VaultId<<T as frame_system::Config>::AccountId, currency::CurrencyId<T>>;
 
+pub type DefaultUnbondingRequest<T> = UnbondingRequest<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+    BalanceOf<T>,
+    currency::CurrencyId<T>,
+>;
+
+pub type DefaultNominatorPosition<T> = NominatorPosition<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Index,
+    BalanceOf<T>,
+    currency::CurrencyId<T>,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -53,6 +80,10 @@ pub mod pallet {
 
         /// Weight information for the extrinsics in this module.
         type WeightInfo: WeightInfo;
+
+        /// The maximum number of unbonding chunks a nominator can have per vault.
+        #[pallet::constant]
+        type MaxUnbondingChunks: Get<u32>;
     }
 
     #[pallet::event]
@@ -74,6 +105,15 @@ pub mod pallet {
             nominator_id: T::AccountId,
             amount: BalanceOf<T>,
         },
+        UnbondCollateral {
+            vault_id: DefaultVaultId<T>,
+            nominator_id: T::AccountId,
+            amount: BalanceOf<T>,
+            unlock_height: T::BlockNumber,
+        },
+        UnbondingPeriodSet {
+            period: T::BlockNumber,
+        },
     }
 
     #[pallet::error]
@@ -92,6 +132,12 @@ pub mod pallet {
         NominationExceedsLimit,
         /// Vault cannot withdraw.
         CollateralizationTooLow,
+        /// Nominator is unbonding more collateral than was nominated.
+        UnbondingExceedsStake,
+        /// Nominator has no unbonded collateral ready to be withdrawn.
+        NoUnbondedCollateral,
+        /// Nominator has too many unbonding chunks for this vault.
+        TooManyUnbondingChunks,
     }
 
     #[pallet::hooks]
@@ -111,6 +157,32 @@ pub mod pallet {
     pub(super) type NominationLimit<T: Config> =
         StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, BalanceOf<T>, ValueQuery>;
 
+    /// The number of (active) blocks nominated collateral stays locked in the vault after
+    /// the nominator requested a withdrawal. Zero means withdrawals are processed instantly.
+    #[pallet::storage]
+    #[pallet::getter(fn unbonding_period)]
+    pub type UnbondingPeriod<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Collateral that nominators requested to withdraw, tagged with the staking nonce of the
+    /// vault at the time. The chunks are void once the nonce advances, since the nominators
+    /// were refunded.
+    #[pallet::storage]
+    pub(super) type Unbonding<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        DefaultVaultId<T>,
+        (T::Index, UnbondingChunks<T>),
+        OptionQuery,
+    >;
+
+    /// The total amount of collateral currently unbonding from a vault, tagged with the staking
+    /// nonce of the vault.
+    #[pallet::storage]
+    pub(super) type TotalUnbonding<T: Config> =
+        StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, (T::Index, BalanceOf<T>), OptionQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig {
         pub is_nomination_enabled: bool,
@@ -225,6 +297,38 @@ pub mod pallet {
             NominationLimit::<T>::insert(vault_id, limit);
             Ok(().into())
         }
+
+        /// Set the number of blocks nominated collateral remains locked after a withdrawal request.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the dispatch origin of this call (must be _Root_)
+        /// * `period` - the unbonding period in (active) blocks
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_unbonding_period())]
+        #[transactional]
+        pub fn set_unbonding_period(origin: OriginFor<T>, period: T::BlockNumber) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            <UnbondingPeriod<T>>::set(period);
+            Self::deposit_event(Event::<T>::UnbondingPeriodSet { period });
+            Ok(().into())
+        }
+
+        /// Release all collateral that finished unbonding from the given vault.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the nominator withdrawing the collateral
+        /// * `vault_id` - the vault the collateral was nominated to
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_unbonded())]
+        #[transactional]
+        pub fn withdraw_unbonded(origin: OriginFor<T>, vault_id: DefaultVaultId<T>) -> DispatchResultWithPostInfo {
+            let nominator_id = ensure_signed(origin)?;
+            ext::security::ensure_parachain_status_running::<T>()?;
+            Self::_withdraw_unbonded(&vault_id, &nominator_id)?;
+            Ok(().into())
+        }
     }
 }
 
@@ -245,24 +349,136 @@ impl<T: Config> Pallet<T> {
         // nominators are always allowed to withdraw from stale staking pools
         if index == nonce {
             // we can only withdraw nominated collateral if the vault is still
-            // above the secure threshold for issued + to_be_issued tokens
+            // above the secure threshold for issued + to_be_issued tokens,
+            // not counting the collateral that is already unbonding
+            let total_unbonding = Self::get_total_unbonding(vault_id);
             ensure!(
-                ext::vault_registry::is_allowed_to_withdraw_collateral::<T>(vault_id, &amount)?,
+                ext::vault_registry::is_allowed_to_withdraw_collateral::<T>(
+                    vault_id,
+                    &amount.checked_add(&total_unbonding)?
+                )?,
                 Error::<T>::CannotWithdrawCollateral
             );
 
             if &vault_id.account_id != nominator_id {
                 ensure!(Self::is_nomination_enabled(), Error::<T>::VaultNominationDisabled);
                 ensure!(Self::is_opted_in(vault_id)?, Error::<T>::VaultNotOptedInToNomination);
+
+                let period = Self::unbonding_period();
+                if !period.is_zero() {
+                    return Self::unbond_collateral(vault_id, nominator_id, &amount, period);
+                }
+            }
+        }
+
+        Self::release_collateral(vault_id, nominator_id, &amount, index)
+    }
+
+    /// Queue `amount` of nominated collateral for withdrawal after `period` blocks. The
+    /// collateral remains in the staking pool, so it keeps backing the vault and can be
+    /// slashed until it is released.
+    fn unbond_collateral(
+        vault_id: &DefaultVaultId<T>,
+        nominator_id: &T::AccountId,
+        amount: &Amount<T>,
+        period: T::BlockNumber,
+    ) -> DispatchResult {
+        let pending = Self::get_pending_unbonding(vault_id, nominator_id);
+        let stake = Self::get_nominator_collateral(vault_id, nominator_id)?;
+        ensure!(
+            pending.checked_add(amount)?.le(&stake)?,
+            Error::<T>::UnbondingExceedsStake
+        );
+
+        let nonce = ext::staking::nonce::<T>(vault_id);
+        let unlock_height = ext::security::active_block_number::<T>().saturating_add(period);
+        let mut chunks = Self::get_unbonding_chunks(vault_id, nominator_id);
+        match chunks.iter_mut().find(|(height, _)| *height == unlock_height) {
+            Some((_, unbonding)) => {
+                *unbonding = unbonding
+                    .checked_add(&amount.amount())
+                    .ok_or(ArithmeticError::Overflow)?;
             }
+            None => chunks
+                .try_push((unlock_height, amount.amount()))
+                .map_err(|_| Error::<T>::TooManyUnbondingChunks)?,
         }
+        <Unbonding<T>>::insert(nominator_id, vault_id, (nonce, chunks));
 
-        ext::vault_registry::decrease_total_backing_collateral(&vault_id.currencies, &amount)?;
+        let total_unbonding = Self::get_total_unbonding(vault_id).checked_add(amount)?;
+        <TotalUnbonding<T>>::insert(vault_id, (nonce, total_unbonding.amount()));
+        // the unbonding collateral no longer counts toward the vault's capacity
+        ext::vault_registry::pool_manager::on_vault_settings_change::<T>(vault_id)?;
+
+        Self::deposit_event(Event::<T>::UnbondCollateral {
+            vault_id: vault_id.clone(),
+            nominator_id: nominator_id.clone(),
+            amount: amount.amount(),
+            unlock_height,
+        });
+        Ok(())
+    }
+
+    pub fn _withdraw_unbonded(vault_id: &DefaultVaultId<T>, nominator_id: &T::AccountId) -> DispatchResult {
+        let nonce = ext::staking::nonce::<T>(vault_id);
+        let (chunks_nonce, mut chunks) =
+            <Unbonding<T>>::get(nominator_id, vault_id).ok_or(Error::<T>::NoUnbondedCollateral)?;
+        if chunks_nonce != nonce {
+            // the nominator was refunded when the nonce advanced
+            <Unbonding<T>>::remove(nominator_id, vault_id);
+            return Ok(());
+        }
+
+        let current_height = ext::security::active_block_number::<T>();
+        let unbonded = chunks
+            .iter()
+            .filter(|(unlock_height, _)| *unlock_height <= current_height)
+            .fold(BalanceOf::<T>::zero(), |total, (_, amount)| {
+                total.saturating_add(*amount)
+            });
+        ensure!(!unbonded.is_zero(), Error::<T>::NoUnbondedCollateral);
+
+        chunks.retain(|(unlock_height, _)| *unlock_height > current_height);
+        if chunks.is_empty() {
+            <Unbonding<T>>::remove(nominator_id, vault_id);
+        } else {
+            <Unbonding<T>>::insert(nominator_id, vault_id, (nonce, chunks));
+        }
+        let total_unbonding = Self::get_total_unbonding(vault_id).amount().saturating_sub(unbonded);
+        if total_unbonding.is_zero() {
+            <TotalUnbonding<T>>::remove(vault_id);
+        } else {
+            <TotalUnbonding<T>>::insert(vault_id, (nonce, total_unbonding));
+        }
+
+        // the stake may have been slashed while unbonding
+        let stake = Self::get_nominator_collateral(vault_id, nominator_id)?;
+        let amount = Amount::new(unbonded, vault_id.collateral_currency()).min(&stake)?;
+        if amount.is_zero() {
+            return ext::vault_registry::pool_manager::on_vault_settings_change::<T>(vault_id);
+        }
+
+        // the unbonding collateral is not issued against, but the vault may have become
+        // undercollateralized in the meantime
+        ensure!(
+            ext::vault_registry::is_allowed_to_withdraw_collateral::<T>(vault_id, &amount)?,
+            Error::<T>::CannotWithdrawCollateral
+        );
+        Self::release_collateral(vault_id, nominator_id, &amount, nonce)
+    }
+
+    fn release_collateral(
+        vault_id: &DefaultVaultId<T>,
+        nominator_id: &T::AccountId,
+        amount: &Amount<T>,
+        index: T::Index,
+    ) -> DispatchResult {
+        ext::vault_registry::decrease_total_backing_collateral(&vault_id.currencies, amount)?;
 
         // withdraw `amount` of stake from the vault staking pool
-        ext::vault_registry::pool_manager::withdraw_collateral::<T>(vault_id, nominator_id, &amount, Some(index))?;
+        ext::vault_registry::pool_manager::withdraw_collateral::<T>(vault_id, nominator_id, amount, Some(index))?;
         amount.unlock_on(&vault_id.account_id)?;
-        amount.transfer(&vault_id.account_id, &nominator_id)?;
+        amount.transfer(&vault_id.account_id, nominator_id)?;
 
         Self::deposit_event(Event::<T>::WithdrawCollateral {
             vault_id: vault_id.clone(),
@@ -341,6 +557,9 @@ impl<T: Config> Pallet<T> {
         // Update the system-wide total backing collateral
         ext::vault_registry::decrease_total_backing_collateral(&vault_id.currencies, &refunded_collateral)?;
 
+        // nominators were refunded, so nothing is left to unbond
+        <TotalUnbonding<T>>::remove(vault_id);
+
         <Vaults<T>>::remove(vault_id);
         Self::deposit_event(Event::<T>::NominationOptOut {
             vault_id: vault_id.clone(),
@@ -363,6 +582,82 @@ impl<T: Config> Pallet<T> {
         Amount::new(limit, vault_id.collateral_currency())
     }
 
+    pub fn get_total_unbonding(vault_id: &DefaultVaultId<T>) -> Amount<T> {
+        let total = match <TotalUnbonding<T>>::get(vault_id) {
+            Some((nonce, total)) if nonce == ext::staking::nonce::<T>(vault_id) => total,
+            _ => Zero::zero(),
+        };
+        Amount::new(total, vault_id.collateral_currency())
+    }
+
+    /// Get the unbonding chunks of a nominator in the current staking pool of the vault.
+    fn get_unbonding_chunks(vault_id: &DefaultVaultId<T>, nominator_id: &T::AccountId) -> UnbondingChunks<T> {
+        match <Unbonding<T>>::get(nominator_id, vault_id) {
+            Some((nonce, chunks)) if nonce == ext::staking::nonce::<T>(vault_id) => chunks,
+            _ => Default::default(),
+        }
+    }
+
+    /// Get the collateral a nominator is currently unbonding from the given vault.
+    pub fn get_pending_unbonding(vault_id: &DefaultVaultId<T>, nominator_id: &T::AccountId) -> Amount<T> {
+        let pending = Self::get_unbonding_chunks(vault_id, nominator_id)
+            .into_iter()
+            .fold(BalanceOf::<T>::zero(), |total, (_, amount)| {
+                total.saturating_add(amount)
+            });
+        Amount::new(pending, vault_id.collateral_currency())
+    }
+
+    /// Get all pending unbonding requests of the given account.
+    pub fn get_unbonding_requests(account_id: T::AccountId) -> Vec<DefaultUnbondingRequest<T>> {
+        <Unbonding<T>>::iter_prefix(account_id)
+            .filter(|(vault_id, (nonce, _))| *nonce == ext::staking::nonce::<T>(vault_id))
+            .flat_map(|(vault_id, (_, chunks))| {
+                chunks.into_iter().map(move |(unlock_height, amount)| UnbondingRequest {
+                    vault_id: vault_id.clone(),
+                    amount,
+                    unlock_height,
+                })
+            })
+            .collect()
+    }
+
+    /// Get every vault the given account nominates, including stale staking pools left behind by a
+    /// nonce increase, together with the stake, slashed amount and claimable rewards per currency.
+    pub fn get_nominator_positions(
+        account_id: T::AccountId,
+    ) -> Result<Vec<DefaultNominatorPosition<T>>, DispatchError> {
+        ext::staking::get_nominations::<T>(&account_id)?
+            .into_iter()
+            .map(|(nonce, vault_id, stake, slashed)| {
+                let stale = nonce < ext::staking::nonce::<T>(&vault_id);
+                let rewards = [
+                    vault_id.wrapped_currency(),
+                    currency::getters::get_native_currency_id::<T>(),
+                ]
+                .into_iter()
+                .map(|currency_id| {
+                    let amount = if stale {
+                        // stale pools no longer receive rewards so nothing needs to be distributed first
+                        ext::staking::compute_reward::<T>(nonce, &vault_id, &account_id, currency_id)?
+                    } else {
+                        ext::fee::compute_vault_rewards::<T>(&vault_id, &account_id, currency_id)?.amount()
+                    };
+                    Ok::<_, DispatchError>(NominatorReward { currency_id, amount })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+                Ok::<_, DispatchError>(NominatorPosition {
+                    vault_id,
+                    nonce,
+                    stale,
+                    stake,
+                    slashed,
+                    rewards,
+                })
+            })
+            .collect()
+    }
+
     pub fn get_nominator_collateral(
         vault_id: &DefaultVaultId<T>,
         nominator_id: &T::AccountId,
@@ -372,8 +667,21 @@ impl<T: Config> Pallet<T> {
     }
 }
 
-impl<T: Config> traits::NominationApi<DefaultVaultId<T>, Amount<T>> for Pallet<T> {
+impl<T: Config> traits::NominationApi<DefaultVaultId<T>, T::AccountId, Amount<T>> for Pallet<T> {
     fn deposit_vault_collateral(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> Result<(), DispatchError> {
         Pallet::<T>::_deposit_collateral(vault_id, &vault_id.account_id, amount.amount())
     }
+
+    fn deposit_nominator_collateral(
+        vault_id: &DefaultVaultId<T>,
+        nominator_id: &T::AccountId,
+        amount: &Amount<T>,
+    ) -> Result<(), DispatchError> {
+        ext::vault_registry::get_active_vault_from_id::<T>(vault_id)?;
+        Pallet::<T>::_deposit_collateral(vault_id, nominator_id, amount.amount())
+    }
+
+    fn get_total_unbonding(vault_id: &DefaultVaultId<T>) -> Amount<T> {
+        Pallet::<T>::get_total_unbonding(vault_id)
+    }
 }
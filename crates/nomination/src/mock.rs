@@ -42,7 +42,7 @@ frame_support::construct_runtime!(
         // Operational
         Security: security::{Pallet, Call, Storage, Event<T>},
         VaultRegistry: vault_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
-        Fee: fee::{Pallet, Call, Config<T>, Storage},
+        Fee: fee::{Pallet, Call, Config<T>, Storage, Event<T>},
         Oracle: oracle::{Pallet, Call, Config<T>, Storage, Event<T>},
         Nomination: nomination::{Pallet, Call, Config, Storage, Event<T>},
         Currency: currency::{Pallet},
@@ -179,6 +179,7 @@ impl vault_registry::Config for Test {
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
     type NominationApi = Nomination;
+    type LendTokens = ();
 }
 
 pub struct CurrencyConvert;
@@ -229,6 +230,7 @@ parameter_types! {
 }
 
 impl fee::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type FeePalletId = FeePalletId;
     type WeightInfo = ();
     type SignedFixedPoint = SignedFixedPoint;
@@ -239,18 +241,28 @@ impl fee::Config for Test {
     type VaultRewards = VaultRewards;
     type VaultStaking = VaultStaking;
     type OnSweep = ();
+    type DexSwap = ();
+    type Nomination = Nomination;
+    type MaxCompoundingNominators = ConstU32<32>;
     type MaxExpectedValue = MaxExpectedValue;
 }
 
+parameter_types! {
+    pub const TreasuryPalletId: PalletId = PalletId(*b"mod/trsy");
+}
+
 impl oracle::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OnExchangeRateChange = ();
+    type TreasuryPalletId = TreasuryPalletId;
+    type DexPriceSource = ();
     type WeightInfo = ();
 }
 
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type MaxUnbondingChunks = ConstU32<32>;
 }
 
 pub type TestError = Error<Test>;
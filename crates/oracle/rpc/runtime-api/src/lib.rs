@@ -6,6 +6,7 @@ use codec::{Codec, Decode, Encode};
 use frame_support::dispatch::DispatchError;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sp_std::vec::Vec;
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -33,9 +34,15 @@ fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(dese
 }
 
 sp_api::decl_runtime_apis! {
-    pub trait OracleApi<Balance, CurrencyId> where
+    pub trait OracleApi<Balance, CurrencyId, OracleKey, UnsignedFixedPoint, Moment, AccountId, OraclePerformance, AggregateInfo> where
         Balance: Codec,
         CurrencyId: Codec,
+        OracleKey: Codec,
+        UnsignedFixedPoint: Codec,
+        Moment: Codec,
+        AccountId: Codec,
+        OraclePerformance: Codec,
+        AggregateInfo: Codec,
     {
         fn wrapped_to_collateral(
             amount: BalanceWrapper<Balance>,
@@ -46,5 +53,20 @@ sp_api::decl_runtime_apis! {
             amount: BalanceWrapper<Balance>,
             currency_id: CurrencyId,
         ) -> Result<BalanceWrapper<Balance>, DispatchError>;
+
+        /// Get the recorded aggregates of the key as (timestamp, price), oldest first
+        fn get_price_history(key: OracleKey) -> Vec<(Moment, UnsignedFixedPoint)>;
+
+        /// Get the time-weighted average price of the key over the last `window`
+        fn get_twap(key: OracleKey, window: Moment) -> Result<UnsignedFixedPoint, DispatchError>;
+
+        /// Get the submission record of the oracle
+        fn get_oracle_performance(oracle_id: AccountId) -> OraclePerformance;
+
+        /// Get the keys that have been fed by the oracles
+        fn get_oracle_keys() -> Vec<OracleKey>;
+
+        /// Get the aggregate of the key, its expiry and the latest value of every feeder
+        fn get_aggregate_info(key: OracleKey) -> AggregateInfo;
     }
 }
@@ -18,10 +18,25 @@ use std::sync::Arc;
 pub use oracle_rpc_runtime_api::{BalanceWrapper, OracleApi as OracleRuntimeApi};
 
 #[rpc(client, server)]
-pub trait OracleApi<BlockHash, Balance, CurrencyId>
-where
+pub trait OracleApi<
+    BlockHash,
+    Balance,
+    CurrencyId,
+    OracleKey,
+    UnsignedFixedPoint,
+    Moment,
+    AccountId,
+    OraclePerformance,
+    AggregateInfo,
+> where
     Balance: Codec + MaybeDisplay + MaybeFromStr,
     CurrencyId: Codec,
+    OracleKey: Codec,
+    UnsignedFixedPoint: Codec + MaybeDisplay + MaybeFromStr,
+    Moment: Codec,
+    AccountId: Codec,
+    OraclePerformance: Codec,
+    AggregateInfo: Codec,
 {
     #[method(name = "oracle_wrappedToCollateral")]
     fn wrapped_to_collateral(
@@ -38,6 +53,21 @@ where
         currency_id: CurrencyId,
         at: Option<BlockHash>,
     ) -> RpcResult<BalanceWrapper<Balance>>;
+
+    #[method(name = "oracle_getPriceHistory")]
+    fn get_price_history(&self, key: OracleKey, at: Option<BlockHash>) -> RpcResult<Vec<(Moment, UnsignedFixedPoint)>>;
+
+    #[method(name = "oracle_getTwap")]
+    fn get_twap(&self, key: OracleKey, window: Moment, at: Option<BlockHash>) -> RpcResult<UnsignedFixedPoint>;
+
+    #[method(name = "oracle_getOraclePerformance")]
+    fn get_oracle_performance(&self, oracle_id: AccountId, at: Option<BlockHash>) -> RpcResult<OraclePerformance>;
+
+    #[method(name = "oracle_getOracleKeys")]
+    fn get_oracle_keys(&self, at: Option<BlockHash>) -> RpcResult<Vec<OracleKey>>;
+
+    #[method(name = "oracle_getAggregateInfo")]
+    fn get_aggregate_info(&self, key: OracleKey, at: Option<BlockHash>) -> RpcResult<AggregateInfo>;
 }
 
 fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
@@ -71,13 +101,51 @@ fn handle_response<T, E: std::fmt::Debug>(result: Result<Result<T, DispatchError
 }
 
 #[async_trait]
-impl<C, Block, Balance, CurrencyId> OracleApiServer<<Block as BlockT>::Hash, Balance, CurrencyId> for Oracle<C, Block>
+impl<
+        C,
+        Block,
+        Balance,
+        CurrencyId,
+        OracleKey,
+        UnsignedFixedPoint,
+        Moment,
+        AccountId,
+        OraclePerformance,
+        AggregateInfo,
+    >
+    OracleApiServer<
+        <Block as BlockT>::Hash,
+        Balance,
+        CurrencyId,
+        OracleKey,
+        UnsignedFixedPoint,
+        Moment,
+        AccountId,
+        OraclePerformance,
+        AggregateInfo,
+    > for Oracle<C, Block>
 where
     Block: BlockT,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: OracleRuntimeApi<Block, Balance, CurrencyId>,
+    C::Api: OracleRuntimeApi<
+        Block,
+        Balance,
+        CurrencyId,
+        OracleKey,
+        UnsignedFixedPoint,
+        Moment,
+        AccountId,
+        OraclePerformance,
+        AggregateInfo,
+    >,
     Balance: Codec + MaybeDisplay + MaybeFromStr,
     CurrencyId: Codec,
+    OracleKey: Codec,
+    UnsignedFixedPoint: Codec + MaybeDisplay + MaybeFromStr,
+    Moment: Codec,
+    AccountId: Codec,
+    OraclePerformance: Codec,
+    AggregateInfo: Codec,
 {
     fn wrapped_to_collateral(
         &self,
@@ -102,4 +170,56 @@ where
 
         handle_response(api.collateral_to_wrapped(&at, amount, currency_id))
     }
+
+    fn get_price_history(
+        &self,
+        key: OracleKey,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(Moment, UnsignedFixedPoint)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_price_history(&at, key)
+            .map_err(|e| internal_err(format!("Unable to fetch price history: {:?}", e)))
+    }
+
+    fn get_twap(
+        &self,
+        key: OracleKey,
+        window: Moment,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<UnsignedFixedPoint> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        handle_response(api.get_twap(&at, key, window))
+    }
+
+    fn get_oracle_performance(
+        &self,
+        oracle_id: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<OraclePerformance> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_oracle_performance(&at, oracle_id)
+            .map_err(|e| internal_err(format!("Unable to fetch oracle performance: {:?}", e)))
+    }
+
+    fn get_oracle_keys(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<OracleKey>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_oracle_keys(&at)
+            .map_err(|e| internal_err(format!("Unable to fetch oracle keys: {:?}", e)))
+    }
+
+    fn get_aggregate_info(&self, key: OracleKey, at: Option<<Block as BlockT>::Hash>) -> RpcResult<AggregateInfo> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_aggregate_info(&at, key)
+            .map_err(|e| internal_err(format!("Unable to fetch aggregate info: {:?}", e)))
+    }
 }
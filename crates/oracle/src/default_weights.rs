@@ -35,9 +35,21 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for oracle.
 pub trait WeightInfo {
 	fn on_initialize() -> Weight;
+	fn sample_fallback_price() -> Weight;
 	fn feed_values(u: u32, ) -> Weight;
 	fn insert_authorized_oracle() -> Weight;
 	fn remove_authorized_oracle() -> Weight;
+	fn set_minimum_submissions() -> Weight;
+	fn set_max_deviation() -> Weight;
+	fn set_price_change_limit() -> Weight;
+	fn accept_pending_price() -> Weight;
+	fn register_oracle() -> Weight;
+	fn deregister_oracle() -> Weight;
+	fn withdraw_oracle_bond() -> Weight;
+	fn slash_oracle() -> Weight;
+	fn set_oracle_bond_parameters() -> Weight;
+	fn set_fallback_source() -> Weight;
+	fn set_max_delay() -> Weight;
 }
 
 /// Weights for oracle using the Substrate node and recommended hardware.
@@ -50,6 +62,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_ref_time(5_971_000 as u64)
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	// Storage: Oracle FallbackSources (r:1 w:0)
+	// Storage: Oracle FallbackSampledAt (r:1 w:1)
+	// Storage: Oracle FallbackSamples (r:1 w:1)
+	// Storage: DexGeneral PairStatuses (r:3 w:0)
+	// Storage: Tokens Accounts (r:6 w:0)
+	fn sample_fallback_price() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64)) // Dummy until we rerun benchmarks
+	}
 	// Storage: Oracle AuthorizedOracles (r:1 w:0)
 	// Storage: Timestamp Now (r:1 w:0)
 	// Storage: Oracle RawValuesUpdated (r:0 w:1)
@@ -71,6 +93,87 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_ref_time(4_367_000 as u64)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: Oracle MinimumSubmissions (r:0 w:1)
+	fn set_minimum_submissions() -> Weight {
+		Weight::from_ref_time(4_367_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle MaxDeviation (r:0 w:1)
+	fn set_max_deviation() -> Weight {
+		Weight::from_ref_time(4_367_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle PriceChangeLimits (r:0 w:1)
+	// Storage: Oracle PriceReference (r:0 w:1)
+	// Storage: Oracle PendingPrice (r:1 w:1)
+	// Storage: Security Errors (r:1 w:1)
+	fn set_price_change_limit() -> Weight {
+		Weight::from_ref_time(10_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Oracle PendingPrice (r:2 w:1)
+	// Storage: Oracle PriceReference (r:0 w:1)
+	// Storage: Oracle Aggregate (r:0 w:1)
+	// Storage: Security Errors (r:1 w:1)
+	fn accept_pending_price() -> Weight {
+		Weight::from_ref_time(10_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Oracle BondParameters (r:1 w:0)
+	// Storage: Oracle AuthorizedOracles (r:1 w:1)
+	// Storage: Oracle OracleBonds (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn register_oracle() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle OracleBonds (r:1 w:0)
+	// Storage: Oracle OracleUnbonding (r:1 w:1)
+	// Storage: Oracle BondParameters (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle AuthorizedOracles (r:0 w:1)
+	fn deregister_oracle() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Oracle OracleUnbonding (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle OracleBonds (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn withdraw_oracle_bond() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle OracleBonds (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn slash_oracle() -> Weight {
+		Weight::from_ref_time(60_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Oracle BondParameters (r:0 w:1)
+	fn set_oracle_bond_parameters() -> Weight {
+		Weight::from_ref_time(4_367_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle FallbackSamples (r:0 w:1)
+	// Storage: Oracle FallbackSampledAt (r:0 w:1)
+	// Storage: Oracle FallbackSources (r:0 w:1)
+	fn set_fallback_source() -> Weight {
+		Weight::from_ref_time(5_124_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle MaxDelays (r:0 w:1)
+	fn set_max_delay() -> Weight {
+		Weight::from_ref_time(4_412_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -82,6 +185,16 @@ impl WeightInfo for () {
 		Weight::from_ref_time(5_971_000 as u64)
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	// Storage: Oracle FallbackSources (r:1 w:0)
+	// Storage: Oracle FallbackSampledAt (r:1 w:1)
+	// Storage: Oracle FallbackSamples (r:1 w:1)
+	// Storage: DexGeneral PairStatuses (r:3 w:0)
+	// Storage: Tokens Accounts (r:6 w:0)
+	fn sample_fallback_price() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64)) // dummy until we rerun benchmarks
+	}
 	// Storage: Oracle AuthorizedOracles (r:1 w:0)
 	// Storage: Timestamp Now (r:1 w:0)
 	// Storage: Oracle RawValuesUpdated (r:0 w:1)
@@ -103,5 +216,86 @@ impl WeightInfo for () {
 		Weight::from_ref_time(4_367_000 as u64)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	// Storage: Oracle MinimumSubmissions (r:0 w:1)
+	fn set_minimum_submissions() -> Weight {
+		Weight::from_ref_time(4_367_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle MaxDeviation (r:0 w:1)
+	fn set_max_deviation() -> Weight {
+		Weight::from_ref_time(4_367_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle PriceChangeLimits (r:0 w:1)
+	// Storage: Oracle PriceReference (r:0 w:1)
+	// Storage: Oracle PendingPrice (r:1 w:1)
+	// Storage: Security Errors (r:1 w:1)
+	fn set_price_change_limit() -> Weight {
+		Weight::from_ref_time(10_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Oracle PendingPrice (r:2 w:1)
+	// Storage: Oracle PriceReference (r:0 w:1)
+	// Storage: Oracle Aggregate (r:0 w:1)
+	// Storage: Security Errors (r:1 w:1)
+	fn accept_pending_price() -> Weight {
+		Weight::from_ref_time(10_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Oracle BondParameters (r:1 w:0)
+	// Storage: Oracle AuthorizedOracles (r:1 w:1)
+	// Storage: Oracle OracleBonds (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn register_oracle() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle OracleBonds (r:1 w:0)
+	// Storage: Oracle OracleUnbonding (r:1 w:1)
+	// Storage: Oracle BondParameters (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle AuthorizedOracles (r:0 w:1)
+	fn deregister_oracle() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Oracle OracleUnbonding (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle OracleBonds (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn withdraw_oracle_bond() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle OracleBonds (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn slash_oracle() -> Weight {
+		Weight::from_ref_time(60_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Oracle BondParameters (r:0 w:1)
+	fn set_oracle_bond_parameters() -> Weight {
+		Weight::from_ref_time(4_367_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle FallbackSamples (r:0 w:1)
+	// Storage: Oracle FallbackSampledAt (r:0 w:1)
+	// Storage: Oracle FallbackSources (r:0 w:1)
+	fn set_fallback_source() -> Weight {
+		Weight::from_ref_time(5_124_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle MaxDelays (r:0 w:1)
+	fn set_max_delay() -> Weight {
+		Weight::from_ref_time(4_412_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
 
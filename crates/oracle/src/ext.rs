@@ -15,6 +15,14 @@ pub(crate) mod security {
         <security::Pallet<T>>::recover_from_oracle_offline()
     }
 
+    pub fn recover_from_price_circuit_breaker<T: crate::Config>() {
+        <security::Pallet<T>>::recover_from_price_circuit_breaker()
+    }
+
+    pub fn ensure_liquidations_enabled<T: crate::Config>() -> DispatchResult {
+        <security::Pallet<T>>::ensure_liquidations_enabled()
+    }
+
     pub(crate) fn set_status<T: crate::Config>(status_code: StatusCode) {
         <security::Pallet<T>>::set_status(status_code)
     }
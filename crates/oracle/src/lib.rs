@@ -36,16 +36,20 @@ use frame_support::{
     traits::Get,
     transactional,
     weights::Weight,
+    PalletId,
 };
 use frame_system::{ensure_root, ensure_signed};
 use scale_info::TypeInfo;
 use security::{ErrorCode, StatusCode};
-use sp_runtime::traits::*;
+use sp_runtime::{traits::*, ArithmeticError, RuntimeDebug};
 use sp_std::{convert::TryInto, vec::Vec};
-use traits::OracleApi;
+use traits::{DexPriceSource, LiquidationGuard, OracleApi};
 
 pub use pallet::*;
-pub use primitives::{oracle::Key as OracleKey, CurrencyId, TruncateFixedPointToInt};
+pub use primitives::{
+    oracle::{AggregateInfo, Key as OracleKey, OraclePerformance},
+    CurrencyId, TruncateFixedPointToInt,
+};
 pub use traits::OnExchangeRateChange;
 
 #[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, Ord, PartialOrd, TypeInfo, MaxEncodedLen)]
@@ -54,6 +58,43 @@ pub struct TimestampedValue<Value, Moment> {
     pub timestamp: Moment,
 }
 
+/// Maximum relative change of an aggregate within a time window.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+pub struct PriceChangeLimit<Value, Moment> {
+    pub max_change: Value,
+    pub window: Moment,
+}
+
+/// Governance parameters of oracle bonding and slashing.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct OracleBondParameters<Balance, Value, Moment> {
+    /// Native currency bond required to register as oracle, zero disables registration
+    pub bond: Balance,
+    /// Fraction of the bond slashed once an oracle reaches `max_faults`
+    pub slash_fraction: Value,
+    /// Number of stale or deviating submissions after which an oracle is slashed, zero
+    /// disables automatic slashing
+    pub max_faults: u32,
+    /// Time after deregistering before the bond can be withdrawn
+    pub unbonding_period: Moment,
+}
+
+/// DEX route used to derive a fallback price when the feeders of a key are offline.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct FallbackSource<CurrencyId, Balance, Moment> {
+    /// Swap path from the wrapped currency to the collateral currency of the key
+    pub path: Vec<CurrencyId>,
+    /// Amount of the wrapped currency quoted to sample the spot price
+    pub amount_in: Balance,
+    /// Period over which the sampled spot prices are averaged
+    pub window: Moment,
+    /// Minimum time between two sampled spot prices
+    pub interval: Moment,
+}
+
+/// Maximum number of aggregates kept in the price history of a key.
+pub const MAX_PRICE_HISTORY: u32 = 100;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -72,6 +113,13 @@ pub mod pallet {
         /// Hook for aggregate changes.
         type OnExchangeRateChange: OnExchangeRateChange<CurrencyId>;
 
+        /// The treasury pallet account for slashed oracle bonds.
+        #[pallet::constant]
+        type TreasuryPalletId: Get<PalletId>;
+
+        /// DEX used to derive fallback prices when the feeders are offline.
+        type DexPriceSource: DexPriceSource<CurrencyId, BalanceOf<Self>>;
+
         /// Weight information for the extrinsics in this module.
         type WeightInfo: WeightInfo;
     }
@@ -94,6 +142,78 @@ pub mod pallet {
         OracleRemoved {
             oracle_id: T::AccountId,
         },
+        /// Event emitted when a submission deviates too far from the median and is
+        /// excluded from the aggregate
+        OracleValueRejected {
+            oracle_id: T::AccountId,
+            key: OracleKey,
+            value: T::UnsignedFixedPoint,
+            median: T::UnsignedFixedPoint,
+        },
+        MinimumSubmissionsChange {
+            minimum_submissions: u32,
+        },
+        MaxDeviationChange {
+            max_deviation: T::UnsignedFixedPoint,
+        },
+        /// Event emitted when an aggregate exceeds its price change limit; the previous
+        /// price is held and liquidations are paused
+        PriceCircuitBreakerTripped {
+            key: OracleKey,
+            price: T::UnsignedFixedPoint,
+            proposed_price: T::UnsignedFixedPoint,
+        },
+        /// Event emitted when a held price is accepted, either after a confirmation
+        /// window or by governance
+        PendingPriceAccepted {
+            key: OracleKey,
+            price: T::UnsignedFixedPoint,
+        },
+        PriceChangeLimitChange {
+            key: OracleKey,
+            max_change: T::UnsignedFixedPoint,
+            window: T::Moment,
+        },
+        OracleRegistered {
+            oracle_id: T::AccountId,
+            bond: BalanceOf<T>,
+        },
+        OracleDeregistered {
+            oracle_id: T::AccountId,
+            unbonding_until: T::Moment,
+        },
+        OracleBondWithdrawn {
+            oracle_id: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Event emitted when part of an oracle's bond is moved to the treasury
+        OracleSlashed {
+            oracle_id: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        OracleBondParametersChange {
+            bond: BalanceOf<T>,
+            slash_fraction: T::UnsignedFixedPoint,
+            max_faults: u32,
+            unbonding_period: T::Moment,
+        },
+        FallbackSourceChange {
+            key: OracleKey,
+            source: Option<FallbackSource<CurrencyId, BalanceOf<T>, T::Moment>>,
+        },
+        /// Event emitted when the aggregate of a key is derived from the DEX because
+        /// too few feeders submitted fresh values
+        FallbackPriceActivated {
+            key: OracleKey,
+        },
+        /// Event emitted when the feeders of a key are back and the fallback is no longer used
+        FallbackPriceDeactivated {
+            key: OracleKey,
+        },
+        MaxDelayChange {
+            key: OracleKey,
+            max_delay: Option<T::Moment>,
+        },
     }
 
     #[pallet::error]
@@ -104,13 +224,29 @@ pub mod pallet {
         MissingExchangeRate,
         /// Unable to convert value
         TryIntoIntError,
+        /// No price is held by the circuit breaker for this key
+        NoPendingPrice,
+        /// Registration requires a non-zero oracle bond
+        OracleBondingDisabled,
+        /// The oracle already has a bond
+        OracleAlreadyRegistered,
+        /// The account has no oracle bond
+        NoOracleBond,
+        /// The oracle has already deregistered
+        OracleAlreadyUnbonding,
+        /// The oracle has not deregistered
+        OracleNotUnbonding,
+        /// The unbonding period has not expired yet
+        UnbondingPeriodNotExpired,
+        /// Fallback sources are only supported for exchange rates, with a path from the
+        /// wrapped currency to the collateral currency
+        InvalidFallbackSource,
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
         fn on_initialize(n: T::BlockNumber) -> Weight {
-            Self::begin_block(n);
-            <T as Config>::WeightInfo::on_initialize()
+            Self::begin_block(n)
         }
     }
 
@@ -141,6 +277,79 @@ pub mod pallet {
     #[pallet::getter(fn max_delay)]
     pub type MaxDelay<T: Config> = StorageValue<_, T::Moment, ValueQuery>;
 
+    /// Maximum delay (milliseconds) for a reported value of the given key to be used,
+    /// overriding `MaxDelay`
+    #[pallet::storage]
+    pub type MaxDelays<T: Config> = StorageMap<_, Blake2_128Concat, OracleKey, T::Moment>;
+
+    /// Minimum number of fresh submissions required for a key before an aggregate is produced
+    #[pallet::storage]
+    #[pallet::getter(fn minimum_submissions)]
+    pub type MinimumSubmissions<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Maximum relative deviation from the median for a submission to be included in the
+    /// aggregate; zero disables outlier rejection
+    #[pallet::storage]
+    #[pallet::getter(fn max_deviation)]
+    pub type MaxDeviation<T: Config> = StorageValue<_, UnsignedFixedPoint<T>, ValueQuery>;
+
+    /// Maximum change of the aggregate per time window for the given key
+    #[pallet::storage]
+    pub type PriceChangeLimits<T: Config> =
+        StorageMap<_, Blake2_128Concat, OracleKey, PriceChangeLimit<UnsignedFixedPoint<T>, T::Moment>>;
+
+    /// Aggregate at the start of the current price change window
+    #[pallet::storage]
+    pub type PriceReference<T: Config> =
+        StorageMap<_, Blake2_128Concat, OracleKey, TimestampedValue<UnsignedFixedPoint<T>, T::Moment>>;
+
+    /// Aggregate held back by the circuit breaker, timestamped with the time it was first held
+    #[pallet::storage]
+    pub type PendingPrice<T: Config> =
+        StorageMap<_, Blake2_128Concat, OracleKey, TimestampedValue<UnsignedFixedPoint<T>, T::Moment>>;
+
+    /// The last `MAX_PRICE_HISTORY` aggregates of a key, oldest first. All but the latest entry are at
+    /// least one `MaxDelay` interval apart.
+    #[pallet::storage]
+    pub type PriceHistory<T: Config> =
+        StorageMap<_, Blake2_128Concat, OracleKey, Vec<TimestampedValue<UnsignedFixedPoint<T>, T::Moment>>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn oracle_bond_parameters)]
+    pub type BondParameters<T: Config> =
+        StorageValue<_, OracleBondParameters<BalanceOf<T>, UnsignedFixedPoint<T>, T::Moment>, ValueQuery>;
+
+    /// Native currency reserved by registered oracles
+    #[pallet::storage]
+    pub type OracleBonds<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>>;
+
+    /// Time from which a deregistered oracle can withdraw its bond
+    #[pallet::storage]
+    pub type OracleUnbonding<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::Moment>;
+
+    /// Submission record of every oracle
+    #[pallet::storage]
+    pub type OracleStats<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, OraclePerformance<T::Moment, UnsignedFixedPoint<T>>, ValueQuery>;
+
+    /// DEX routes of the keys that governance opted in to a fallback price
+    #[pallet::storage]
+    pub type FallbackSources<T: Config> =
+        StorageMap<_, Blake2_128Concat, OracleKey, FallbackSource<CurrencyId, BalanceOf<T>, T::Moment>>;
+
+    /// DEX spot prices sampled once per interval for the keys with a fallback source, oldest first
+    #[pallet::storage]
+    pub type FallbackSamples<T: Config> =
+        StorageMap<_, Blake2_128Concat, OracleKey, Vec<TimestampedValue<UnsignedFixedPoint<T>, T::Moment>>, ValueQuery>;
+
+    /// Time at which the DEX route of a key was last sampled
+    #[pallet::storage]
+    pub type FallbackSampledAt<T: Config> = StorageMap<_, Blake2_128Concat, OracleKey, T::Moment>;
+
+    /// True if the current aggregate of the key is derived from its fallback source
+    #[pallet::storage]
+    pub type FallbackActive<T: Config> = StorageMap<_, Blake2_128Concat, OracleKey, bool, ValueQuery>;
+
     // Oracles allowed to set the exchange rate, maps to the name
     #[pallet::storage]
     #[pallet::getter(fn authorized_oracles)]
@@ -208,6 +417,16 @@ pub mod pallet {
 
             // fail if the signer is not an authorized oracle
             ensure!(Self::is_authorized(&signer), Error::<T>::InvalidOracleSource);
+            // once bonding is enabled, authorized oracles also need to be bonded
+            ensure!(
+                Self::oracle_bond_parameters().bond.is_zero() || OracleBonds::<T>::contains_key(&signer),
+                Error::<T>::NoOracleBond
+            );
+
+            // a slashed oracle loses its authorization, so its values are discarded
+            if Self::record_submission(&signer, &values)? {
+                return Ok(Pays::No.into());
+            }
 
             Self::_feed_values(signer, values);
             Ok(Pays::No.into())
@@ -248,21 +467,268 @@ pub mod pallet {
             Self::deposit_event(Event::OracleRemoved { oracle_id: account_id });
             Ok(())
         }
+
+        /// Sets the minimum number of fresh submissions needed to produce an aggregate
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `minimum_submissions` - the new minimum number of submissions per key
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_minimum_submissions())]
+        #[transactional]
+        pub fn set_minimum_submissions(origin: OriginFor<T>, minimum_submissions: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            <MinimumSubmissions<T>>::put(minimum_submissions);
+            Self::deposit_event(Event::MinimumSubmissionsChange { minimum_submissions });
+            Ok(())
+        }
+
+        /// Sets the maximum relative deviation from the median for a submission to be
+        /// included in the aggregate (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `max_deviation` - the new maximum deviation, e.g. 0.05 for 5%; zero disables the check
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_deviation())]
+        #[transactional]
+        pub fn set_max_deviation(origin: OriginFor<T>, max_deviation: T::UnsignedFixedPoint) -> DispatchResult {
+            ensure_root(origin)?;
+            <MaxDeviation<T>>::put(max_deviation);
+            Self::deposit_event(Event::MaxDeviationChange { max_deviation });
+            Ok(())
+        }
+
+        /// Sets the maximum change of the aggregate within a time window (only executable by
+        /// the Root account). A larger change is held back until it persists for another
+        /// window or is accepted by governance.
+        ///
+        /// # Arguments
+        /// * `key` - the key to limit
+        /// * `max_change` - the maximum relative change, e.g. 0.2 for 20%; zero removes the limit
+        /// * `window` - the length of the window in milliseconds
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_price_change_limit())]
+        #[transactional]
+        pub fn set_price_change_limit(
+            origin: OriginFor<T>,
+            key: OracleKey,
+            max_change: T::UnsignedFixedPoint,
+            window: T::Moment,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if max_change.is_zero() {
+                <PriceChangeLimits<T>>::remove(&key);
+                <PriceReference<T>>::remove(&key);
+                <PendingPrice<T>>::remove(&key);
+                Self::recover_from_price_circuit_breaker();
+            } else {
+                <PriceChangeLimits<T>>::insert(&key, PriceChangeLimit { max_change, window });
+            }
+            Self::deposit_event(Event::PriceChangeLimitChange {
+                key,
+                max_change,
+                window,
+            });
+            Ok(())
+        }
+
+        /// Accepts the price held back by the circuit breaker (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `key` - the key of the held price
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::accept_pending_price())]
+        #[transactional]
+        pub fn accept_pending_price(origin: OriginFor<T>, key: OracleKey) -> DispatchResult {
+            ensure_root(origin)?;
+            let pending = <PendingPrice<T>>::get(&key).ok_or(Error::<T>::NoPendingPrice)?;
+            Self::accept_pending_price_for(&key, pending.value);
+            Aggregate::<T>::insert(&key, pending.value);
+            Self::record_price(&key, pending.value);
+            if let OracleKey::ExchangeRate(currency_id) = &key {
+                T::OnExchangeRateChange::on_exchange_rate_change(currency_id);
+            }
+            Ok(())
+        }
+
+        /// Bonds the caller by reserving the oracle bond in the native currency. The caller
+        /// must have been approved as oracle by governance through `insert_authorized_oracle`.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_oracle())]
+        #[transactional]
+        pub fn register_oracle(origin: OriginFor<T>) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let bond = Self::oracle_bond_parameters().bond;
+            ensure!(!bond.is_zero(), Error::<T>::OracleBondingDisabled);
+            ensure!(Self::is_authorized(&signer), Error::<T>::InvalidOracleSource);
+            ensure!(
+                !OracleBonds::<T>::contains_key(&signer),
+                Error::<T>::OracleAlreadyRegistered
+            );
+
+            Amount::<T>::new(bond, T::GetNativeCurrencyId::get()).lock_on(&signer)?;
+            OracleBonds::<T>::insert(&signer, bond);
+            Self::deposit_event(Event::OracleRegistered {
+                oracle_id: signer,
+                bond,
+            });
+            Ok(())
+        }
+
+        /// Stops the caller from feeding values and starts the unbonding period of its bond. An
+        /// oracle that lost its authorization by being slashed keeps the rest of its bond locked
+        /// until it deregisters through this call.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::deregister_oracle())]
+        #[transactional]
+        pub fn deregister_oracle(origin: OriginFor<T>) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            ensure!(OracleBonds::<T>::contains_key(&signer), Error::<T>::NoOracleBond);
+            ensure!(
+                !OracleUnbonding::<T>::contains_key(&signer),
+                Error::<T>::OracleAlreadyUnbonding
+            );
+
+            let unbonding_until =
+                Self::get_current_time().saturating_add(Self::oracle_bond_parameters().unbonding_period);
+            OracleUnbonding::<T>::insert(&signer, unbonding_until);
+            <AuthorizedOracles<T>>::remove(&signer);
+            Self::deposit_event(Event::OracleDeregistered {
+                oracle_id: signer,
+                unbonding_until,
+            });
+            Ok(())
+        }
+
+        /// Releases the remaining bond of the caller once its unbonding period has expired
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_oracle_bond())]
+        #[transactional]
+        pub fn withdraw_oracle_bond(origin: OriginFor<T>) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let unbonding_until = OracleUnbonding::<T>::get(&signer).ok_or(Error::<T>::OracleNotUnbonding)?;
+            ensure!(
+                Self::get_current_time() >= unbonding_until,
+                Error::<T>::UnbondingPeriodNotExpired
+            );
+
+            let amount = OracleBonds::<T>::take(&signer).ok_or(Error::<T>::NoOracleBond)?;
+            OracleUnbonding::<T>::remove(&signer);
+            Amount::<T>::new(amount, T::GetNativeCurrencyId::get()).unlock_on(&signer)?;
+            Self::deposit_event(Event::OracleBondWithdrawn {
+                oracle_id: signer,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Moves part of an oracle's bond to the treasury (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `oracle_id` - the oracle to slash
+        /// * `amount` - the amount to slash, capped at the remaining bond
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::slash_oracle())]
+        #[transactional]
+        pub fn slash_oracle(origin: OriginFor<T>, oracle_id: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::slash_bond(&oracle_id, amount)
+        }
+
+        /// Sets the oracle bonding and slashing parameters (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `parameters` - the new bond, slash fraction, fault limit and unbonding period
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_oracle_bond_parameters())]
+        #[transactional]
+        pub fn set_oracle_bond_parameters(
+            origin: OriginFor<T>,
+            parameters: OracleBondParameters<BalanceOf<T>, UnsignedFixedPoint<T>, T::Moment>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            BondParameters::<T>::put(parameters);
+            Self::deposit_event(Event::OracleBondParametersChange {
+                bond: parameters.bond,
+                slash_fraction: parameters.slash_fraction,
+                max_faults: parameters.max_faults,
+                unbonding_period: parameters.unbonding_period,
+            });
+            Ok(())
+        }
+
+        /// Opts a key in to a DEX-derived fallback price, used while too few feeders submit
+        /// fresh values (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `key` - the exchange rate key to derive a fallback for
+        /// * `source` - the DEX route to sample, or `None` to opt the key out
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_fallback_source())]
+        #[transactional]
+        pub fn set_fallback_source(
+            origin: OriginFor<T>,
+            key: OracleKey,
+            source: Option<FallbackSource<CurrencyId, BalanceOf<T>, T::Moment>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            FallbackSamples::<T>::remove(&key);
+            FallbackSampledAt::<T>::remove(&key);
+            match source {
+                Some(ref source) => {
+                    let currency_id = match key {
+                        OracleKey::ExchangeRate(currency_id) => currency_id,
+                        _ => return Err(Error::<T>::InvalidFallbackSource.into()),
+                    };
+                    ensure!(
+                        source.path.len() >= 2
+                            && source.path.first() == Some(&T::GetWrappedCurrencyId::get())
+                            && source.path.last() == Some(&currency_id)
+                            && !source.amount_in.is_zero(),
+                        Error::<T>::InvalidFallbackSource
+                    );
+                    FallbackSources::<T>::insert(&key, source);
+                }
+                None => FallbackSources::<T>::remove(&key),
+            }
+            Self::deposit_event(Event::FallbackSourceChange { key, source });
+            Ok(())
+        }
+
+        /// Sets the maximum delay for reported values of a key to be used (only executable by
+        /// the Root account)
+        ///
+        /// # Arguments
+        /// * `key` - the key to set the delay for
+        /// * `max_delay` - the delay in milliseconds, or `None` to use the global `MaxDelay`
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_delay())]
+        #[transactional]
+        pub fn set_max_delay(origin: OriginFor<T>, key: OracleKey, max_delay: Option<T::Moment>) -> DispatchResult {
+            ensure_root(origin)?;
+            match max_delay {
+                Some(max_delay) => MaxDelays::<T>::insert(&key, max_delay),
+                None => MaxDelays::<T>::remove(&key),
+            }
+            Self::deposit_event(Event::MaxDelayChange { key, max_delay });
+            Ok(())
+        }
     }
 }
 
 #[cfg_attr(test, mockable)]
 impl<T: Config> Pallet<T> {
     // public only for testing purposes
-    pub fn begin_block(_height: T::BlockNumber) {
+    pub fn begin_block(_height: T::BlockNumber) -> Weight {
         // read to a temporary value, because we can't alter the map while we iterate over it
         let raw_values_updated: Vec<_> = RawValuesUpdated::<T>::iter().collect();
 
         let current_time = Self::get_current_time();
+        let (sources, sampled) = Self::sample_fallback_prices(current_time);
 
         let mut updated_items = Vec::new();
         for (key, is_updated) in raw_values_updated.iter() {
-            if *is_updated || Self::is_outdated(key, current_time) {
+            if *is_updated || Self::is_outdated(key, current_time) || Self::is_awaiting_fallback(key, &sampled) {
                 let new_value = Self::update_aggregate(key);
                 updated_items.push((key.clone(), new_value));
             }
@@ -285,6 +751,9 @@ impl<T: Config> Pallet<T> {
                 Self::report_oracle_offline();
             }
         }
+
+        <T as Config>::WeightInfo::on_initialize()
+            .saturating_add(<T as Config>::WeightInfo::sample_fallback_price().saturating_mul(sources))
     }
 
     // public only for testing purposes
@@ -304,8 +773,129 @@ impl<T: Config> Pallet<T> {
         });
     }
 
+    /// Updates the performance record of the oracle for a new submission and slashes its
+    /// bond once it accumulated too many faults. A slashed oracle loses its authorization, while
+    /// the rest of its bond stays locked until it deregisters. Returns true if the oracle was slashed.
+    fn record_submission(
+        oracle_id: &T::AccountId,
+        values: &[(OracleKey, UnsignedFixedPoint<T>)],
+    ) -> Result<bool, DispatchError> {
+        let now = Self::get_current_time();
+        let parameters = Self::oracle_bond_parameters();
+        let mut stats = OracleStats::<T>::get(oracle_id);
+
+        // the previous submission expired before this one arrived
+        let max_delay = values
+            .iter()
+            .map(|(key, _)| Self::get_key_max_delay(key))
+            .max()
+            .unwrap_or_else(Self::get_max_delay);
+        if stats.submissions > 0 && now > stats.last_submission.saturating_add(max_delay) {
+            stats.stale_submissions.saturating_inc();
+            stats.faults.saturating_inc();
+        }
+
+        let deviation = values
+            .iter()
+            .filter_map(|(key, value)| {
+                let median = Self::round_median(oracle_id, key, *value)?;
+                let difference = if *value > median {
+                    value.saturating_sub(median)
+                } else {
+                    median.saturating_sub(*value)
+                };
+                difference.checked_div(&median)
+            })
+            .max()
+            .unwrap_or_default();
+        let max_deviation = Self::max_deviation();
+        if !max_deviation.is_zero() && deviation > max_deviation {
+            stats.deviating_submissions.saturating_inc();
+            stats.faults.saturating_inc();
+        }
+
+        stats.submissions.saturating_inc();
+        stats.last_submission = now;
+        stats.last_deviation = deviation;
+
+        let bond = match OracleBonds::<T>::get(oracle_id) {
+            Some(bond) if parameters.max_faults > 0 && stats.faults >= parameters.max_faults => bond,
+            _ => {
+                OracleStats::<T>::insert(oracle_id, stats);
+                return Ok(false);
+            }
+        };
+        stats.faults = 0;
+        OracleStats::<T>::insert(oracle_id, stats);
+
+        let amount = Amount::<T>::new(bond, T::GetNativeCurrencyId::get()).checked_mul(&parameters.slash_fraction)?;
+        Self::slash_bond(oracle_id, amount.amount())?;
+        <AuthorizedOracles<T>>::remove(oracle_id);
+        Self::deposit_event(Event::OracleRemoved {
+            oracle_id: oracle_id.clone(),
+        });
+        Ok(true)
+    }
+
+    /// Returns the median of the fresh values submitted for `key` in the current round, where
+    /// `value` replaces any previous submission of `oracle_id`
+    fn round_median(
+        oracle_id: &T::AccountId,
+        key: &OracleKey,
+        value: UnsignedFixedPoint<T>,
+    ) -> Option<UnsignedFixedPoint<T>> {
+        let min_timestamp = Self::get_current_time().saturating_sub(Self::get_key_max_delay(key));
+        let mut round: Vec<_> = RawValues::<T>::iter_prefix(key)
+            .filter(|(id, raw)| id != oracle_id && raw.timestamp >= min_timestamp)
+            .map(|(_, raw)| raw.value)
+            .collect();
+        round.push(value);
+        Self::median(round)
+    }
+
+    /// Moves up to `amount` of the oracle's bond to the treasury
+    fn slash_bond(oracle_id: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+        let bond = OracleBonds::<T>::get(oracle_id).ok_or(Error::<T>::NoOracleBond)?;
+        let amount = Amount::<T>::new(amount.min(bond), T::GetNativeCurrencyId::get());
+        amount.unlock_on(oracle_id)?;
+        amount.transfer(oracle_id, &Self::treasury_account_id())?;
+        OracleBonds::<T>::insert(oracle_id, bond.saturating_sub(amount.amount()));
+        Self::deposit_event(Event::OracleSlashed {
+            oracle_id: oracle_id.clone(),
+            amount: amount.amount(),
+        });
+        Ok(())
+    }
+
     /// Public getters
 
+    pub fn treasury_account_id() -> T::AccountId {
+        T::TreasuryPalletId::get().into_account_truncating()
+    }
+
+    /// Get the keys that have been fed by the oracles
+    pub fn get_oracle_keys() -> Vec<OracleKey> {
+        RawValuesUpdated::<T>::iter_keys().collect()
+    }
+
+    /// Get the aggregate of the key, its expiry and the latest value of every feeder
+    pub fn get_aggregate_info(key: &OracleKey) -> AggregateInfo<T::AccountId, T::Moment, UnsignedFixedPoint<T>> {
+        AggregateInfo {
+            aggregate: Aggregate::<T>::get(key),
+            valid_until: ValidUntil::<T>::get(key),
+            max_delay: Self::get_key_max_delay(key),
+            is_fallback: FallbackActive::<T>::get(key),
+            raw_values: RawValues::<T>::iter_prefix(key)
+                .map(|(oracle_id, x)| (oracle_id, x.timestamp, x.value))
+                .collect(),
+        }
+    }
+
+    /// Get the submission record of the oracle
+    pub fn get_oracle_performance(oracle_id: &T::AccountId) -> OraclePerformance<T::Moment, UnsignedFixedPoint<T>> {
+        OracleStats::<T>::get(oracle_id)
+    }
+
     /// Get the exchange rate in planck per satoshi
     pub fn get_price(key: OracleKey) -> Result<UnsignedFixedPoint<T>, DispatchError> {
         ext::security::ensure_parachain_status_running::<T>()?;
@@ -331,27 +921,186 @@ impl<T: Config> Pallet<T> {
         amount.checked_div(&rate).map(|x| x.amount())
     }
 
+    /// Get the recorded aggregates of the given key, oldest first
+    pub fn get_price_history(key: &OracleKey) -> Vec<TimestampedValue<UnsignedFixedPoint<T>, T::Moment>> {
+        PriceHistory::<T>::get(key)
+    }
+
+    /// Get the time-weighted average price of the given key over the last `window`. Each
+    /// recorded aggregate is weighted by the time until it was replaced by the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key to get the average price for
+    /// * `window` - the period to average over, ending now
+    pub fn get_twap(key: &OracleKey, window: T::Moment) -> Result<UnsignedFixedPoint<T>, DispatchError> {
+        ext::security::ensure_parachain_status_running::<T>()?;
+
+        Self::time_weighted_average(&PriceHistory::<T>::get(key), window)
+    }
+
+    /// True if the aggregate of the key is currently derived from its DEX fallback source
+    pub fn is_fallback_price(key: &OracleKey) -> bool {
+        FallbackActive::<T>::get(key)
+    }
+
+    /// Averages the entries over the last `window`, weighting each by the time until it was
+    /// replaced by the next one
+    fn time_weighted_average(
+        history: &[TimestampedValue<UnsignedFixedPoint<T>, T::Moment>],
+        window: T::Moment,
+    ) -> Result<UnsignedFixedPoint<T>, DispatchError> {
+        let latest = history.last().ok_or(Error::<T>::MissingExchangeRate)?.value;
+        let now = Self::get_current_time();
+        let start = now.saturating_sub(window);
+
+        let mut weighted_sum = UnsignedFixedPoint::<T>::zero();
+        let mut total_duration = UnsignedFixedPoint::<T>::zero();
+        for (index, entry) in history.iter().enumerate() {
+            let until = history.get(index.saturating_add(1)).map_or(now, |next| next.timestamp);
+            if until <= start {
+                continue;
+            }
+            let duration = until.saturating_sub(entry.timestamp.max(start));
+            let duration = UnsignedFixedPoint::<T>::checked_from_integer(BalanceOf::<T>::unique_saturated_from(
+                duration.unique_saturated_into(),
+            ))
+            .ok_or(ArithmeticError::Overflow)?;
+            weighted_sum = entry
+                .value
+                .checked_mul(&duration)
+                .and_then(|x| weighted_sum.checked_add(&x))
+                .ok_or(ArithmeticError::Overflow)?;
+            total_duration = total_duration.checked_add(&duration).ok_or(ArithmeticError::Overflow)?;
+        }
+
+        if total_duration.is_zero() {
+            return Ok(latest);
+        }
+        Ok(weighted_sum
+            .checked_div(&total_duration)
+            .ok_or(ArithmeticError::DivisionByZero)?)
+    }
+
+    /// Records the aggregate in the price history, dropping the oldest entry when full. The last
+    /// entry is always the latest aggregate, while all earlier entries are at least one `MaxDelay`
+    /// interval of the key apart, such that frequent updates can not crowd older prices out of the
+    /// history. An aggregate thus replaces the last entry as long as that is within one interval
+    /// of its predecessor.
+    fn record_price(key: &OracleKey, value: UnsignedFixedPoint<T>) {
+        let now = Self::get_current_time();
+        let interval = Self::get_key_max_delay(key);
+        PriceHistory::<T>::mutate(key, |history| {
+            let entry = TimestampedValue { value, timestamp: now };
+            match history.as_mut_slice() {
+                [.., previous, last] if last.timestamp < previous.timestamp.saturating_add(interval) => {
+                    *last = entry;
+                }
+                _ => history.push(entry),
+            }
+            if history.len() > MAX_PRICE_HISTORY as usize {
+                history.remove(0);
+            }
+        });
+    }
+
+    /// Appends the current DEX spot price to the samples of every key with a fallback source
+    /// whose sampling interval has passed. The samples are dropped when the route has no
+    /// liquidity, so that no stale price is used. Returns the number of fallback sources and
+    /// the keys that were sampled.
+    fn sample_fallback_prices(now: T::Moment) -> (u64, Vec<OracleKey>) {
+        let mut sources = 0u64;
+        let mut sampled = Vec::new();
+        for (key, source) in FallbackSources::<T>::iter() {
+            sources.saturating_inc();
+            if matches!(FallbackSampledAt::<T>::get(&key), Some(t) if now < t.saturating_add(source.interval)) {
+                continue;
+            }
+            FallbackSampledAt::<T>::insert(&key, now);
+            match Self::dex_spot_price(&source) {
+                Some(value) => FallbackSamples::<T>::mutate(&key, |samples| {
+                    samples.push(TimestampedValue { value, timestamp: now });
+                    if samples.len() > MAX_PRICE_HISTORY as usize {
+                        samples.remove(0);
+                    }
+                }),
+                None => FallbackSamples::<T>::remove(&key),
+            }
+            sampled.push(key);
+        }
+        (sources, sampled)
+    }
+
+    /// Returns the spot price of the route, i.e. the received amount per quoted amount
+    fn dex_spot_price(source: &FallbackSource<CurrencyId, BalanceOf<T>, T::Moment>) -> Option<UnsignedFixedPoint<T>> {
+        let amount_out = T::DexPriceSource::get_amount_out(source.amount_in, &source.path)?;
+        UnsignedFixedPoint::<T>::checked_from_rational(amount_out, source.amount_in)
+    }
+
+    /// Returns the time-weighted DEX price of the key if it has a fallback source, together
+    /// with the sampling interval of the source
+    fn fallback_price(key: &OracleKey) -> Option<(UnsignedFixedPoint<T>, T::Moment)> {
+        let source = FallbackSources::<T>::get(key)?;
+        let value = Self::time_weighted_average(&FallbackSamples::<T>::get(key), source.window).ok()?;
+        Some((value, source.interval))
+    }
+
+    /// True if the key was sampled this block and either uses or could get a fallback price
+    fn is_awaiting_fallback(key: &OracleKey, sampled: &[OracleKey]) -> bool {
+        sampled.contains(key) && (FallbackActive::<T>::get(key) || !Aggregate::<T>::contains_key(key))
+    }
+
     fn update_aggregate(key: &OracleKey) -> Option<T::UnsignedFixedPoint> {
         RawValuesUpdated::<T>::insert(key, false);
-        let mut raw_values: Vec<_> = RawValues::<T>::iter_prefix(key).map(|(_, value)| value).collect();
-        let min_timestamp = Self::get_current_time().saturating_sub(Self::get_max_delay());
-        raw_values.retain(|value| value.timestamp >= min_timestamp);
-        if raw_values.len() == 0 {
-            Aggregate::<T>::remove(key);
-            ValidUntil::<T>::remove(key);
-            None
+        let mut raw_values: Vec<_> = RawValues::<T>::iter_prefix(key).collect();
+        let max_delay = Self::get_key_max_delay(key);
+        let min_timestamp = Self::get_current_time().saturating_sub(max_delay);
+        raw_values.retain(|(_, value)| value.timestamp >= min_timestamp);
+        let raw_values = Self::reject_outliers(key, raw_values);
+        let min_submissions = Self::minimum_submissions().max(1) as usize;
+        if raw_values.len() < min_submissions {
+            let (value, interval) = match Self::fallback_price(key) {
+                Some((value, interval)) => (Self::apply_price_change_limit(key, value), interval),
+                None => {
+                    Aggregate::<T>::remove(key);
+                    ValidUntil::<T>::remove(key);
+                    if FallbackActive::<T>::take(key) {
+                        Self::deposit_event(Event::<T>::FallbackPriceDeactivated { key: key.clone() });
+                    }
+                    return None;
+                }
+            };
+
+            // the fallback is re-evaluated once the next spot price is sampled
+            Aggregate::<T>::insert(key, value);
+            ValidUntil::<T>::insert(key, Self::get_current_time().saturating_add(interval));
+            Self::record_price(key, value);
+            if !FallbackActive::<T>::get(key) {
+                FallbackActive::<T>::insert(key, true);
+                Self::deposit_event(Event::<T>::FallbackPriceActivated { key: key.clone() });
+            }
+            if let OracleKey::ExchangeRate(currency_id) = key {
+                T::OnExchangeRateChange::on_exchange_rate_change(currency_id);
+            }
+
+            Some(value)
         } else {
             let valid_until = raw_values
                 .iter()
-                .map(|x| x.timestamp)
+                .map(|(_, x)| x.timestamp)
                 .min()
-                .map(|timestamp| timestamp + Self::get_max_delay())
+                .map(|timestamp| timestamp + max_delay)
                 .unwrap_or_default(); // Unwrap will never fail, but if somehow it did, we retry next block
 
-            let value = Self::median(raw_values.iter().map(|x| x.value).collect())?;
+            let value = Self::median(raw_values.iter().map(|(_, x)| x.value).collect())?;
+            let value = Self::apply_price_change_limit(key, value);
 
             Aggregate::<T>::insert(key, value);
             ValidUntil::<T>::insert(key, valid_until);
+            Self::record_price(key, value);
+            if FallbackActive::<T>::take(key) {
+                Self::deposit_event(Event::<T>::FallbackPriceDeactivated { key: key.clone() });
+            }
             if let OracleKey::ExchangeRate(currency_id) = key {
                 T::OnExchangeRateChange::on_exchange_rate_change(currency_id);
             }
@@ -360,6 +1109,122 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Returns the price to use as aggregate: either `value`, or the previous aggregate if
+    /// `value` exceeds the price change limit and has not yet been confirmed.
+    fn apply_price_change_limit(key: &OracleKey, value: UnsignedFixedPoint<T>) -> UnsignedFixedPoint<T> {
+        let limit = match PriceChangeLimits::<T>::get(key) {
+            Some(limit) => limit,
+            None => return value,
+        };
+        let previous = match Aggregate::<T>::get(key) {
+            Some(previous) => previous,
+            None => return value,
+        };
+        let now = Self::get_current_time();
+
+        // start a new window once the current one has passed
+        let reference = match PriceReference::<T>::get(key) {
+            Some(reference) if now < reference.timestamp.saturating_add(limit.window) => reference,
+            _ => {
+                let reference = TimestampedValue {
+                    value: previous,
+                    timestamp: now,
+                };
+                PriceReference::<T>::insert(key, reference);
+                reference
+            }
+        };
+
+        let change = if value > reference.value {
+            value.saturating_sub(reference.value)
+        } else {
+            reference.value.saturating_sub(value)
+        };
+        if change <= reference.value.saturating_mul(limit.max_change) {
+            if PendingPrice::<T>::take(key).is_some() {
+                Self::recover_from_price_circuit_breaker();
+            }
+            return value;
+        }
+
+        match PendingPrice::<T>::get(key) {
+            Some(pending) if now >= pending.timestamp.saturating_add(limit.window) => {
+                // the move persisted for a full confirmation window
+                Self::accept_pending_price_for(key, value);
+                value
+            }
+            Some(pending) => {
+                PendingPrice::<T>::insert(key, TimestampedValue { value, ..pending });
+                previous
+            }
+            None => {
+                PendingPrice::<T>::insert(key, TimestampedValue { value, timestamp: now });
+                ext::security::insert_error::<T>(ErrorCode::PriceCircuitBreaker);
+                Self::deposit_event(Event::<T>::PriceCircuitBreakerTripped {
+                    key: key.clone(),
+                    price: previous,
+                    proposed_price: value,
+                });
+                previous
+            }
+        }
+    }
+
+    fn accept_pending_price_for(key: &OracleKey, value: UnsignedFixedPoint<T>) {
+        PendingPrice::<T>::remove(key);
+        PriceReference::<T>::insert(
+            key,
+            TimestampedValue {
+                value,
+                timestamp: Self::get_current_time(),
+            },
+        );
+        Self::recover_from_price_circuit_breaker();
+        Self::deposit_event(Event::<T>::PendingPriceAccepted {
+            key: key.clone(),
+            price: value,
+        });
+    }
+
+    /// Removes the submissions that deviate more than `MaxDeviation` from their median,
+    /// emitting an event for every rejected oracle.
+    fn reject_outliers(
+        key: &OracleKey,
+        raw_values: Vec<(T::AccountId, TimestampedValue<UnsignedFixedPoint<T>, T::Moment>)>,
+    ) -> Vec<(T::AccountId, TimestampedValue<UnsignedFixedPoint<T>, T::Moment>)> {
+        let max_deviation = Self::max_deviation();
+        if max_deviation.is_zero() {
+            return raw_values;
+        }
+        let median = match Self::median(raw_values.iter().map(|(_, x)| x.value).collect()) {
+            Some(median) => median,
+            None => return raw_values,
+        };
+        let band = median.saturating_mul(max_deviation);
+
+        raw_values
+            .into_iter()
+            .filter(|(oracle_id, x)| {
+                let deviation = if x.value > median {
+                    x.value.saturating_sub(median)
+                } else {
+                    median.saturating_sub(x.value)
+                };
+                if deviation > band {
+                    Self::deposit_event(Event::<T>::OracleValueRejected {
+                        oracle_id: oracle_id.clone(),
+                        key: key.clone(),
+                        value: x.value,
+                        median,
+                    });
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+
     fn median(mut raw_values: Vec<UnsignedFixedPoint<T>>) -> Option<UnsignedFixedPoint<T>> {
         let mid_index = raw_values.len().checked_div(2)?;
         raw_values.sort_unstable();
@@ -389,6 +1254,11 @@ impl<T: Config> Pallet<T> {
         <MaxDelay<T>>::get()
     }
 
+    /// Returns the maximum delay of the key, defaulting to the global `MaxDelay`
+    fn get_key_max_delay(key: &OracleKey) -> T::Moment {
+        MaxDelays::<T>::get(key).unwrap_or_else(Self::get_max_delay)
+    }
+
     /// Set the current exchange rate. ONLY FOR TESTING.
     ///
     /// # Arguments
@@ -416,6 +1286,15 @@ impl<T: Config> Pallet<T> {
         ext::security::recover_from_oracle_offline::<T>()
     }
 
+    /// Re-enables liquidations once no price is held back anymore
+    fn recover_from_price_circuit_breaker() {
+        if PendingPrice::<T>::iter().next().is_none()
+            && ext::security::get_errors::<T>().contains(&ErrorCode::PriceCircuitBreaker)
+        {
+            ext::security::recover_from_price_circuit_breaker::<T>()
+        }
+    }
+
     /// Returns the current timestamp
     fn get_current_time() -> T::Moment {
         <pallet_timestamp::Pallet<T>>::get()
@@ -432,24 +1311,66 @@ impl<T: Config> Pallet<T> {
     }
 }
 
-impl<T: Config> OracleApi<Amount<T>, T::CurrencyId> for Pallet<T> {
-    fn convert(amount: &Amount<T>, currency_id: T::CurrencyId) -> Result<Amount<T>, DispatchError> {
+impl<T: Config> LiquidationGuard for Pallet<T> {
+    fn ensure_liquidations_enabled() -> DispatchResult {
+        ext::security::ensure_liquidations_enabled::<T>()
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Converts the amount using the exchange rates (in planck per satoshi) returned by `get_rate`
+    fn convert_with_rates(
+        amount: &Amount<T>,
+        currency_id: T::CurrencyId,
+        get_rate: impl Fn(T::CurrencyId) -> Result<UnsignedFixedPoint<T>, DispatchError>,
+    ) -> Result<Amount<T>, DispatchError> {
+        let wrapped_to_collateral = |amount: BalanceOf<T>, currency_id| -> Result<BalanceOf<T>, DispatchError> {
+            let rate = get_rate(currency_id)?;
+            Amount::<T>::new(amount, currency_id)
+                .checked_mul(&rate)
+                .map(|x| x.amount())
+        };
+        let collateral_to_wrapped = |amount: BalanceOf<T>, currency_id| -> Result<BalanceOf<T>, DispatchError> {
+            let rate = get_rate(currency_id)?;
+            if amount.is_zero() {
+                return Ok(Zero::zero());
+            }
+            Amount::<T>::new(amount, currency_id)
+                .checked_div(&rate)
+                .map(|x| x.amount())
+        };
+
         let converted = match (amount.currency(), currency_id) {
             (x, y) if x == y => amount.amount(),
             (x, _) if x == T::GetWrappedCurrencyId::get() => {
                 // convert interbtc to collateral
-                Self::wrapped_to_collateral(amount.amount(), currency_id)?
+                wrapped_to_collateral(amount.amount(), currency_id)?
             }
             (from_currency, x) if x == T::GetWrappedCurrencyId::get() => {
                 // convert collateral to interbtc
-                Self::collateral_to_wrapped(amount.amount(), from_currency)?
+                collateral_to_wrapped(amount.amount(), from_currency)?
             }
             (_, _) => {
                 // first convert to btc, then convert the btc to the desired currency
-                let base = Self::collateral_to_wrapped(amount.amount(), amount.currency())?;
-                Self::wrapped_to_collateral(base, currency_id)?
+                let base = collateral_to_wrapped(amount.amount(), amount.currency())?;
+                wrapped_to_collateral(base, currency_id)?
             }
         };
         Ok(Amount::new(converted, currency_id))
     }
 }
+
+impl<T: Config> OracleApi<Amount<T>, T::CurrencyId> for Pallet<T> {
+    fn convert(amount: &Amount<T>, currency_id: T::CurrencyId) -> Result<Amount<T>, DispatchError> {
+        Self::convert_with_rates(amount, currency_id, |currency_id| {
+            Self::get_price(OracleKey::ExchangeRate(currency_id))
+        })
+    }
+
+    fn convert_twap(amount: &Amount<T>, currency_id: T::CurrencyId, window: u64) -> Result<Amount<T>, DispatchError> {
+        let window = T::Moment::unique_saturated_from(window);
+        Self::convert_with_rates(amount, currency_id, |currency_id| {
+            Self::get_twap(&OracleKey::ExchangeRate(currency_id), window)
+        })
+    }
+}
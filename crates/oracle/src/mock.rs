@@ -3,6 +3,7 @@ use crate::{Config, Error};
 use frame_support::{
     parameter_types,
     traits::{ConstU32, Everything, GenesisBuild},
+    PalletId,
 };
 use mocktopus::mocking::clear_mocks;
 use orml_traits::parameter_type_with_key;
@@ -131,9 +132,15 @@ impl currency::Config for Test {
     type CurrencyConversion = CurrencyConvert;
 }
 
+parameter_types! {
+    pub const TreasuryPalletId: PalletId = PalletId(*b"mod/trsy");
+}
+
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OnExchangeRateChange = ();
+    type TreasuryPalletId = TreasuryPalletId;
+    type DexPriceSource = ();
     type WeightInfo = ();
 }
 
@@ -1,4 +1,4 @@
-use crate::{mock::*, CurrencyId, OracleKey};
+use crate::{mock::*, AggregateInfo, CurrencyId, OracleKey};
 use frame_support::{assert_err, assert_ok, dispatch::DispatchError};
 use mocktopus::mocking::*;
 use sp_arithmetic::FixedU128;
@@ -157,6 +157,157 @@ mod oracle_offline_detection {
     }
 }
 
+#[test]
+fn aggregate_requires_minimum_submissions() {
+    run_test(|| {
+        let key = OracleKey::ExchangeRate(Token(DOT));
+        Oracle::is_authorized.mock_safe(|_| MockResult::Return(true));
+        assert_ok!(Oracle::set_minimum_submissions(RuntimeOrigin::root(), 2));
+
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(1),
+            vec![(key.clone(), FixedU128::from(100))]
+        ));
+        mine_block();
+        assert_eq!(crate::Aggregate::<Test>::get(&key), None);
+
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(2),
+            vec![(key.clone(), FixedU128::from(102))]
+        ));
+        mine_block();
+        assert_eq!(Oracle::get_price(key.clone()), Ok(FixedU128::from(101)));
+    });
+}
+
+#[test]
+fn aggregate_rejects_outlier_submissions() {
+    run_test(|| {
+        let key = OracleKey::ExchangeRate(Token(DOT));
+        Oracle::is_authorized.mock_safe(|_| MockResult::Return(true));
+        assert_ok!(Oracle::set_max_deviation(
+            RuntimeOrigin::root(),
+            FixedU128::checked_from_rational(5, 100).unwrap()
+        ));
+
+        for (oracle, value) in [(1, 100), (2, 102), (3, 150)] {
+            assert_ok!(Oracle::feed_values(
+                RuntimeOrigin::signed(oracle),
+                vec![(key.clone(), FixedU128::from(value))]
+            ));
+        }
+        mine_block();
+
+        assert_emitted!(Event::OracleValueRejected {
+            oracle_id: 3,
+            key: key.clone(),
+            value: FixedU128::from(150),
+            median: FixedU128::from(102),
+        });
+        assert_not_emitted!(Event::OracleValueRejected {
+            oracle_id: 1,
+            key: key.clone(),
+            value: FixedU128::from(100),
+            median: FixedU128::from(102),
+        });
+        assert_eq!(Oracle::get_price(key.clone()), Ok(FixedU128::from(101)));
+    });
+}
+
+mod price_circuit_breaker {
+    use super::*;
+    use crate::Aggregate;
+    use security::ErrorCode;
+
+    fn key() -> OracleKey {
+        OracleKey::ExchangeRate(Token(DOT))
+    }
+
+    fn feed_value_at(time: u64, value: u128) {
+        Oracle::get_current_time.mock_safe(move || MockResult::Return(time));
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(1),
+            vec![(key(), FixedU128::from(value))]
+        ));
+        mine_block();
+    }
+
+    fn liquidations_paused() -> bool {
+        security::Pallet::<Test>::get_errors().contains(&ErrorCode::PriceCircuitBreaker)
+    }
+
+    fn setup() {
+        Oracle::is_authorized.mock_safe(|_| MockResult::Return(true));
+        Oracle::get_max_delay.mock_safe(|| MockResult::Return(100));
+        assert_ok!(Oracle::set_price_change_limit(
+            RuntimeOrigin::root(),
+            key(),
+            FixedU128::checked_from_rational(10, 100).unwrap(),
+            10
+        ));
+    }
+
+    #[test]
+    fn large_price_move_is_held_until_confirmed() {
+        run_test(|| {
+            setup();
+
+            feed_value_at(0, 100);
+            assert_eq!(Aggregate::<Test>::get(key()), Some(FixedU128::from(100)));
+
+            // a change within the limit is applied immediately
+            feed_value_at(1, 105);
+            assert_eq!(Aggregate::<Test>::get(key()), Some(FixedU128::from(105)));
+
+            feed_value_at(2, 150);
+            assert_eq!(Aggregate::<Test>::get(key()), Some(FixedU128::from(105)));
+            assert!(liquidations_paused());
+            assert_emitted!(Event::PriceCircuitBreakerTripped {
+                key: key(),
+                price: FixedU128::from(105),
+                proposed_price: FixedU128::from(150),
+            });
+
+            // the price is held until the move persisted for a full window
+            feed_value_at(11, 150);
+            assert_eq!(Aggregate::<Test>::get(key()), Some(FixedU128::from(105)));
+            assert!(liquidations_paused());
+
+            feed_value_at(12, 150);
+            assert_eq!(Aggregate::<Test>::get(key()), Some(FixedU128::from(150)));
+            assert!(!liquidations_paused());
+            assert_emitted!(Event::PendingPriceAccepted {
+                key: key(),
+                price: FixedU128::from(150),
+            });
+        });
+    }
+
+    #[test]
+    fn held_price_can_be_accepted_by_governance() {
+        run_test(|| {
+            setup();
+
+            feed_value_at(0, 100);
+            feed_value_at(1, 200);
+            assert_eq!(Aggregate::<Test>::get(key()), Some(FixedU128::from(100)));
+            assert!(liquidations_paused());
+
+            assert_err!(
+                Oracle::accept_pending_price(RuntimeOrigin::signed(1), key()),
+                DispatchError::BadOrigin
+            );
+            assert_err!(
+                Oracle::accept_pending_price(RuntimeOrigin::root(), OracleKey::ExchangeRate(Token(KSM))),
+                TestError::NoPendingPrice
+            );
+            assert_ok!(Oracle::accept_pending_price(RuntimeOrigin::root(), key()));
+            assert_eq!(Aggregate::<Test>::get(key()), Some(FixedU128::from(200)));
+            assert!(!liquidations_paused());
+        });
+    }
+}
+
 #[test]
 fn feed_values_fails_with_invalid_oracle_source() {
     run_test(|| {
@@ -363,3 +514,589 @@ fn test_median() {
         assert_eq!(Oracle::median(input_fixedpoint), output_fixedpoint);
     }
 }
+
+mod price_history {
+    use super::*;
+    use crate::MAX_PRICE_HISTORY;
+    use currency::Amount;
+    use traits::OracleApi;
+
+    fn key() -> OracleKey {
+        OracleKey::ExchangeRate(Token(DOT))
+    }
+
+    fn feed_value_at(time: u64, value: u128) {
+        Oracle::get_current_time.mock_safe(move || MockResult::Return(time));
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(1),
+            vec![(key(), FixedU128::from(value))]
+        ));
+        mine_block();
+    }
+
+    fn setup() {
+        Oracle::is_authorized.mock_safe(|_| MockResult::Return(true));
+        Oracle::get_max_delay.mock_safe(|| MockResult::Return(1000));
+    }
+
+    #[test]
+    fn price_history_is_bounded() {
+        run_test(|| {
+            setup();
+
+            for interval in 0..MAX_PRICE_HISTORY as u64 + 5 {
+                feed_value_at(interval * 1000, 100 + interval as u128);
+            }
+
+            let history = Oracle::get_price_history(&key());
+            assert_eq!(history.len(), MAX_PRICE_HISTORY as usize);
+            assert_eq!(history.first().unwrap().timestamp, 5000);
+            assert_eq!(history.first().unwrap().value, FixedU128::from(105));
+            assert_eq!(history.last().unwrap().timestamp, (MAX_PRICE_HISTORY as u64 + 4) * 1000);
+        });
+    }
+
+    #[test]
+    fn price_history_records_one_price_per_max_delay() {
+        run_test(|| {
+            setup();
+
+            feed_value_at(0, 100);
+            feed_value_at(999, 200);
+            feed_value_at(1000, 300);
+
+            let history = Oracle::get_price_history(&key());
+            assert_eq!(
+                history.iter().map(|x| (x.timestamp, x.value)).collect::<Vec<_>>(),
+                vec![(0, FixedU128::from(100)), (1000, FixedU128::from(300))]
+            );
+        });
+    }
+
+    #[test]
+    fn price_history_always_ends_with_latest_price() {
+        run_test(|| {
+            setup();
+
+            let history = || {
+                Oracle::get_price_history(&key())
+                    .iter()
+                    .map(|x| (x.timestamp, x.value))
+                    .collect::<Vec<_>>()
+            };
+
+            feed_value_at(0, 100);
+            feed_value_at(500, 200);
+            assert_eq!(history(), vec![(0, FixedU128::from(100)), (500, FixedU128::from(200))]);
+
+            // replaces the last price, which is within one interval of its predecessor
+            feed_value_at(800, 300);
+            assert_eq!(history(), vec![(0, FixedU128::from(100)), (800, FixedU128::from(300))]);
+            feed_value_at(1200, 400);
+            assert_eq!(history(), vec![(0, FixedU128::from(100)), (1200, FixedU128::from(400))]);
+
+            // the last price is a full interval after its predecessor, so it is kept
+            feed_value_at(1500, 500);
+            assert_eq!(
+                history(),
+                vec![
+                    (0, FixedU128::from(100)),
+                    (1200, FixedU128::from(400)),
+                    (1500, FixedU128::from(500))
+                ]
+            );
+
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(1500));
+            assert_eq!(Oracle::get_twap(&key(), 0), Ok(FixedU128::from(500)));
+        });
+    }
+
+    #[test]
+    fn twap_weights_prices_by_duration() {
+        run_test(|| {
+            setup();
+
+            assert_err!(Oracle::get_twap(&key(), 10), TestError::MissingExchangeRate);
+
+            feed_value_at(0, 100);
+            feed_value_at(10_000, 200);
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(40_000));
+
+            // 100 for 10 seconds, 200 for 30 seconds
+            assert_eq!(Oracle::get_twap(&key(), 40_000), Ok(FixedU128::from(175)));
+            // the window only covers the second price
+            assert_eq!(Oracle::get_twap(&key(), 20_000), Ok(FixedU128::from(200)));
+            // 100 for 10 seconds, 200 for 10 seconds
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(20_000));
+            assert_eq!(Oracle::get_twap(&key(), 100_000), Ok(FixedU128::from(150)));
+            // an empty window returns the latest price
+            assert_eq!(Oracle::get_twap(&key(), 0), Ok(FixedU128::from(200)));
+        });
+    }
+
+    #[test]
+    fn convert_twap_uses_average_exchange_rate() {
+        run_test(|| {
+            setup();
+
+            feed_value_at(0, 100);
+            feed_value_at(10_000, 200);
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(40_000));
+
+            let amount = Amount::<Test>::new(10, DEFAULT_WRAPPED_CURRENCY);
+            assert_eq!(
+                <Oracle as OracleApi<_, _>>::convert_twap(&amount, DEFAULT_COLLATERAL_CURRENCY, 40_000),
+                Ok(Amount::new(1750, DEFAULT_COLLATERAL_CURRENCY))
+            );
+            assert_eq!(
+                <Oracle as OracleApi<_, _>>::convert(&amount, DEFAULT_COLLATERAL_CURRENCY),
+                Ok(Amount::new(2000, DEFAULT_COLLATERAL_CURRENCY))
+            );
+        });
+    }
+}
+
+mod oracle_bonds {
+    use super::*;
+    use crate::{OracleBondParameters, OracleBonds, OraclePerformance};
+    use currency::Amount;
+
+    const BOND: u128 = 1000;
+
+    fn key() -> OracleKey {
+        OracleKey::ExchangeRate(Token(DOT))
+    }
+
+    fn native(amount: u128) -> Amount<Test> {
+        Amount::new(amount, DEFAULT_NATIVE_CURRENCY)
+    }
+
+    fn free_balance(account_id: AccountId) -> Balance {
+        orml_tokens::Accounts::<Test>::get(account_id, DEFAULT_NATIVE_CURRENCY).free
+    }
+
+    fn reserved_balance(account_id: AccountId) -> Balance {
+        orml_tokens::Accounts::<Test>::get(account_id, DEFAULT_NATIVE_CURRENCY).reserved
+    }
+
+    fn setup(max_faults: u32) {
+        Oracle::get_max_delay.mock_safe(|| MockResult::Return(100));
+        assert_ok!(Oracle::set_oracle_bond_parameters(
+            RuntimeOrigin::root(),
+            OracleBondParameters {
+                bond: BOND,
+                slash_fraction: FixedU128::checked_from_rational(50, 100).unwrap(),
+                max_faults,
+                unbonding_period: 10,
+            }
+        ));
+        assert_ok!(native(BOND).mint_to(&1));
+        assert_ok!(Oracle::insert_authorized_oracle(
+            RuntimeOrigin::root(),
+            1,
+            b"bonded".to_vec()
+        ));
+        assert_ok!(Oracle::register_oracle(RuntimeOrigin::signed(1)));
+    }
+
+    #[test]
+    fn register_oracle_requires_bond() {
+        run_test(|| {
+            assert_err!(
+                Oracle::register_oracle(RuntimeOrigin::signed(1)),
+                TestError::OracleBondingDisabled
+            );
+
+            setup(0);
+            assert!(Oracle::is_authorized(&1));
+            assert_eq!(reserved_balance(1), BOND);
+            assert_err!(
+                Oracle::register_oracle(RuntimeOrigin::signed(1)),
+                TestError::OracleAlreadyRegistered
+            );
+        });
+    }
+
+    #[test]
+    fn register_oracle_requires_governance_approval() {
+        run_test(|| {
+            setup(0);
+            assert_ok!(native(BOND).mint_to(&2));
+            assert_err!(
+                Oracle::register_oracle(RuntimeOrigin::signed(2)),
+                TestError::InvalidOracleSource
+            );
+            assert!(!Oracle::is_authorized(&2));
+            assert_eq!(reserved_balance(2), 0);
+        });
+    }
+
+    #[test]
+    fn bond_is_released_after_unbonding_period() {
+        run_test(|| {
+            setup(0);
+
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(5));
+            assert_ok!(Oracle::deregister_oracle(RuntimeOrigin::signed(1)));
+            assert!(!Oracle::is_authorized(&1));
+            assert_err!(
+                Oracle::feed_values(RuntimeOrigin::signed(1), vec![(key(), FixedU128::from(100))]),
+                TestError::InvalidOracleSource
+            );
+
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(14));
+            assert_err!(
+                Oracle::withdraw_oracle_bond(RuntimeOrigin::signed(1)),
+                TestError::UnbondingPeriodNotExpired
+            );
+
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(15));
+            assert_ok!(Oracle::withdraw_oracle_bond(RuntimeOrigin::signed(1)));
+            assert_eq!(reserved_balance(1), 0);
+            assert_eq!(free_balance(1), BOND);
+            assert_eq!(OracleBonds::<Test>::get(1), None);
+        });
+    }
+
+    #[test]
+    fn governance_can_slash_oracle_bond() {
+        run_test(|| {
+            setup(0);
+
+            assert_ok!(Oracle::slash_oracle(RuntimeOrigin::root(), 1, 300));
+            assert_eq!(reserved_balance(1), BOND - 300);
+            assert_eq!(free_balance(Oracle::treasury_account_id()), 300);
+
+            // the slashed amount is capped at the remaining bond
+            assert_ok!(Oracle::slash_oracle(RuntimeOrigin::root(), 1, BOND));
+            assert_eq!(reserved_balance(1), 0);
+            assert_eq!(free_balance(Oracle::treasury_account_id()), BOND);
+            assert_err!(
+                Oracle::slash_oracle(RuntimeOrigin::root(), 2, 1),
+                TestError::NoOracleBond
+            );
+        });
+    }
+
+    #[test]
+    fn deviating_oracle_is_slashed_automatically() {
+        run_test(|| {
+            setup(2);
+            assert_ok!(Oracle::set_max_deviation(
+                RuntimeOrigin::root(),
+                FixedU128::checked_from_rational(10, 100).unwrap()
+            ));
+            assert_ok!(native(BOND).mint_to(&2));
+            assert_ok!(Oracle::insert_authorized_oracle(
+                RuntimeOrigin::root(),
+                2,
+                b"bonded".to_vec()
+            ));
+            assert_ok!(Oracle::register_oracle(RuntimeOrigin::signed(2)));
+            assert_ok!(Oracle::feed_values(
+                RuntimeOrigin::signed(2),
+                vec![(key(), FixedU128::from(100))]
+            ));
+            mine_block();
+
+            // the median of the round is 125
+            assert_ok!(Oracle::feed_values(
+                RuntimeOrigin::signed(1),
+                vec![(key(), FixedU128::from(150))]
+            ));
+            assert_eq!(
+                Oracle::get_oracle_performance(&1),
+                OraclePerformance {
+                    submissions: 1,
+                    stale_submissions: 0,
+                    deviating_submissions: 1,
+                    faults: 1,
+                    last_submission: 0,
+                    last_deviation: FixedU128::checked_from_rational(20, 100).unwrap(),
+                }
+            );
+            assert!(Oracle::is_authorized(&1));
+
+            assert_ok!(Oracle::feed_values(
+                RuntimeOrigin::signed(1),
+                vec![(key(), FixedU128::from(150))]
+            ));
+            assert!(!Oracle::is_authorized(&1));
+            assert_eq!(reserved_balance(1), BOND / 2);
+            assert_eq!(free_balance(Oracle::treasury_account_id()), BOND / 2);
+            assert_eq!(Oracle::get_oracle_performance(&1).faults, 0);
+            assert_emitted!(Event::OracleSlashed {
+                oracle_id: 1,
+                amount: BOND / 2,
+            });
+
+            // the rest of the bond is released by deregistering
+            assert_ok!(Oracle::deregister_oracle(RuntimeOrigin::signed(1)));
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(10));
+            assert_ok!(Oracle::withdraw_oracle_bond(RuntimeOrigin::signed(1)));
+            assert_eq!(reserved_balance(1), 0);
+            assert_eq!(free_balance(1), BOND / 2);
+        });
+    }
+
+    #[test]
+    fn unbonded_oracle_can_not_feed_values() {
+        run_test(|| {
+            setup(0);
+            assert_ok!(Oracle::insert_authorized_oracle(
+                RuntimeOrigin::root(),
+                2,
+                b"unbonded".to_vec()
+            ));
+            assert_err!(
+                Oracle::feed_values(RuntimeOrigin::signed(2), vec![(key(), FixedU128::from(100))]),
+                TestError::NoOracleBond
+            );
+            assert_ok!(Oracle::feed_values(
+                RuntimeOrigin::signed(1),
+                vec![(key(), FixedU128::from(100))]
+            ));
+        });
+    }
+}
+
+mod fallback_price {
+    use super::*;
+    use crate::{Aggregate, FallbackSamples, FallbackSource, ValidUntil};
+
+    fn key() -> OracleKey {
+        OracleKey::ExchangeRate(Token(DOT))
+    }
+
+    fn source(path: Vec<CurrencyId>) -> FallbackSource<CurrencyId, Balance, Moment> {
+        FallbackSource {
+            path,
+            amount_in: 100,
+            window: 100,
+            interval: 5,
+        }
+    }
+
+    fn setup() {
+        Oracle::is_authorized.mock_safe(|_| MockResult::Return(true));
+        Oracle::get_max_delay.mock_safe(|| MockResult::Return(10));
+        Oracle::dex_spot_price.mock_safe(|_| MockResult::Return(Some(FixedU128::from(120))));
+        Oracle::get_current_time.mock_safe(|| MockResult::Return(0));
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(1),
+            vec![(key(), FixedU128::from(100))]
+        ));
+        mine_block();
+    }
+
+    #[test]
+    fn set_fallback_source_validates_path() {
+        run_test(|| {
+            assert_err!(
+                Oracle::set_fallback_source(
+                    RuntimeOrigin::root(),
+                    OracleKey::FeeEstimation,
+                    Some(source(vec![Token(IBTC), Token(DOT)]))
+                ),
+                TestError::InvalidFallbackSource
+            );
+            assert_err!(
+                Oracle::set_fallback_source(
+                    RuntimeOrigin::root(),
+                    key(),
+                    Some(source(vec![Token(IBTC), Token(KSM)]))
+                ),
+                TestError::InvalidFallbackSource
+            );
+            assert_err!(
+                Oracle::set_fallback_source(RuntimeOrigin::root(), key(), Some(source(vec![Token(DOT)]))),
+                TestError::InvalidFallbackSource
+            );
+            assert_ok!(Oracle::set_fallback_source(
+                RuntimeOrigin::root(),
+                key(),
+                Some(source(vec![Token(IBTC), Token(INTR), Token(DOT)]))
+            ));
+        });
+    }
+
+    #[test]
+    fn fallback_is_only_used_for_opted_in_keys() {
+        run_test(|| {
+            setup();
+
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(20));
+            mine_block();
+            assert_eq!(Aggregate::<Test>::get(key()), None);
+            assert!(!Oracle::is_fallback_price(&key()));
+        });
+    }
+
+    #[test]
+    fn fallback_replaces_stale_feeders() {
+        run_test(|| {
+            setup();
+            assert_ok!(Oracle::set_fallback_source(
+                RuntimeOrigin::root(),
+                key(),
+                Some(source(vec![Token(IBTC), Token(DOT)]))
+            ));
+
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(5));
+            mine_block();
+            assert_eq!(Oracle::get_price(key()), Ok(FixedU128::from(100)));
+            assert!(!Oracle::is_fallback_price(&key()));
+
+            // the feeder value expired, the averaged DEX price is used instead
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(20));
+            mine_block();
+            assert_eq!(Oracle::get_price(key()), Ok(FixedU128::from(120)));
+            assert!(Oracle::is_fallback_price(&key()));
+            assert_emitted!(Event::FallbackPriceActivated { key: key() });
+
+            // the feeders are back
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(30));
+            assert_ok!(Oracle::feed_values(
+                RuntimeOrigin::signed(1),
+                vec![(key(), FixedU128::from(110))]
+            ));
+            mine_block();
+            assert_eq!(Oracle::get_price(key()), Ok(FixedU128::from(110)));
+            assert!(!Oracle::is_fallback_price(&key()));
+            assert_emitted!(Event::FallbackPriceDeactivated { key: key() });
+        });
+    }
+
+    #[test]
+    fn fallback_is_sampled_once_per_interval() {
+        run_test(|| {
+            setup();
+            assert_ok!(Oracle::set_fallback_source(
+                RuntimeOrigin::root(),
+                key(),
+                Some(source(vec![Token(IBTC), Token(DOT)]))
+            ));
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(20));
+            mine_block();
+            assert!(Oracle::is_fallback_price(&key()));
+            assert_eq!(FallbackSamples::<Test>::get(key()).len(), 1);
+            assert_eq!(ValidUntil::<Test>::get(key()), Some(25));
+
+            // neither sampled nor re-aggregated within the interval
+            Oracle::dex_spot_price.mock_safe(|_| MockResult::Return(Some(FixedU128::from(130))));
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(24));
+            mine_block();
+            assert_eq!(FallbackSamples::<Test>::get(key()).len(), 1);
+            assert_eq!(Oracle::get_price(key()), Ok(FixedU128::from(120)));
+
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(25));
+            mine_block();
+            assert_eq!(FallbackSamples::<Test>::get(key()).len(), 2);
+            assert_eq!(ValidUntil::<Test>::get(key()), Some(30));
+        });
+    }
+
+    #[test]
+    fn fallback_is_dropped_without_dex_liquidity() {
+        run_test(|| {
+            setup();
+            assert_ok!(Oracle::set_fallback_source(
+                RuntimeOrigin::root(),
+                key(),
+                Some(source(vec![Token(IBTC), Token(DOT)]))
+            ));
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(20));
+            mine_block();
+            assert!(Oracle::is_fallback_price(&key()));
+
+            Oracle::dex_spot_price.mock_safe(|_| MockResult::Return(None));
+            Oracle::get_current_time.mock_safe(|| MockResult::Return(25));
+            mine_block();
+            assert_eq!(Aggregate::<Test>::get(key()), None);
+            assert!(!Oracle::is_fallback_price(&key()));
+        });
+    }
+}
+
+#[test]
+fn per_key_max_delay_overrides_global_delay() {
+    run_test(|| {
+        let exchange_rate = OracleKey::ExchangeRate(Token(DOT));
+        let fee_estimation = OracleKey::FeeEstimation;
+        crate::MaxDelay::<Test>::put(10);
+        assert_ok!(Oracle::set_max_delay(
+            RuntimeOrigin::root(),
+            fee_estimation.clone(),
+            Some(100)
+        ));
+        assert_emitted!(Event::MaxDelayChange {
+            key: fee_estimation.clone(),
+            max_delay: Some(100),
+        });
+
+        Oracle::get_current_time.mock_safe(|| MockResult::Return(0));
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(0),
+            vec![
+                (exchange_rate.clone(), FixedU128::from(100)),
+                (fee_estimation.clone(), FixedU128::from(3))
+            ]
+        ));
+        mine_block();
+        assert_eq!(crate::ValidUntil::<Test>::get(&exchange_rate), Some(10));
+        assert_eq!(crate::ValidUntil::<Test>::get(&fee_estimation), Some(100));
+
+        Oracle::get_current_time.mock_safe(|| MockResult::Return(50));
+        mine_block();
+        assert_eq!(crate::Aggregate::<Test>::get(&exchange_rate), None);
+        assert_eq!(crate::Aggregate::<Test>::get(&fee_estimation), Some(FixedU128::from(3)));
+
+        // removing the override falls back to the global delay
+        assert_ok!(Oracle::set_max_delay(
+            RuntimeOrigin::root(),
+            fee_estimation.clone(),
+            None
+        ));
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(0),
+            vec![(fee_estimation.clone(), FixedU128::from(3))]
+        ));
+        mine_block();
+        assert_eq!(crate::ValidUntil::<Test>::get(&fee_estimation), Some(60));
+    });
+}
+
+#[test]
+fn get_aggregate_info_returns_raw_values() {
+    run_test(|| {
+        let key = OracleKey::ExchangeRate(Token(DOT));
+        crate::MaxDelay::<Test>::put(10);
+        Oracle::is_authorized.mock_safe(|_| MockResult::Return(true));
+
+        Oracle::get_current_time.mock_safe(|| MockResult::Return(2));
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(1),
+            vec![(key.clone(), FixedU128::from(100))]
+        ));
+        Oracle::get_current_time.mock_safe(|| MockResult::Return(4));
+        assert_ok!(Oracle::feed_values(
+            RuntimeOrigin::signed(2),
+            vec![(key.clone(), FixedU128::from(110))]
+        ));
+        mine_block();
+
+        assert_eq!(Oracle::get_oracle_keys(), vec![key.clone()]);
+        let mut info = Oracle::get_aggregate_info(&key);
+        info.raw_values.sort();
+        assert_eq!(
+            info,
+            AggregateInfo {
+                aggregate: Some(FixedU128::from(105)),
+                valid_until: Some(12),
+                max_delay: 10,
+                is_fallback: false,
+                raw_values: vec![(1, 2, FixedU128::from(100)), (2, 4, FixedU128::from(110))],
+            }
+        );
+    });
+}
@@ -13,7 +13,7 @@ use frame_support::assert_ok;
 use frame_system::RawOrigin;
 use orml_traits::MultiCurrency;
 use primitives::{CurrencyId, CurrencyId::Token, TokenSymbol::*, VaultCurrencyPair, VaultId};
-use sp_core::{H256, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::traits::One;
 use sp_std::prelude::*;
 use vault_registry::types::Vault;
@@ -308,6 +308,100 @@ BtcRelay::<T>::parachain_confirmations() + 1u32.into());
         ));
     }: _(RawOrigin::Signed(vault_id.account_id.clone()), redeem_id, proof, raw_tx)
 
+    execute_redeems {
+        let n in 1 .. MAX_REDEEMS_PER_BATCH;
+
+        let vault_id = get_vault_id::<T>();
+        let relayer_id: T::AccountId = account("Relayer", 0, 0);
+
+        initialize_oracle::<T>();
+
+        // every request is paid out to a different address
+        let redeem_ids: Vec<_> = (0..n).map(|i| H256::from_low_u64_be(i as u64)).collect();
+        for (i, redeem_id) in redeem_ids.iter().enumerate() {
+            let mut redeem_request = test_request::<T>(&vault_id);
+            redeem_request.btc_address = BtcAddress::P2PKH(H160::from_low_u64_be(i as u64 + 1));
+            Redeem::<T>::insert_redeem_request(redeem_id, &redeem_request);
+        }
+
+        register_public_key::<T>(vault_id.clone());
+
+        let vault = Vault {
+            id: vault_id.clone(),
+            ..Vault::new(vault_id.clone())
+        };
+
+        VaultRegistry::<T>::insert_vault(
+            &vault_id,
+            vault
+        );
+
+        let height = 0;
+        let block = BlockBuilder::new()
+            .with_version(4)
+            .with_coinbase(&BtcAddress::dummy(), 50, 3)
+            .with_timestamp(1588813835)
+            .mine(U256::from(2).pow(254.into())).unwrap();
+
+        let block_hash = block.header.hash;
+        let raw_block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
+        let block_header = BtcRelay::<T>::parse_raw_block_header(&raw_block_header).unwrap();
+
+        Security::<T>::set_active_block_number(1u32.into());
+        BtcRelay::<T>::_initialize(relayer_id.clone(), block_header, height).unwrap();
+
+        let value = 0;
+        let mut transaction_builder = TransactionBuilder::new();
+        transaction_builder
+            .with_version(2)
+            .add_input(
+                TransactionInputBuilder::new()
+                    .with_source(TransactionInputSource::FromOutput(block.transactions[0].hash(), 0))
+                    .with_script(&[
+                        0, 71, 48, 68, 2, 32, 91, 128, 41, 150, 96, 53, 187, 63, 230, 129, 53, 234,
+                        210, 186, 21, 187, 98, 38, 255, 112, 30, 27, 228, 29, 132, 140, 155, 62, 123,
+                        216, 232, 168, 2, 32, 72, 126, 179, 207, 142, 8, 99, 8, 32, 78, 244, 166, 106,
+                        160, 207, 227, 61, 210, 172, 234, 234, 93, 59, 159, 79, 12, 194, 240, 212, 3,
+                        120, 50, 1, 71, 81, 33, 3, 113, 209, 131, 177, 9, 29, 242, 229, 15, 217, 247,
+                        165, 78, 111, 80, 79, 50, 200, 117, 80, 30, 233, 210, 167, 133, 175, 62, 253,
+                        134, 127, 212, 51, 33, 2, 128, 200, 184, 235, 148, 25, 43, 34, 28, 173, 55, 54,
+                        189, 164, 187, 243, 243, 152, 7, 84, 210, 85, 156, 238, 77, 97, 188, 240, 162,
+                        197, 105, 62, 82, 174,
+                    ])
+                    .build(),
+            );
+        for i in 0..n {
+            transaction_builder.add_output(TransactionOutput::payment(value.into(), &BtcAddress::P2PKH(H160::from_low_u64_be(i as u64 + 1))));
+        }
+        let op_return = btc_relay::OpReturnPaymentData::<T>::batch_op_return(&redeem_ids);
+        let transaction = transaction_builder
+            .add_output(TransactionOutput::op_return(0, op_return.as_bytes()))
+            .build();
+
+        let block = BlockBuilder::new()
+            .with_previous_hash(block_hash)
+            .with_version(4)
+            .with_coinbase(&BtcAddress::dummy(), 50, 3)
+            .with_timestamp(1588813835)
+            .add_transaction(transaction.clone())
+            .mine(U256::from(2).pow(254.into())).unwrap();
+
+        let tx_id = transaction.tx_id();
+        let proof = block.merkle_proof(&[tx_id]).unwrap().try_format().unwrap();
+        let raw_tx = transaction.format_with(true);
+
+        let raw_block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
+        let block_header = BtcRelay::<T>::parse_raw_block_header(&raw_block_header).unwrap();
+
+        BtcRelay::<T>::_store_block_header(&relayer_id, block_header).unwrap();
+        Security::<T>::set_active_block_number(Security::<T>::active_block_number() +
+BtcRelay::<T>::parachain_confirmations() + 1u32.into());
+
+        assert_ok!(Oracle::<T>::_set_exchange_rate(get_collateral_currency_id::<T>(),
+            UnsignedFixedPoint::<T>::one()
+        ));
+    }: _(RawOrigin::Signed(vault_id.account_id.clone()), redeem_ids, proof, raw_tx)
+
     cancel_redeem_reimburse {
         let origin: T::AccountId = account("Origin", 0, 0);
         let vault_id = get_vault_id::<T>();
@@ -380,6 +474,9 @@ BtcRelay::<T>::parachain_confirmations() + 1u32.into());
     set_redeem_period {
     }: _(RawOrigin::Root, 1u32.into())
 
+    set_expired_redeem_reimburse {
+    }: _(RawOrigin::Root, true)
+
     self_redeem {
         assert_ok!(Oracle::<T>::_set_exchange_rate(get_collateral_currency_id::<T>(),
             UnsignedFixedPoint::<T>::one()
@@ -41,6 +41,10 @@ pub trait WeightInfo {
 	fn cancel_redeem_retry() -> Weight;
 	fn set_redeem_period() -> Weight;
 	fn self_redeem() -> Weight;
+	fn execute_redeems(n: u32, ) -> Weight;
+	fn request_redeem_auto(n: u32, ) -> Weight;
+	fn cancel_expired_redeem() -> Weight;
+	fn set_expired_redeem_reimburse() -> Weight;
 }
 
 /// Weights for redeem using the Substrate node and recommended hardware.
@@ -145,6 +149,40 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	fn self_redeem() -> Weight {
 		Self::execute_redeem() // Dummy until we rerun benchmarks
 	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: Redeem RedeemRequests (r:1 w:1)
+	// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: BTCRelay Chains (r:1 w:0)
+	// Storage: BTCRelay BlockHeaders (r:1 w:0)
+	// Storage: BTCRelay StableBitcoinConfirmations (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: BTCRelay StableParachainConfirmations (r:1 w:0)
+	// Storage: Rewards TotalStake (r:1 w:1)
+	// Storage: VaultRegistry Vaults (r:1 w:1)
+	// Storage: Rewards Stake (r:1 w:1)
+	// Storage: Rewards RewardTally (r:1 w:1)
+	// Storage: Rewards RewardPerToken (r:1 w:0)
+	fn execute_redeems(n: u32, ) -> Weight {
+		Weight::from_ref_time(97_512_000 as u64)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_ref_time(71_284_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
+	}
+	fn request_redeem_auto(n: u32, ) -> Weight {
+		Self::request_redeem().saturating_mul(vault_registry::MAX_VAULTS_PER_REQUEST as u64)
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(n as u64))) // Dummy until we rerun benchmarks
+	}
+	fn cancel_expired_redeem() -> Weight {
+		Self::cancel_redeem_reimburse() // Dummy until we rerun benchmarks
+	}
+	// Storage: Redeem ExpiredRedeemReimburse (r:0 w:1)
+	fn set_expired_redeem_reimburse() -> Weight {
+		Weight::from_ref_time(3_194_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -248,5 +286,39 @@ impl WeightInfo for () {
 	fn self_redeem() -> Weight {
 		Self::execute_redeem() // dummy until we rerun benchmarks
 	}
+	// Storage: Security ParachainStatus (r:1 w:0)
+	// Storage: Redeem RedeemRequests (r:1 w:1)
+	// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
+	// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	// Storage: BTCRelay Chains (r:1 w:0)
+	// Storage: BTCRelay BlockHeaders (r:1 w:0)
+	// Storage: BTCRelay StableBitcoinConfirmations (r:1 w:0)
+	// Storage: Security ActiveBlockCount (r:1 w:0)
+	// Storage: BTCRelay StableParachainConfirmations (r:1 w:0)
+	// Storage: Rewards TotalStake (r:1 w:1)
+	// Storage: VaultRegistry Vaults (r:1 w:1)
+	// Storage: Rewards Stake (r:1 w:1)
+	// Storage: Rewards RewardTally (r:1 w:1)
+	// Storage: Rewards RewardPerToken (r:1 w:0)
+	fn execute_redeems(n: u32, ) -> Weight {
+		Weight::from_ref_time(97_512_000 as u64)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_ref_time(71_284_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
+	}
+	fn request_redeem_auto(n: u32, ) -> Weight {
+		Self::request_redeem().saturating_mul(vault_registry::MAX_VAULTS_PER_REQUEST as u64)
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(n as u64))) // dummy until we rerun benchmarks
+	}
+	fn cancel_expired_redeem() -> Weight {
+		Self::cancel_redeem_reimburse() // dummy until we rerun benchmarks
+	}
+	// Storage: Redeem ExpiredRedeemReimburse (r:0 w:1)
+	fn set_expired_redeem_reimburse() -> Weight {
+		Weight::from_ref_time(3_194_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
 
@@ -7,24 +7,39 @@ pub(crate) mod btc_relay {
     use btc_relay::BtcAddress;
     use frame_support::dispatch::DispatchError;
     use sp_core::H256;
-    use sp_std::convert::TryInto;
+    use sp_std::{
+        convert::{TryFrom, TryInto},
+        vec::Vec,
+    };
 
-    pub fn verify_and_validate_op_return_transaction<T: crate::Config, V: TryInto<Value>>(
+    pub fn get_and_verify_op_return_payment<T: crate::Config, V: TryFrom<Value>>(
         merkle_proof: MerkleProof,
         transaction: Transaction,
         recipient_btc_address: BtcAddress,
-        expected_btc: V,
         op_return_id: H256,
-    ) -> Result<(), DispatchError> {
-        <btc_relay::Pallet<T>>::verify_and_validate_op_return_transaction(
+    ) -> Result<V, DispatchError> {
+        <btc_relay::Pallet<T>>::get_and_verify_op_return_payment(
             merkle_proof,
             transaction,
             recipient_btc_address,
-            expected_btc,
             op_return_id,
         )
     }
 
+    pub fn verify_and_validate_op_return_batch_transaction<T: crate::Config, V: TryInto<Value>>(
+        merkle_proof: MerkleProof,
+        transaction: Transaction,
+        expected_payments: Vec<(V, BtcAddress)>,
+        op_return_ids: &[H256],
+    ) -> Result<(), DispatchError> {
+        <btc_relay::Pallet<T>>::verify_and_validate_op_return_batch_transaction(
+            merkle_proof,
+            transaction,
+            expected_payments,
+            op_return_ids,
+        )
+    }
+
     pub fn get_best_block_height<T: crate::Config>() -> u32 {
         <btc_relay::Pallet<T>>::get_best_block_height()
     }
@@ -51,7 +66,8 @@ pub(crate) mod vault_registry {
     use crate::DefaultVaultId;
     use currency::Amount;
     use frame_support::dispatch::{DispatchError, DispatchResult};
-    use vault_registry::types::{CurrencyId, CurrencySource, DefaultVault};
+    use sp_std::vec::Vec;
+    use vault_registry::types::{CurrencyId, CurrencySource, DefaultVault, DefaultVaultCurrencyPair};
 
     pub fn get_liquidated_collateral<T: crate::Config>(
         vault_id: &DefaultVaultId<T>,
@@ -65,6 +81,15 @@ pub(crate) mod vault_registry {
         <vault_registry::Pallet<T>>::get_free_redeemable_tokens(vault_id)
     }
 
+    pub fn select_vaults_for_redeem<T: crate::Config>(
+        currency_pair: &DefaultVaultCurrencyPair<T>,
+        candidates: &[DefaultVaultId<T>],
+        amount: &Amount<T>,
+        min_amount: &Amount<T>,
+    ) -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
+        <vault_registry::Pallet<T>>::select_vaults_for_redeem(currency_pair, candidates, amount, min_amount)
+    }
+
     pub fn transfer_funds<T: crate::Config>(
         from: CurrencySource<T>,
         to: CurrencySource<T>,
@@ -116,6 +141,20 @@ pub(crate) mod vault_registry {
         <vault_registry::Pallet<T>>::decrease_liquidated_collateral(vault_id, amount)
     }
 
+    pub fn confiscate_liquidated_basket_collateral<T: crate::Config>(
+        vault_id: &DefaultVaultId<T>,
+        tokens: &Amount<T>,
+        to_be_redeemed_tokens: &Amount<T>,
+        reimbursed_redeemer: Option<&T::AccountId>,
+    ) -> DispatchResult {
+        <vault_registry::Pallet<T>>::confiscate_liquidated_basket_collateral(
+            vault_id,
+            tokens,
+            to_be_redeemed_tokens,
+            reimbursed_redeemer,
+        )
+    }
+
     pub fn redeem_tokens_liquidation<T: crate::Config>(
         currency_id: CurrencyId<T>,
         redeemer_id: &T::AccountId,
@@ -221,6 +260,7 @@ pub(crate) mod oracle {
 pub(crate) mod fee {
     use currency::Amount;
     use frame_support::dispatch::{DispatchError, DispatchResult};
+    use oracle::types::UnsignedFixedPoint;
 
     pub fn fee_pool_account_id<T: crate::Config>() -> T::AccountId {
         <fee::Pallet<T>>::fee_pool_account_id()
@@ -230,6 +270,10 @@ pub(crate) mod fee {
         <fee::Pallet<T>>::get_redeem_fee(amount)
     }
 
+    pub fn get_redeem_fee_value<T: crate::Config>() -> UnsignedFixedPoint<T> {
+        <fee::Pallet<T>>::redeem_fee()
+    }
+
     pub fn distribute_rewards<T: crate::Config>(amount: &Amount<T>) -> DispatchResult {
         <fee::Pallet<T>>::distribute_rewards(amount)
     }
@@ -238,6 +282,10 @@ pub(crate) mod fee {
         <fee::Pallet<T>>::get_punishment_fee(amount)
     }
 
+    pub fn get_keeper_reward_fee<T: crate::Config>(amount: &Amount<T>) -> Result<Amount<T>, DispatchError> {
+        <fee::Pallet<T>>::get_keeper_reward_fee(amount)
+    }
+
     pub fn get_premium_redeem_fee<T: crate::Config>(amount: &Amount<T>) -> Result<Amount<T>, DispatchError> {
         <fee::Pallet<T>>::get_premium_redeem_fee(amount)
     }
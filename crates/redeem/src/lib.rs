@@ -37,18 +37,24 @@ use frame_support::{
     ensure, transactional,
 };
 use frame_system::{ensure_root, ensure_signed};
-use oracle::OracleKey;
+use oracle::{types::UnsignedFixedPoint, OracleKey};
 use sp_core::H256;
-use sp_runtime::{ArithmeticError, FixedPointNumber};
+use sp_runtime::{
+    traits::{One, Saturating},
+    ArithmeticError, FixedPointNumber,
+};
 use sp_std::{convert::TryInto, vec::Vec};
 use types::DefaultVaultId;
 use vault_registry::{
-    types::{CurrencyId, DefaultVaultCurrencyPair},
+    types::{CandidateVaults, CurrencyId, DefaultVaultCurrencyPair},
     CurrencySource,
 };
 
 pub use pallet::*;
 
+/// Maximum number of redeem requests that can be executed with a single Bitcoin transaction
+pub const MAX_REDEEMS_PER_BATCH: u32 = 20;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -115,6 +121,22 @@ pub mod pallet {
             amount: BalanceOf<T>,
             fee: BalanceOf<T>,
         },
+        RedeemAmountChange {
+            redeem_id: H256,
+            amount: BalanceOf<T>,
+            fee: BalanceOf<T>,
+            premium: BalanceOf<T>,
+            outstanding_amount: BalanceOf<T>,
+            slashed_amount: BalanceOf<T>,
+        },
+        KeeperReward {
+            redeem_id: H256,
+            keeper: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        ExpiredRedeemReimburseChange {
+            reimburse: bool,
+        },
     }
 
     #[pallet::error]
@@ -137,6 +159,10 @@ pub mod pallet {
         TryIntoIntError,
         /// Redeem amount is too small.
         AmountBelowDustAmount,
+        /// Batch of redeem requests is empty, too large or contains duplicates.
+        InvalidRedeemBatch,
+        /// Not expected origin.
+        InvalidExecutor,
     }
 
     /// The time difference in number of blocks between a redeem request is created and required completion time by a
@@ -146,6 +172,12 @@ pub mod pallet {
     #[pallet::getter(fn redeem_period)]
     pub(super) type RedeemPeriod<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+    /// Whether redeem requests cancelled by `cancel_expired_redeem` are reimbursed in collateral
+    /// (`true`) or retried, such that the redeemer keeps their tokens (`false`).
+    #[pallet::storage]
+    #[pallet::getter(fn expired_redeem_reimburse)]
+    pub(super) type ExpiredRedeemReimburse<T: Config> = StorageValue<_, bool, ValueQuery>;
+
     /// Users create redeem requests to receive BTC in return for their previously issued tokens.
     /// This mapping provides access from a unique hash redeemId to a Redeem struct.
     #[pallet::storage]
@@ -259,6 +291,8 @@ pub mod pallet {
         /// A Vault calls this function after receiving an RequestRedeem event with their public key.
         /// Before calling the function, the Vault transfers the specific amount of BTC to the BTC address
         /// given in the original redeem request. The Vault completes the redeem with this function.
+        /// If less than the requested amount was transferred, only the redeemer may execute the request,
+        /// in which case only the paid part is redeemed and the remainder is released back to the redeemer.
         ///
         /// # Arguments
         ///
@@ -277,8 +311,8 @@ pub mod pallet {
             merkle_proof: Vec<u8>,
             raw_tx: Vec<u8>,
         ) -> DispatchResultWithPostInfo {
-            let _ = ensure_signed(origin)?;
-            Self::_execute_redeem(redeem_id, merkle_proof, raw_tx)?;
+            let executor = ensure_signed(origin)?;
+            Self::_execute_redeem(executor, redeem_id, merkle_proof, raw_tx)?;
 
             // Don't take tx fees on success. If the vault had to pay for this function, it would
             // have been vulnerable to a griefing attack where users would redeem amounts just
@@ -364,6 +398,96 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Completes multiple redeem requests that were paid out with a single Bitcoin transaction.
+        /// The transaction must contain one payment per redeemer address and an OP_RETURN committing
+        /// to all `redeem_ids`, in the given order.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - anyone executing these redeem requests
+        /// * `redeem_ids` - identifiers of the redeem requests as output from request_redeem
+        /// * `merkle_proof` - raw bytes
+        /// * `raw_tx` - raw bytes
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::execute_redeems(redeem_ids.len() as u32))]
+        #[transactional]
+        pub fn execute_redeems(
+            origin: OriginFor<T>,
+            redeem_ids: Vec<H256>,
+            merkle_proof: Vec<u8>,
+            raw_tx: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+            Self::_execute_redeems(redeem_ids, merkle_proof, raw_tx)?;
+
+            // Don't take tx fees on success, for the same reason as in `execute_redeem`.
+            Ok(Pays::No.into())
+        }
+
+        /// Initializes a request to burn issued tokens without choosing a single vault. Out of the given
+        /// candidates, vaults below the premium redeem threshold are used first, followed by all other
+        /// vaults, each group in descending order of redeemable tokens. The amount is only split if the first vault can
+        /// not redeem all of it, and never across more than [`vault_registry::MAX_VAULTS_PER_REQUEST`]
+        /// vaults. A separate redeem request is created for every selected vault.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - sender of the transaction
+        /// * `amount_wrapped` - amount of issued tokens
+        /// * `btc_address` - the address to receive BTC
+        /// * `currency_pair` - the collateral and wrapped currency of the vaults to redeem from
+        /// * `candidates` - the vaults to choose from
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_redeem_auto(candidates.len() as u32))]
+        #[transactional]
+        pub fn request_redeem_auto(
+            origin: OriginFor<T>,
+            #[pallet::compact] amount_wrapped: BalanceOf<T>,
+            btc_address: BtcAddress,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            candidates: CandidateVaults<T>,
+        ) -> DispatchResultWithPostInfo {
+            let redeemer = ensure_signed(origin)?;
+            Self::_request_redeem_auto(redeemer, amount_wrapped, btc_address, currency_pair, &candidates)?;
+            Ok(().into())
+        }
+
+        /// Cancel an expired redeem request on behalf of the redeemer. Whether the redeemer is
+        /// reimbursed or keeps their tokens is decided by [`ExpiredRedeemReimburse`]. On top of the
+        /// punishment fee, the vault pays a keeper reward to the caller out of its collateral. If the
+        /// vault is liquidated, the reward is paid by the liquidation vault instead.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - anyone cancelling the redeem request
+        /// * `redeem_id` - identifier of redeem request as output from request_redeem
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_expired_redeem())]
+        #[transactional]
+        pub fn cancel_expired_redeem(origin: OriginFor<T>, redeem_id: H256) -> DispatchResultWithPostInfo {
+            let keeper = ensure_signed(origin)?;
+            Self::_cancel_expired_redeem(keeper, redeem_id)?;
+            Ok(().into())
+        }
+
+        /// Set whether redeem requests cancelled by `cancel_expired_redeem` are reimbursed.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the dispatch origin of this call (must be _Root_)
+        /// * `reimburse` - reimburse the redeemer if true, retry otherwise
+        ///
+        /// # Weight: `O(1)`
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_expired_redeem_reimburse())]
+        #[transactional]
+        pub fn set_expired_redeem_reimburse(origin: OriginFor<T>, reimburse: bool) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            <ExpiredRedeemReimburse<T>>::set(reimburse);
+            Self::deposit_event(Event::ExpiredRedeemReimburseChange { reimburse });
+            Ok(().into())
+        }
     }
 }
 
@@ -542,6 +666,22 @@ impl<T: Config> Pallet<T> {
         Ok(redeem_id)
     }
 
+    fn _request_redeem_auto(
+        redeemer: T::AccountId,
+        amount_wrapped: BalanceOf<T>,
+        btc_address: BtcAddress,
+        currency_pair: DefaultVaultCurrencyPair<T>,
+        candidates: &[DefaultVaultId<T>],
+    ) -> Result<Vec<H256>, DispatchError> {
+        let amount_wrapped = Amount::new(amount_wrapped, currency_pair.wrapped);
+        let min_amount = Self::get_min_redeem_amount(currency_pair.wrapped)?;
+
+        ext::vault_registry::select_vaults_for_redeem::<T>(&currency_pair, candidates, &amount_wrapped, &min_amount)?
+            .into_iter()
+            .map(|(vault_id, amount)| Self::_request_redeem(redeemer.clone(), amount.amount(), btc_address, vault_id))
+            .collect()
+    }
+
     fn _liquidation_redeem(
         redeemer: T::AccountId,
         currencies: DefaultVaultCurrencyPair<T>,
@@ -568,20 +708,150 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn _execute_redeem(redeem_id: H256, raw_merkle_proof: Vec<u8>, raw_tx: Vec<u8>) -> Result<(), DispatchError> {
-        let redeem = Self::get_open_redeem_request_from_id(&redeem_id)?;
+    fn _execute_redeem(
+        executor: T::AccountId,
+        redeem_id: H256,
+        raw_merkle_proof: Vec<u8>,
+        raw_tx: Vec<u8>,
+    ) -> Result<(), DispatchError> {
+        let mut redeem = Self::get_open_redeem_request_from_id(&redeem_id)?;
 
         // check the transaction inclusion and validity
         let transaction = ext::btc_relay::parse_transaction::<T>(&raw_tx)?;
         let merkle_proof = ext::btc_relay::parse_merkle_proof::<T>(&raw_merkle_proof)?;
-        ext::btc_relay::verify_and_validate_op_return_transaction::<T, _>(
+        let amount_transferred = ext::btc_relay::get_and_verify_op_return_payment::<T, BalanceOf<T>>(
             merkle_proof,
             transaction,
             redeem.btc_address,
-            redeem.amount_btc,
             redeem_id,
         )?;
+        let amount_transferred = Amount::new(amount_transferred, redeem.vault.wrapped_currency());
+
+        // the vault must never pay out more than requested
+        ensure!(
+            !amount_transferred.is_zero() && amount_transferred.le(&redeem.amount_btc())?,
+            btc_relay::Error::<T>::InvalidPaymentAmount
+        );
+
+        if amount_transferred.lt(&redeem.amount_btc())? {
+            // only the redeemer can accept payments with insufficient amounts
+            ensure!(executor == redeem.redeemer, Error::<T>::InvalidExecutor);
+            Self::decrease_redeem_amount(&redeem_id, &mut redeem, amount_transferred)?;
+        }
 
+        Self::complete_redeem(redeem_id, redeem)
+    }
+
+    /// Reduces a redeem request to the amount that was actually transferred. The outstanding tokens
+    /// are released back to the redeemer, and the vault is punished for them as it would be if the
+    /// redeemer had cancelled the request without reimbursement.
+    fn decrease_redeem_amount(
+        redeem_id: &H256,
+        redeem: &mut DefaultRedeemRequest<T>,
+        amount_transferred: Amount<T>,
+    ) -> DispatchResult {
+        let vault_id = redeem.vault.clone();
+        let expected_amount = redeem.amount_btc();
+        let outstanding_amount = expected_amount.checked_sub(&amount_transferred)?;
+
+        // the parachain fee and the premium are only charged for the part that was paid
+        let settled_fee =
+            ext::vault_registry::calculate_collateral::<T>(&redeem.fee(), &amount_transferred, &expected_amount)?;
+        let settled_premium =
+            ext::vault_registry::calculate_collateral::<T>(&redeem.premium()?, &amount_transferred, &expected_amount)?;
+        let outstanding_fee = redeem.fee().checked_sub(&settled_fee)?;
+
+        let vault = ext::vault_registry::get_vault_from_id::<T>(&vault_id)?;
+        let slashed_amount = if vault.is_liquidated() {
+            let vault_to_be_redeemed_tokens = Amount::new(vault.to_be_redeemed_tokens, vault_id.wrapped_currency());
+            let confiscated_collateral = ext::vault_registry::calculate_collateral::<T>(
+                &ext::vault_registry::get_liquidated_collateral::<T>(&vault_id)?,
+                &outstanding_amount,
+                &vault_to_be_redeemed_tokens, // note: this is the value read prior to making changes
+            )?;
+            ext::vault_registry::decrease_liquidated_collateral::<T>(&vault_id, &confiscated_collateral)?;
+            ext::vault_registry::transfer_funds::<T>(
+                CurrencySource::LiquidatedCollateral(vault_id.clone()),
+                CurrencySource::LiquidationVault(vault_id.currencies.clone()),
+                &confiscated_collateral,
+            )?;
+
+            confiscated_collateral
+        } else {
+            let outstanding_in_collateral = outstanding_amount.convert_to(vault_id.collateral_currency())?;
+            let punishment_fee_in_collateral = ext::fee::get_punishment_fee::<T>(&outstanding_in_collateral)?;
+            ext::vault_registry::transfer_funds_saturated::<T>(
+                CurrencySource::Collateral(vault_id.clone()),
+                CurrencySource::FreeBalance(redeem.redeemer.clone()),
+                &punishment_fee_in_collateral,
+            )?
+        };
+
+        // unlock the outstanding tokens, including their share of the fee
+        outstanding_amount
+            .checked_add(&outstanding_fee)?
+            .unlock_on(&redeem.redeemer)?;
+        ext::vault_registry::decrease_to_be_redeemed_tokens::<T>(&vault_id, &outstanding_amount)?;
+
+        redeem.amount_btc = amount_transferred.amount();
+        redeem.fee = settled_fee.amount();
+        redeem.premium = settled_premium.amount();
+        Self::insert_redeem_request(redeem_id, redeem);
+
+        Self::deposit_event(Event::<T>::RedeemAmountChange {
+            redeem_id: *redeem_id,
+            amount: redeem.amount_btc,
+            fee: redeem.fee,
+            premium: redeem.premium,
+            outstanding_amount: outstanding_amount.amount(),
+            slashed_amount: slashed_amount.amount(),
+        });
+
+        Ok(())
+    }
+
+    fn _execute_redeems(
+        redeem_ids: Vec<H256>,
+        raw_merkle_proof: Vec<u8>,
+        raw_tx: Vec<u8>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            !redeem_ids.is_empty() && redeem_ids.len() <= MAX_REDEEMS_PER_BATCH as usize,
+            Error::<T>::InvalidRedeemBatch
+        );
+        for (index, redeem_id) in redeem_ids.iter().enumerate() {
+            ensure!(
+                !redeem_ids[index + 1..].contains(redeem_id),
+                Error::<T>::InvalidRedeemBatch
+            );
+        }
+
+        let redeems = redeem_ids
+            .iter()
+            .map(|redeem_id| Self::get_open_redeem_request_from_id(redeem_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // check the transaction inclusion and validity
+        let transaction = ext::btc_relay::parse_transaction::<T>(&raw_tx)?;
+        let merkle_proof = ext::btc_relay::parse_merkle_proof::<T>(&raw_merkle_proof)?;
+        ext::btc_relay::verify_and_validate_op_return_batch_transaction::<T, _>(
+            merkle_proof,
+            transaction,
+            redeems
+                .iter()
+                .map(|redeem| (redeem.amount_btc, redeem.btc_address))
+                .collect(),
+            &redeem_ids,
+        )?;
+
+        for (redeem_id, redeem) in redeem_ids.into_iter().zip(redeems.into_iter()) {
+            Self::complete_redeem(redeem_id, redeem)?;
+        }
+        Ok(())
+    }
+
+    /// Burns the tokens of a redeem request for which the payment has been verified, and marks it as completed
+    fn complete_redeem(redeem_id: H256, redeem: DefaultRedeemRequest<T>) -> DispatchResult {
         // burn amount (without parachain fee, but including transfer fee)
         let burn_amount = redeem.amount_btc().checked_add(&redeem.transfer_fee_btc())?;
         burn_amount.burn_from(&redeem.redeemer)?;
@@ -612,6 +882,37 @@ impl<T: Config> Pallet<T> {
         let redeem = Self::get_open_redeem_request_from_id(&redeem_id)?;
         ensure!(redeemer == redeem.redeemer, Error::<T>::UnauthorizedRedeemer);
 
+        Self::cancel_expired_request(redeem_id, redeem, reimburse, None)?;
+        Ok(())
+    }
+
+    fn _cancel_expired_redeem(keeper: T::AccountId, redeem_id: H256) -> DispatchResult {
+        ext::security::ensure_parachain_status_running::<T>()?;
+
+        let redeem = Self::get_open_redeem_request_from_id(&redeem_id)?;
+        let reward = Self::cancel_expired_request(redeem_id, redeem, Self::expired_redeem_reimburse(), Some(&keeper))?;
+
+        Self::deposit_event(Event::<T>::KeeperReward {
+            redeem_id,
+            keeper,
+            amount: reward.amount(),
+        });
+
+        Ok(())
+    }
+
+    /// Cancels a redeem request after it has expired. If a keeper cancels the request on behalf of
+    /// the redeemer, the vault pays them a reward out of its collateral, on top of the punishment fee.
+    /// Liquidated vaults are not punished, so their keeper reward is paid by the liquidation vault.
+    /// Returns the keeper reward.
+    fn cancel_expired_request(
+        redeem_id: H256,
+        redeem: DefaultRedeemRequest<T>,
+        reimburse: bool,
+        keeper: Option<&T::AccountId>,
+    ) -> Result<Amount<T>, DispatchError> {
+        let redeemer = redeem.redeemer.clone();
+
         // only cancellable after the request has expired
         ensure!(
             ext::btc_relay::has_request_expired::<T>(
@@ -631,7 +932,7 @@ impl<T: Config> Pallet<T> {
         let amount_wrapped_in_collateral = vault_to_be_burned_tokens.convert_to(vault_id.collateral_currency())?;
 
         // now update the collateral; the logic is different for liquidated vaults.
-        let slashed_amount = if vault.is_liquidated() {
+        let (slashed_amount, keeper_reward) = if vault.is_liquidated() {
             let confiscated_collateral = ext::vault_registry::calculate_collateral::<T>(
                 &ext::vault_registry::get_liquidated_collateral::<T>(&redeem.vault)?,
                 &vault_to_be_burned_tokens,
@@ -649,8 +950,24 @@ impl<T: Config> Pallet<T> {
                 slashing_destination,
                 &confiscated_collateral,
             )?;
+            // the same share of the basket collateral goes to the same destination
+            ext::vault_registry::confiscate_liquidated_basket_collateral::<T>(
+                &vault_id,
+                &vault_to_be_burned_tokens,
+                &vault_to_be_redeemed_tokens,
+                reimburse.then(|| &redeemer),
+            )?;
 
-            confiscated_collateral
+            let keeper_reward = match keeper {
+                Some(keeper) => ext::vault_registry::transfer_funds_saturated::<T>(
+                    CurrencySource::LiquidationVault(vault_id.currencies.clone()),
+                    CurrencySource::FreeBalance(keeper.clone()),
+                    &ext::fee::get_keeper_reward_fee::<T>(&amount_wrapped_in_collateral)?,
+                )?,
+                None => Amount::zero(vault_id.collateral_currency()),
+            };
+
+            (confiscated_collateral, keeper_reward)
         } else {
             // not liquidated
 
@@ -670,9 +987,18 @@ impl<T: Config> Pallet<T> {
                 &amount_to_slash,
             )?;
 
+            let keeper_reward = match keeper {
+                Some(keeper) => ext::vault_registry::transfer_funds_saturated::<T>(
+                    CurrencySource::Collateral(vault_id.clone()),
+                    CurrencySource::FreeBalance(keeper.clone()),
+                    &ext::fee::get_keeper_reward_fee::<T>(&amount_wrapped_in_collateral)?,
+                )?,
+                None => Amount::zero(vault_id.collateral_currency()),
+            };
+
             let _ = ext::vault_registry::ban_vault::<T>(&vault_id);
 
-            amount_to_slash
+            (amount_to_slash, keeper_reward)
         };
 
         // first update the issued tokens; this logic is the same regardless of whether or not the vault is liquidated
@@ -715,7 +1041,7 @@ impl<T: Config> Pallet<T> {
             status: new_status,
         });
 
-        Ok(())
+        Ok(keeper_reward)
     }
 
     fn _mint_tokens_for_reimbursed_redeem(vault_id: DefaultVaultId<T>, redeem_id: H256) -> DispatchResult {
@@ -801,6 +1127,18 @@ impl<T: Config> Pallet<T> {
     pub fn get_dust_value(currency_id: CurrencyId<T>) -> Amount<T> {
         Amount::new(<RedeemBtcDustValue<T>>::get(), currency_id)
     }
+
+    /// Get the smallest amount of wrapped tokens a redeem request can be made for, such that the
+    /// amount left after subtracting the redeem fee and the inclusion fee is not below the dust value.
+    fn get_min_redeem_amount(currency_id: CurrencyId<T>) -> Result<Amount<T>, DispatchError> {
+        let min_to_be_burned =
+            Self::get_dust_value(currency_id).checked_add(&Self::get_current_inclusion_fee(currency_id)?)?;
+        let fee_complement = UnsignedFixedPoint::<T>::one().saturating_sub(ext::fee::get_redeem_fee_value::<T>());
+        // round up, since the fee is rounded up as well
+        min_to_be_burned
+            .checked_div(&fee_complement)?
+            .checked_add(&Amount::new(1u32.into(), currency_id))
+    }
     /// Fetch all redeem requests for the specified account.
     ///
     /// # Arguments
@@ -1,6 +1,6 @@
 use crate::{ext, mock::*};
 
-use crate::types::{RedeemRequest, RedeemRequestStatus};
+use crate::types::{DefaultVaultId, RedeemRequest, RedeemRequestStatus};
 use bitcoin::types::{MerkleProof, Transaction};
 use btc_relay::BtcAddress;
 use currency::Amount;
@@ -8,7 +8,7 @@ use frame_support::{assert_err, assert_noop, assert_ok, dispatch::DispatchError}
 use mocktopus::mocking::*;
 use security::Pallet as Security;
 use sp_core::{H160, H256};
-use vault_registry::{DefaultVault, VaultStatus};
+use vault_registry::{CurrencySource, DefaultVault, VaultStatus};
 
 type Event = crate::Event<Test>;
 
@@ -235,6 +235,38 @@ fn test_request_redeem_succeeds_with_normal_redeem() {
     })
 }
 
+#[test]
+fn test_request_redeem_auto_requests_from_selected_vaults() {
+    run_test(|| {
+        let vault2 = DefaultVaultId::<Test>::new(
+            VAULT.account_id + 1,
+            VAULT.collateral_currency(),
+            VAULT.wrapped_currency(),
+        );
+        let selected_vaults = vec![(VAULT, wrapped(200)), (vault2, wrapped(100))];
+        let expected_requests = selected_vaults.clone();
+        let btc_address = BtcAddress::random();
+
+        ext::vault_registry::select_vaults_for_redeem::<Test>.mock_safe(move |currency_pair, candidates, amount, _| {
+            assert_eq!(currency_pair, &DEFAULT_CURRENCY_PAIR);
+            assert_eq!(candidates, &[VAULT]);
+            assert_eq!(amount, &wrapped(300));
+            MockResult::Return(Ok(selected_vaults.clone()))
+        });
+        Redeem::_request_redeem.mock_safe(move |redeemer, amount, address, vault_id| {
+            assert_eq!(redeemer, USER);
+            assert_eq!(address, btc_address);
+            assert!(expected_requests.contains(&(vault_id, wrapped(amount))));
+            MockResult::Return(Ok(H256::from_low_u64_be(amount as u64)))
+        });
+
+        assert_eq!(
+            Redeem::_request_redeem_auto(USER, 300, btc_address, DEFAULT_CURRENCY_PAIR, &[VAULT]),
+            Ok(vec![H256::from_low_u64_be(200), H256::from_low_u64_be(100)])
+        );
+    })
+}
+
 #[test]
 fn test_request_redeem_fails_with_default_btc_address() {
     run_test(|| {
@@ -407,8 +439,8 @@ fn test_execute_redeem_succeeds_with_another_account() {
         );
         ext::btc_relay::parse_merkle_proof::<Test>.mock_safe(|_| MockResult::Return(Ok(dummy_merkle_proof())));
         ext::btc_relay::parse_transaction::<Test>.mock_safe(|_| MockResult::Return(Ok(Transaction::default())));
-        ext::btc_relay::verify_and_validate_op_return_transaction::<Test, Balance>
-            .mock_safe(|_, _, _, _, _| MockResult::Return(Ok(())));
+        ext::btc_relay::get_and_verify_op_return_payment::<Test, Balance>
+            .mock_safe(|_, _, _, _| MockResult::Return(Ok(100)));
 
         let btc_fee = Redeem::get_current_inclusion_fee(DEFAULT_WRAPPED_CURRENCY).unwrap();
 
@@ -488,8 +520,8 @@ fn test_execute_redeem_succeeds() {
         );
         ext::btc_relay::parse_merkle_proof::<Test>.mock_safe(|_| MockResult::Return(Ok(dummy_merkle_proof())));
         ext::btc_relay::parse_transaction::<Test>.mock_safe(|_| MockResult::Return(Ok(Transaction::default())));
-        ext::btc_relay::verify_and_validate_op_return_transaction::<Test, Balance>
-            .mock_safe(|_, _, _, _, _| MockResult::Return(Ok(())));
+        ext::btc_relay::get_and_verify_op_return_payment::<Test, Balance>
+            .mock_safe(|_, _, _, _| MockResult::Return(Ok(100)));
 
         let btc_fee = Redeem::get_current_inclusion_fee(DEFAULT_WRAPPED_CURRENCY).unwrap();
 
@@ -546,6 +578,302 @@ fn test_execute_redeem_succeeds() {
     })
 }
 
+#[test]
+fn test_execute_redeem_with_underpayment_succeeds() {
+    run_test(|| {
+        convert_to.mock_safe(|_, x| MockResult::Return(Ok(x)));
+        Security::<Test>::set_active_block_number(40);
+        <vault_registry::Pallet<Test>>::insert_vault(
+            &VAULT,
+            vault_registry::Vault {
+                issued_tokens: 200,
+                to_be_redeemed_tokens: 200,
+                ..default_vault()
+            },
+        );
+        ext::btc_relay::parse_merkle_proof::<Test>.mock_safe(|_| MockResult::Return(Ok(dummy_merkle_proof())));
+        ext::btc_relay::parse_transaction::<Test>.mock_safe(|_| MockResult::Return(Ok(Transaction::default())));
+        ext::btc_relay::get_and_verify_op_return_payment::<Test, Balance>
+            .mock_safe(|_, _, _, _| MockResult::Return(Ok(60)));
+
+        let btc_fee = Redeem::get_current_inclusion_fee(DEFAULT_WRAPPED_CURRENCY).unwrap();
+
+        inject_redeem_request(
+            H256([0u8; 32]),
+            RedeemRequest {
+                period: 0,
+                vault: VAULT,
+                opentime: 40,
+                fee: 10,
+                amount_btc: 100,
+                premium: 20,
+                redeemer: USER,
+                btc_address: BtcAddress::random(),
+                btc_height: 0,
+                status: RedeemRequestStatus::Pending,
+                transfer_fee_btc: btc_fee.amount(),
+            },
+        );
+
+        ext::fee::get_punishment_fee::<Test>.mock_safe(|amount| {
+            assert_eq!(amount, &collateral(40));
+            MockResult::Return(Ok(collateral(4)))
+        });
+        ext::vault_registry::transfer_funds_saturated::<Test>
+            .mock_safe(move |_, _, amount| MockResult::Return(Ok(amount.clone())));
+        ext::vault_registry::decrease_to_be_redeemed_tokens::<Test>.mock_safe(|vault, amount| {
+            assert_eq!(vault, &VAULT);
+            assert_eq!(amount, &wrapped(40));
+            MockResult::Return(Ok(()))
+        });
+        // the outstanding amount and its share of the fee are released, the rest of the fee is paid
+        Amount::<Test>::unlock_on.mock_safe(|amount, _| {
+            assert!(amount == &wrapped(44) || amount == &wrapped(6));
+            MockResult::Return(Ok(()))
+        });
+        Amount::<Test>::transfer.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+        ext::fee::distribute_rewards::<Test>.mock_safe(|_| MockResult::Return(Ok(())));
+        Amount::<Test>::burn_from.mock_safe(move |amount_wrapped, redeemer| {
+            assert_eq!(redeemer, &USER);
+            assert_eq!(amount_wrapped, &(wrapped(60) + btc_fee));
+            MockResult::Return(Ok(()))
+        });
+        ext::vault_registry::redeem_tokens::<Test>.mock_safe(move |vault, amount_wrapped, premium, _| {
+            assert_eq!(vault, &VAULT);
+            assert_eq!(amount_wrapped, &(wrapped(60) + btc_fee));
+            assert_eq!(premium, &collateral(12));
+            MockResult::Return(Ok(()))
+        });
+
+        assert_ok!(Redeem::execute_redeem(
+            RuntimeOrigin::signed(USER),
+            H256([0u8; 32]),
+            Vec::default(),
+            Vec::default()
+        ));
+        assert_emitted!(Event::RedeemAmountChange {
+            redeem_id: H256([0; 32]),
+            amount: 60,
+            fee: 6,
+            premium: 12,
+            outstanding_amount: 40,
+            slashed_amount: 4,
+        });
+        assert_emitted!(Event::ExecuteRedeem {
+            redeem_id: H256([0; 32]),
+            redeemer: USER,
+            vault_id: VAULT,
+            amount: 60,
+            fee: 6,
+            transfer_fee: btc_fee.amount(),
+        });
+        assert_err!(
+            Redeem::get_open_redeem_request_from_id(&H256([0u8; 32])),
+            TestError::RedeemCompleted,
+        );
+    })
+}
+
+#[test]
+fn test_execute_redeem_with_underpayment_fails_with_invalid_executor() {
+    run_test(|| {
+        ext::btc_relay::parse_merkle_proof::<Test>.mock_safe(|_| MockResult::Return(Ok(dummy_merkle_proof())));
+        ext::btc_relay::parse_transaction::<Test>.mock_safe(|_| MockResult::Return(Ok(Transaction::default())));
+        ext::btc_relay::get_and_verify_op_return_payment::<Test, Balance>
+            .mock_safe(|_, _, _, _| MockResult::Return(Ok(60)));
+
+        inject_redeem_request(
+            H256([0u8; 32]),
+            RedeemRequest {
+                period: 0,
+                vault: VAULT,
+                opentime: 40,
+                fee: 0,
+                amount_btc: 100,
+                premium: 0,
+                redeemer: USER,
+                btc_address: BtcAddress::random(),
+                btc_height: 0,
+                status: RedeemRequestStatus::Pending,
+                transfer_fee_btc: 0,
+            },
+        );
+
+        assert_noop!(
+            Redeem::execute_redeem(
+                RuntimeOrigin::signed(VAULT.account_id),
+                H256([0u8; 32]),
+                Vec::default(),
+                Vec::default()
+            ),
+            TestError::InvalidExecutor
+        );
+    })
+}
+
+#[test]
+fn test_execute_redeem_with_overpayment_fails() {
+    run_test(|| {
+        ext::btc_relay::parse_merkle_proof::<Test>.mock_safe(|_| MockResult::Return(Ok(dummy_merkle_proof())));
+        ext::btc_relay::parse_transaction::<Test>.mock_safe(|_| MockResult::Return(Ok(Transaction::default())));
+        ext::btc_relay::get_and_verify_op_return_payment::<Test, Balance>
+            .mock_safe(|_, _, _, _| MockResult::Return(Ok(101)));
+
+        inject_redeem_request(
+            H256([0u8; 32]),
+            RedeemRequest {
+                period: 0,
+                vault: VAULT,
+                opentime: 40,
+                fee: 0,
+                amount_btc: 100,
+                premium: 0,
+                redeemer: USER,
+                btc_address: BtcAddress::random(),
+                btc_height: 0,
+                status: RedeemRequestStatus::Pending,
+                transfer_fee_btc: 0,
+            },
+        );
+
+        assert_noop!(
+            Redeem::execute_redeem(
+                RuntimeOrigin::signed(USER),
+                H256([0u8; 32]),
+                Vec::default(),
+                Vec::default()
+            ),
+            btc_relay::Error::<Test>::InvalidPaymentAmount
+        );
+    })
+}
+
+#[test]
+fn test_execute_redeems_succeeds() {
+    run_test(|| {
+        convert_to.mock_safe(|_, x| MockResult::Return(Ok(x)));
+        Security::<Test>::set_active_block_number(40);
+        <vault_registry::Pallet<Test>>::insert_vault(
+            &VAULT,
+            vault_registry::Vault {
+                id: VAULT,
+                to_be_replaced_tokens: 0,
+                to_be_issued_tokens: 0,
+                issued_tokens: 400,
+                to_be_redeemed_tokens: 400,
+                replace_collateral: 0,
+                active_replace_collateral: 0,
+                banned_until: None,
+                secure_collateral_threshold: None,
+                status: VaultStatus::Active(true),
+                liquidated_collateral: 0,
+            },
+        );
+        ext::btc_relay::parse_merkle_proof::<Test>.mock_safe(|_| MockResult::Return(Ok(dummy_merkle_proof())));
+        ext::btc_relay::parse_transaction::<Test>.mock_safe(|_| MockResult::Return(Ok(Transaction::default())));
+
+        let btc_fee = Redeem::get_current_inclusion_fee(DEFAULT_WRAPPED_CURRENCY).unwrap();
+        let redeem_ids = vec![H256([0u8; 32]), H256([1u8; 32])];
+        let btc_addresses = vec![BtcAddress::random(), BtcAddress::random()];
+        let amounts = vec![100, 150];
+
+        for i in 0..2 {
+            inject_redeem_request(
+                redeem_ids[i],
+                RedeemRequest {
+                    period: 0,
+                    vault: VAULT,
+                    opentime: 40,
+                    fee: 0,
+                    amount_btc: amounts[i],
+                    premium: 0,
+                    redeemer: USER,
+                    btc_address: btc_addresses[i],
+                    btc_height: 0,
+                    status: RedeemRequestStatus::Pending,
+                    transfer_fee_btc: btc_fee.amount(),
+                },
+            );
+        }
+
+        let expected_ids = redeem_ids.clone();
+        let expected_payments: Vec<_> = amounts.iter().cloned().zip(btc_addresses.iter().cloned()).collect();
+        ext::btc_relay::verify_and_validate_op_return_batch_transaction::<Test, Balance>.mock_safe(
+            move |_, _, payments: Vec<(Balance, BtcAddress)>, op_return_ids: &[H256]| {
+                assert_eq!(payments, expected_payments);
+                assert_eq!(op_return_ids, &expected_ids[..]);
+                MockResult::Return(Ok(()))
+            },
+        );
+        Amount::<Test>::burn_from.mock_safe(|_, _| MockResult::Return(Ok(())));
+        ext::vault_registry::redeem_tokens::<Test>.mock_safe(|_, _, _, _| MockResult::Return(Ok(())));
+
+        assert_ok!(Redeem::execute_redeems(
+            RuntimeOrigin::signed(VAULT.account_id),
+            redeem_ids.clone(),
+            Vec::default(),
+            Vec::default()
+        ));
+        for i in 0..2 {
+            assert_emitted!(Event::ExecuteRedeem {
+                redeem_id: redeem_ids[i],
+                redeemer: USER,
+                vault_id: VAULT,
+                amount: amounts[i],
+                fee: 0,
+                transfer_fee: btc_fee.amount(),
+            });
+            assert_err!(
+                Redeem::get_open_redeem_request_from_id(&redeem_ids[i]),
+                TestError::RedeemCompleted,
+            );
+        }
+    })
+}
+
+#[test]
+fn test_execute_redeems_fails_with_invalid_batch() {
+    run_test(|| {
+        let execute_redeems = |redeem_ids: Vec<H256>| {
+            Redeem::execute_redeems(
+                RuntimeOrigin::signed(VAULT.account_id),
+                redeem_ids,
+                Vec::default(),
+                Vec::default(),
+            )
+        };
+
+        assert_err!(execute_redeems(vec![]), TestError::InvalidRedeemBatch);
+        assert_err!(
+            execute_redeems(vec![H256([0u8; 32]), H256([0u8; 32])]),
+            TestError::InvalidRedeemBatch
+        );
+        assert_err!(
+            execute_redeems(
+                (0..=crate::MAX_REDEEMS_PER_BATCH)
+                    .map(|i| H256::from_low_u64_be(i as u64))
+                    .collect()
+            ),
+            TestError::InvalidRedeemBatch
+        );
+    })
+}
+
+#[test]
+fn test_execute_redeems_fails_with_redeem_id_not_found() {
+    run_test(|| {
+        assert_err!(
+            Redeem::execute_redeems(
+                RuntimeOrigin::signed(VAULT.account_id),
+                vec![H256([0u8; 32])],
+                Vec::default(),
+                Vec::default()
+            ),
+            TestError::RedeemIdNotFound
+        );
+    })
+}
+
 #[test]
 fn test_cancel_redeem_fails_with_redeem_id_not_found() {
     run_test(|| {
@@ -673,6 +1001,164 @@ fn test_cancel_redeem_succeeds() {
     })
 }
 
+#[test]
+fn test_cancel_expired_redeem_pays_keeper_reward() {
+    run_test(|| {
+        inject_redeem_request(
+            H256([0u8; 32]),
+            RedeemRequest {
+                period: 0,
+                vault: VAULT,
+                opentime: 10,
+                fee: 0,
+                amount_btc: 10,
+                premium: 0,
+                redeemer: USER,
+                btc_address: BtcAddress::random(),
+                btc_height: 0,
+                status: RedeemRequestStatus::Pending,
+                transfer_fee_btc: Redeem::get_current_inclusion_fee(DEFAULT_WRAPPED_CURRENCY)
+                    .unwrap()
+                    .amount(),
+            },
+        );
+
+        ext::btc_relay::has_request_expired::<Test>.mock_safe(|_, _, _| MockResult::Return(Ok(true)));
+        ext::vault_registry::ban_vault::<Test>.mock_safe(|_| MockResult::Return(Ok(())));
+        Amount::<Test>::unlock_on.mock_safe(|_, _| MockResult::Return(Ok(())));
+        ext::vault_registry::transfer_funds_saturated::<Test>.mock_safe(move |from, to, amount| {
+            if to == CurrencySource::FreeBalance(CAROL) {
+                // the keeper reward is slashed from the vault, on top of the punishment fee
+                assert_eq!(from, CurrencySource::Collateral(VAULT));
+                assert_eq!(amount, &Amount::new(2, DEFAULT_COLLATERAL_CURRENCY));
+            }
+            MockResult::Return(Ok(amount.clone()))
+        });
+        ext::vault_registry::get_vault_from_id::<Test>.mock_safe(|_| {
+            MockResult::Return(Ok(vault_registry::types::Vault {
+                status: VaultStatus::Active(true),
+                ..vault_registry::types::Vault::new(VAULT)
+            }))
+        });
+        ext::vault_registry::decrease_to_be_redeemed_tokens::<Test>.mock_safe(|_, _| MockResult::Return(Ok(())));
+        ext::fee::get_keeper_reward_fee::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(Amount::new(2, DEFAULT_COLLATERAL_CURRENCY))));
+
+        assert_ok!(Redeem::cancel_expired_redeem(
+            RuntimeOrigin::signed(CAROL),
+            H256([0u8; 32])
+        ));
+        assert_emitted!(Event::CancelRedeem {
+            redeem_id: H256([0; 32]),
+            redeemer: USER,
+            vault_id: VAULT,
+            slashed_amount: 1,
+            status: RedeemRequestStatus::Retried
+        });
+        assert_emitted!(Event::KeeperReward {
+            redeem_id: H256([0; 32]),
+            keeper: CAROL,
+            amount: 2,
+        });
+    })
+}
+
+#[test]
+fn test_cancel_expired_redeem_pays_keeper_reward_from_liquidation_vault() {
+    run_test(|| {
+        inject_redeem_request(
+            H256([0u8; 32]),
+            RedeemRequest {
+                period: 0,
+                vault: VAULT,
+                opentime: 10,
+                fee: 0,
+                amount_btc: 10,
+                premium: 0,
+                redeemer: USER,
+                btc_address: BtcAddress::random(),
+                btc_height: 0,
+                status: RedeemRequestStatus::Pending,
+                transfer_fee_btc: Redeem::get_current_inclusion_fee(DEFAULT_WRAPPED_CURRENCY)
+                    .unwrap()
+                    .amount(),
+            },
+        );
+
+        ext::btc_relay::has_request_expired::<Test>.mock_safe(|_, _, _| MockResult::Return(Ok(true)));
+        Amount::<Test>::unlock_on.mock_safe(|_, _| MockResult::Return(Ok(())));
+        ext::vault_registry::get_vault_from_id::<Test>.mock_safe(|_| {
+            MockResult::Return(Ok(vault_registry::types::Vault {
+                status: VaultStatus::Liquidated,
+                ..vault_registry::types::Vault::new(VAULT)
+            }))
+        });
+        ext::vault_registry::get_liquidated_collateral::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(Amount::new(10, DEFAULT_COLLATERAL_CURRENCY))));
+        ext::vault_registry::calculate_collateral::<Test>
+            .mock_safe(|_, _, _| MockResult::Return(Ok(Amount::new(5, DEFAULT_COLLATERAL_CURRENCY))));
+        ext::vault_registry::decrease_liquidated_collateral::<Test>.mock_safe(|_, _| MockResult::Return(Ok(())));
+        ext::vault_registry::transfer_funds::<Test>.mock_safe(|from, to, _| {
+            assert_eq!(from, CurrencySource::LiquidatedCollateral(VAULT));
+            assert_eq!(to, CurrencySource::LiquidationVault(VAULT.currencies.clone()));
+            MockResult::Return(Ok(()))
+        });
+        ext::vault_registry::transfer_funds_saturated::<Test>.mock_safe(move |from, to, amount| {
+            // liquidated vaults are not punished, so the liquidation vault pays the keeper reward
+            assert_eq!(from, CurrencySource::LiquidationVault(VAULT.currencies.clone()));
+            assert_eq!(to, CurrencySource::FreeBalance(CAROL));
+            MockResult::Return(Ok(amount.clone()))
+        });
+        ext::vault_registry::decrease_to_be_redeemed_tokens::<Test>.mock_safe(|_, _| MockResult::Return(Ok(())));
+        ext::fee::get_keeper_reward_fee::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(Amount::new(2, DEFAULT_COLLATERAL_CURRENCY))));
+
+        assert_ok!(Redeem::cancel_expired_redeem(
+            RuntimeOrigin::signed(CAROL),
+            H256([0u8; 32])
+        ));
+        assert_emitted!(Event::CancelRedeem {
+            redeem_id: H256([0; 32]),
+            redeemer: USER,
+            vault_id: VAULT,
+            slashed_amount: 5,
+            status: RedeemRequestStatus::Retried
+        });
+        assert_emitted!(Event::KeeperReward {
+            redeem_id: H256([0; 32]),
+            keeper: CAROL,
+            amount: 2,
+        });
+    })
+}
+
+#[test]
+fn test_cancel_expired_redeem_fails_with_time_not_expired() {
+    run_test(|| {
+        Redeem::get_open_redeem_request_from_id.mock_safe(|_| {
+            MockResult::Return(Ok(RedeemRequest {
+                period: 0,
+                vault: VAULT,
+                opentime: 0,
+                fee: 0,
+                amount_btc: 0,
+                premium: 0,
+                redeemer: USER,
+                btc_address: BtcAddress::random(),
+                btc_height: 0,
+                status: RedeemRequestStatus::Pending,
+                transfer_fee_btc: 0,
+            }))
+        });
+        ext::btc_relay::has_request_expired::<Test>.mock_safe(|_, _, _| MockResult::Return(Ok(false)));
+
+        assert_noop!(
+            Redeem::cancel_expired_redeem(RuntimeOrigin::signed(CAROL), H256([0u8; 32])),
+            TestError::TimeNotExpired
+        );
+    })
+}
+
 #[test]
 fn test_mint_tokens_for_reimbursed_redeem() {
     // PRECONDITION: The vault MUST NOT be banned.
@@ -748,6 +1234,19 @@ fn test_set_redeem_period_only_root() {
     })
 }
 
+#[test]
+fn test_set_expired_redeem_reimburse_only_root() {
+    run_test(|| {
+        assert_noop!(
+            Redeem::set_expired_redeem_reimburse(RuntimeOrigin::signed(USER), true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Redeem::set_expired_redeem_reimburse(RuntimeOrigin::root(), true));
+        assert!(Redeem::expired_redeem_reimburse());
+        assert_emitted!(Event::ExpiredRedeemReimburseChange { reimburse: true });
+    })
+}
+
 mod spec_based_tests {
     use super::*;
 
@@ -849,8 +1348,8 @@ mod spec_based_tests {
             );
             ext::btc_relay::parse_merkle_proof::<Test>.mock_safe(|_| MockResult::Return(Ok(dummy_merkle_proof())));
             ext::btc_relay::parse_transaction::<Test>.mock_safe(|_| MockResult::Return(Ok(Transaction::default())));
-            ext::btc_relay::verify_and_validate_op_return_transaction::<Test, Balance>
-                .mock_safe(|_, _, _, _, _| MockResult::Return(Ok(())));
+            ext::btc_relay::get_and_verify_op_return_payment::<Test, Balance>
+                .mock_safe(|_, _, _, _| MockResult::Return(Ok(100)));
 
             let btc_fee = Redeem::get_current_inclusion_fee(DEFAULT_WRAPPED_CURRENCY).unwrap();
             let redeem_request = RedeemRequest {
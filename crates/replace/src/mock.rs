@@ -44,7 +44,7 @@ frame_support::construct_runtime!(
         VaultRegistry: vault_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
         Oracle: oracle::{Pallet, Call, Config<T>, Storage, Event<T>},
         Replace: replace::{Pallet, Call, Config<T>, Storage, Event<T>},
-        Fee: fee::{Pallet, Call, Config<T>, Storage},
+        Fee: fee::{Pallet, Call, Config<T>, Storage, Event<T>},
         Nomination: nomination::{Pallet, Call, Config, Storage, Event<T>},
         Currency: currency::{Pallet},
     }
@@ -201,11 +201,13 @@ impl vault_registry::Config for Test {
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
     type NominationApi = Nomination;
+    type LendTokens = ();
 }
 
 impl nomination::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type MaxUnbondingChunks = ConstU32<32>;
 }
 
 parameter_types! {
@@ -233,9 +235,15 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const TreasuryPalletId: PalletId = PalletId(*b"mod/trsy");
+}
+
 impl oracle::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OnExchangeRateChange = ();
+    type TreasuryPalletId = TreasuryPalletId;
+    type DexPriceSource = ();
     type WeightInfo = ();
 }
 
@@ -245,6 +253,7 @@ parameter_types! {
 }
 
 impl fee::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type FeePalletId = FeePalletId;
     type WeightInfo = ();
     type SignedFixedPoint = SignedFixedPoint;
@@ -255,6 +264,9 @@ impl fee::Config for Test {
     type VaultRewards = VaultRewards;
     type VaultStaking = VaultStaking;
     type OnSweep = ();
+    type DexSwap = ();
+    type Nomination = Nomination;
+    type MaxCompoundingNominators = ConstU32<32>;
     type MaxExpectedValue = MaxExpectedValue;
 }
 
@@ -2,9 +2,21 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use frame_support::dispatch::DispatchError;
 use oracle_rpc_runtime_api::BalanceWrapper;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+/// the commission charged by a vault, along with its announced change (if any)
+pub struct VaultCommission<UnsignedFixedPoint, BlockNumber> {
+    pub current: UnsignedFixedPoint,
+    pub pending: Option<UnsignedFixedPoint>,
+    pub apply_height: Option<BlockNumber>,
+}
 
 sp_api::decl_runtime_apis! {
     pub trait RewardApi<AccountId, VaultId, CurrencyId, Balance, BlockNumber, UnsignedFixedPoint> where
@@ -32,5 +44,8 @@ sp_api::decl_runtime_apis! {
 
         /// Estimate vault reward rate for a one year period
         fn estimate_vault_reward_rate(vault_id: VaultId) -> Result<UnsignedFixedPoint, DispatchError>;
+
+        /// Get the current and announced commission of a vault
+        fn get_vault_commission(vault_id: VaultId) -> VaultCommission<UnsignedFixedPoint, BlockNumber>;
     }
 }
@@ -16,7 +16,7 @@ use sp_runtime::{
 };
 use std::sync::Arc;
 
-pub use reward_rpc_runtime_api::RewardApi as RewardRuntimeApi;
+pub use reward_rpc_runtime_api::{RewardApi as RewardRuntimeApi, VaultCommission};
 
 #[rpc(client, server)]
 pub trait RewardApi<BlockHash, AccountId, VaultId, CurrencyId, Balance, BlockNumber, UnsignedFixedPoint>
@@ -74,6 +74,13 @@ where
 
     #[method(name = "reward_estimateVaultRewardRate")]
     fn estimate_vault_reward_rate(&self, vault_id: VaultId, at: Option<BlockHash>) -> RpcResult<UnsignedFixedPoint>;
+
+    #[method(name = "reward_getVaultCommission")]
+    fn get_vault_commission(
+        &self,
+        vault_id: VaultId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<VaultCommission<UnsignedFixedPoint, BlockNumber>>;
 }
 
 fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
@@ -212,4 +219,16 @@ where
             "Unable to estimate the current reward".into(),
         )
     }
+
+    fn get_vault_commission(
+        &self,
+        vault_id: VaultId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<VaultCommission<UnsignedFixedPoint, BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_vault_commission(&at, vault_id)
+            .map_err(|e| internal_err(format!("Unable to fetch the vault commission: {:?}", e)))
+    }
 }
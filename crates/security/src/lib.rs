@@ -68,6 +68,8 @@ pub mod pallet {
     pub enum Error<T> {
         /// Parachain is not running.
         ParachainNotRunning,
+        /// Liquidations are paused by the price circuit breaker.
+        LiquidationsPaused,
     }
 
     #[pallet::hooks]
@@ -198,6 +200,15 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Ensures liquidations are not paused by the price circuit breaker
+    pub fn ensure_liquidations_enabled() -> DispatchResult {
+        if <Errors<T>>::get().contains(&ErrorCode::PriceCircuitBreaker) {
+            Err(Error::<T>::LiquidationsPaused.into())
+        } else {
+            Ok(())
+        }
+    }
+
     /// Checks if the Parachain has a OracleOffline Error state
     pub fn is_parachain_error_oracle_offline() -> bool {
         Self::parachain_status() == StatusCode::Error && <Errors<T>>::get().contains(&ErrorCode::OracleOffline)
@@ -249,7 +260,7 @@ impl<T: Config> Pallet<T> {
             Self::remove_error(error_code);
         }
 
-        if Self::get_errors().is_empty() {
+        if !Self::get_errors().iter().any(ErrorCode::halts_parachain) {
             Self::set_status(StatusCode::Running);
         }
 
@@ -265,6 +276,12 @@ impl<T: Config> Pallet<T> {
         Self::recover_from_(vec![ErrorCode::OracleOffline])
     }
 
+    /// Recovers the BTC Parachain state from a `PRICE_CIRCUIT_BREAKER` error,
+    /// which re-enables liquidations.
+    pub fn recover_from_price_circuit_breaker() {
+        Self::recover_from_(vec![ErrorCode::PriceCircuitBreaker])
+    }
+
     /// Increment and return the `Nonce`.
     fn get_nonce() -> U256 {
         <Nonce<T>>::mutate(|n| {
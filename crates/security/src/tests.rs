@@ -71,6 +71,32 @@ fn test_recover_from_oracle_offline_succeeds() {
     })
 }
 
+#[test]
+fn test_recover_from_price_circuit_breaker_succeeds() {
+    run_test(|| {
+        test_recover_from_(
+            Security::recover_from_price_circuit_breaker,
+            vec![ErrorCode::PriceCircuitBreaker],
+        );
+    })
+}
+
+#[test]
+fn test_recover_from_oracle_offline_with_price_circuit_breaker_succeeds() {
+    run_test(|| {
+        Security::set_status(StatusCode::Error);
+        Security::insert_error(ErrorCode::PriceCircuitBreaker);
+        assert_noop!(Security::ensure_liquidations_enabled(), TestError::LiquidationsPaused);
+
+        // the circuit breaker only pauses liquidations, it does not halt the parachain
+        test_recover_from_(Security::recover_from_oracle_offline, vec![ErrorCode::OracleOffline]);
+        assert_noop!(Security::ensure_liquidations_enabled(), TestError::LiquidationsPaused);
+
+        Security::recover_from_price_circuit_breaker();
+        assert_ok!(Security::ensure_liquidations_enabled());
+    })
+}
+
 #[test]
 fn test_get_secure_id() {
     run_test(|| {
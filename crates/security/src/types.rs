@@ -24,6 +24,16 @@ pub enum ErrorCode {
     /// No error. Used as default value
     None = 0,
     OracleOffline = 1,
+    /// An exchange rate moved more than allowed. Liquidations are paused, but the
+    /// parachain keeps running.
+    PriceCircuitBreaker = 2,
+}
+
+impl ErrorCode {
+    /// Whether the error sets the parachain status to `Error`.
+    pub fn halts_parachain(&self) -> bool {
+        !matches!(self, ErrorCode::PriceCircuitBreaker)
+    }
 }
 
 impl Default for ErrorCode {
@@ -71,7 +71,7 @@ use sp_runtime::{
     traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, MaybeSerializeDeserialize, One, Saturating, Zero},
     ArithmeticError,
 };
-use sp_std::{cmp, convert::TryInto};
+use sp_std::{cmp, convert::TryInto, vec::Vec};
 
 pub(crate) type SignedFixedPoint<T> = <T as Config>::SignedFixedPoint;
 
@@ -267,7 +267,24 @@ pub mod pallet {
     #[pallet::storage]
     pub type Nonce<T: Config> = StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, T::Index, ValueQuery>;
 
+    /// Index of the pools (at any nonce) in which an account holds stake, so that the nominations
+    /// of an account can be looked up without iterating over all stake.
+    #[pallet::storage]
+    pub type Nominations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        (T::Index, DefaultVaultId<T>),
+        (),
+        OptionQuery,
+    >;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info] // no MaxEncodedLen for <T as frame_system::Config>::Index
     pub struct Pallet<T>(_);
 
@@ -382,6 +399,7 @@ impl<T: Config> Pallet<T> {
         checked_add_mut!(Stake<T>, nonce, (vault_id, nominator_id), &amount);
         checked_add_mut!(TotalStake<T>, nonce, vault_id, &amount);
         checked_add_mut!(TotalCurrentStake<T>, nonce, vault_id, &amount);
+        <Nominations<T>>::insert(nominator_id, (nonce, vault_id), ());
 
         <SlashTally<T>>::mutate(nonce, (vault_id, nominator_id), |slash_tally| {
             let slash_per_token = Self::slash_per_token_at_index(nonce, vault_id);
@@ -612,6 +630,9 @@ impl<T: Config> Pallet<T> {
         checked_sub_mut!(TotalStake<T>, nonce, vault_id, &to_slash);
 
         let stake = checked_sub_mut!(Stake<T>, nonce, (vault_id, nominator_id), &to_slash);
+        if stake.is_zero() {
+            <Nominations<T>>::remove(nominator_id, (nonce, vault_id));
+        }
         <SlashTally<T>>::insert(
             nonce,
             (vault_id, nominator_id),
@@ -637,7 +658,10 @@ impl<T: Config> Pallet<T> {
             return Err(Error::<T>::InsufficientFunds.into());
         }
 
-        checked_sub_mut!(Stake<T>, nonce, (vault_id, nominator_id), &amount);
+        let remaining = checked_sub_mut!(Stake<T>, nonce, (vault_id, nominator_id), &amount);
+        if remaining.is_zero() {
+            <Nominations<T>>::remove(nominator_id, (nonce, vault_id));
+        }
         checked_sub_mut!(TotalStake<T>, nonce, vault_id, &amount);
         checked_sub_mut!(TotalCurrentStake<T>, nonce, vault_id, &amount);
 
@@ -858,10 +882,14 @@ where
     }
 }
 
-pub trait StakingApi<PoolId, Index, Balance> {
+pub trait StakingApi<PoolId, Index, AccountId, Balance> {
     /// Get the newest nonce for the staking pool.
     fn nonce(pool_id: &PoolId) -> Index;
 
+    /// Get every pool (at any nonce) in which `nominator_id` holds stake, as `(nonce, pool_id, stake, slashed)`,
+    /// where `stake` is the stake after slashing and `slashed` is the amount slashed since the stake last changed.
+    fn get_nominations(nominator_id: &AccountId) -> Result<Vec<(Index, PoolId, Balance, Balance)>, DispatchError>;
+
     /// Slash an `amount` of stake from the `pool_id`.
     fn slash_stake(pool_id: &PoolId, amount: Balance) -> Result<(), DispatchError>;
 
@@ -869,7 +897,7 @@ pub trait StakingApi<PoolId, Index, Balance> {
     fn force_refund(pool_id: &PoolId) -> Result<Balance, DispatchError>;
 }
 
-impl<T, Balance> StakingApi<DefaultVaultId<T>, T::Index, Balance> for Pallet<T>
+impl<T, Balance> StakingApi<DefaultVaultId<T>, T::Index, T::AccountId, Balance> for Pallet<T>
 where
     T: Config,
     Balance: BalanceToFixedPoint<SignedFixedPoint<T>>,
@@ -879,6 +907,33 @@ where
         Pallet::<T>::nonce(vault_id)
     }
 
+    fn get_nominations(
+        nominator_id: &T::AccountId,
+    ) -> Result<Vec<(T::Index, DefaultVaultId<T>, Balance, Balance)>, DispatchError> {
+        <Nominations<T>>::iter_key_prefix(nominator_id)
+            .filter_map(|(nonce, vault_id)| {
+                let stake = Pallet::<T>::stake_at_index(nonce, &vault_id, nominator_id);
+                (!stake.is_zero()).then_some((nonce, vault_id, stake))
+            })
+            .map(|(nonce, vault_id, stake)| {
+                let current_stake = Pallet::<T>::compute_precise_stake_at_index(nonce, &vault_id, nominator_id)?;
+                let slashed = stake
+                    .checked_sub(&current_stake)
+                    .ok_or(ArithmeticError::Underflow)?
+                    .truncate_to_inner()
+                    .ok_or(Error::<T>::TryIntoIntError)?
+                    .try_into()
+                    .map_err(|_| Error::<T>::TryIntoIntError)?;
+                let current_stake = current_stake
+                    .truncate_to_inner()
+                    .ok_or(Error::<T>::TryIntoIntError)?
+                    .try_into()
+                    .map_err(|_| Error::<T>::TryIntoIntError)?;
+                Ok::<_, DispatchError>((nonce, vault_id, current_stake, slashed))
+            })
+            .collect()
+    }
+
     fn slash_stake(vault_id: &DefaultVaultId<T>, amount: Balance) -> DispatchResult {
         Pallet::<T>::slash_stake(vault_id, amount.to_fixed().ok_or(Error::<T>::TryIntoIntError)?)
     }
@@ -1143,4 +1198,79 @@ pub mod migration {
 
         Ok(())
     }
+
+    pub mod v1 {
+        use super::*;
+        use frame_support::{
+            pallet_prelude::{StorageVersion, Weight},
+            traits::OnRuntimeUpgrade,
+        };
+
+        /// Migrate the staking pallet from V0 to V1, building the index of nominations
+        /// from the existing stake.
+        pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+        impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+            #[cfg(feature = "try-runtime")]
+            fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+                assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "Can only upgrade from version 0");
+                Ok(Vec::new())
+            }
+
+            fn on_runtime_upgrade() -> Weight {
+                if StorageVersion::get::<Pallet<T>>() != 0 {
+                    return T::DbWeight::get().reads(1);
+                }
+
+                let mut weight = T::DbWeight::get().reads(1);
+                for (nonce, (vault_id, nominator_id), stake) in Stake::<T>::iter() {
+                    weight.saturating_accrue(T::DbWeight::get().reads(1));
+                    if !stake.is_zero() {
+                        Nominations::<T>::insert(nominator_id, (nonce, vault_id), ());
+                        weight.saturating_accrue(T::DbWeight::get().writes(1));
+                    }
+                }
+
+                StorageVersion::new(1).put::<Pallet<T>>();
+                weight.saturating_add(T::DbWeight::get().writes(1))
+            }
+
+            #[cfg(feature = "try-runtime")]
+            fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+                assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "Must upgrade");
+                for (nonce, (vault_id, nominator_id), stake) in Stake::<T>::iter() {
+                    assert_eq!(
+                        Nominations::<T>::contains_key(nominator_id, (nonce, vault_id)),
+                        !stake.is_zero(),
+                        "Nomination was not indexed"
+                    );
+                }
+                Ok(())
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use crate::mock::*;
+            use sp_arithmetic::FixedI128;
+
+            #[test]
+            fn should_index_existing_nominations() {
+                run_test(|| {
+                    StorageVersion::new(0).put::<Staking>();
+                    Stake::<Test>::insert(0, (VAULT, ALICE.account_id), FixedI128::from(50));
+                    Stake::<Test>::insert(1, (VAULT, ALICE.account_id), FixedI128::zero());
+
+                    MigrateToV1::<Test>::on_runtime_upgrade();
+
+                    assert_eq!(StorageVersion::get::<Staking>(), 1);
+                    assert_eq!(
+                        Nominations::<Test>::iter_key_prefix(ALICE.account_id).collect::<Vec<_>>(),
+                        vec![(0, VAULT)]
+                    );
+                })
+            }
+        }
+    }
 }
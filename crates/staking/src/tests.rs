@@ -306,3 +306,27 @@ fn should_compute_stake_after_adjustments() {
         assert_ok!(Staking::compute_stake(&VAULT, &VAULT.account_id), 1_000_000);
     })
 }
+
+#[test]
+fn should_index_nominations() {
+    use crate::StakingApi;
+
+    let get_nominations = |account_id| <Staking as StakingApi<_, _, _, Balance>>::get_nominations(&account_id).unwrap();
+
+    run_test(|| {
+        assert_ok!(Staking::deposit_stake(&VAULT, &VAULT.account_id, fixed!(100)));
+        assert_ok!(Staking::deposit_stake(&VAULT, &ALICE.account_id, fixed!(50)));
+        assert_ok!(Staking::slash_stake(&VAULT, fixed!(30)));
+        assert_eq!(get_nominations(ALICE.account_id), vec![(0, VAULT, 40, 10)]);
+
+        // the stake of the vault moves to the next nonce, nominators keep theirs in the old one
+        assert_ok!(Staking::force_refund(&VAULT));
+        assert_eq!(get_nominations(VAULT.account_id), vec![(1, VAULT, 80, 0)]);
+        assert_eq!(get_nominations(ALICE.account_id), vec![(0, VAULT, 40, 10)]);
+
+        assert_ok!(Staking::deposit_stake(&VAULT, &BOB.account_id, fixed!(50)));
+        assert_ok!(Staking::withdraw_stake(&VAULT, &BOB.account_id, fixed!(50), None));
+        assert_eq!(get_nominations(BOB.account_id), vec![]);
+        assert_eq!(crate::Nominations::<Test>::iter_prefix(BOB.account_id).count(), 0);
+    })
+}
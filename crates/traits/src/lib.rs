@@ -18,16 +18,93 @@ impl ConvertToBigUint for u128 {
 
 pub trait OracleApi<Amount, CurrencyId> {
     fn convert(amount: &Amount, to: CurrencyId) -> Result<Amount, DispatchError>;
+
+    /// Converts `amount` at the time-weighted average exchange rates over the last `window` milliseconds.
+    fn convert_twap(amount: &Amount, to: CurrencyId, window: u64) -> Result<Amount, DispatchError>;
 }
 
-pub trait NominationApi<VaultId, Amount> {
+pub trait NominationApi<VaultId, AccountId, Amount> {
     fn deposit_vault_collateral(vault_id: &VaultId, amount: &Amount) -> Result<(), DispatchError>;
+
+    /// Deposits `amount` of collateral held by `nominator_id` into the active vault `vault_id`.
+    fn deposit_nominator_collateral(
+        vault_id: &VaultId,
+        nominator_id: &AccountId,
+        amount: &Amount,
+    ) -> Result<(), DispatchError>;
+
+    /// Collateral that nominators are unbonding from `vault_id`. It keeps backing the vault until
+    /// it is released, but must not be issued against.
+    fn get_total_unbonding(vault_id: &VaultId) -> Amount;
 }
 
 pub trait OnExchangeRateChange<CurrencyId> {
     fn on_exchange_rate_change(currency_id: &CurrencyId);
 }
 
+pub trait LiquidationGuard {
+    /// Fails if liquidations are currently paused.
+    fn ensure_liquidations_enabled() -> Result<(), DispatchError>;
+}
+
+impl LiquidationGuard for () {
+    fn ensure_liquidations_enabled() -> Result<(), DispatchError> {
+        Ok(())
+    }
+}
+
+pub trait DexPriceSource<CurrencyId, Balance> {
+    /// Returns the amount of the last currency of `path` received when swapping `amount_in`
+    /// of the first currency, or `None` if there is no route with liquidity.
+    fn get_amount_out(amount_in: Balance, path: &[CurrencyId]) -> Option<Balance>;
+}
+
+impl<CurrencyId, Balance> DexPriceSource<CurrencyId, Balance> for () {
+    fn get_amount_out(_amount_in: Balance, _path: &[CurrencyId]) -> Option<Balance> {
+        None
+    }
+}
+
+pub trait DexSwap<AccountId, CurrencyId, Balance> {
+    /// Whether swaps can be made at all.
+    fn is_available() -> bool;
+
+    /// Swaps exactly `amount_in` of the first currency of `path` held by `who` into the last
+    /// currency, failing if less than `amount_out_min` would be received. Returns the amount received.
+    fn swap_exact_amount_in(
+        who: &AccountId,
+        amount_in: Balance,
+        amount_out_min: Balance,
+        path: &[CurrencyId],
+    ) -> Result<Balance, DispatchError>;
+}
+
+impl<AccountId, CurrencyId, Balance> DexSwap<AccountId, CurrencyId, Balance> for () {
+    fn is_available() -> bool {
+        false
+    }
+
+    fn swap_exact_amount_in(
+        _who: &AccountId,
+        _amount_in: Balance,
+        _amount_out_min: Balance,
+        _path: &[CurrencyId],
+    ) -> Result<Balance, DispatchError> {
+        Err(DispatchError::Other("No DEX available"))
+    }
+}
+
+pub trait LendTokenRedeemer<AccountId, Amount> {
+    /// Redeems lend tokens held by `supplier` such that they receive `underlying_amount`.
+    fn redeem_underlying(supplier: &AccountId, underlying_amount: &Amount) -> Result<(), DispatchError>;
+}
+
+impl<AccountId, Amount> LendTokenRedeemer<AccountId, Amount> for () {
+    fn redeem_underlying(_supplier: &AccountId, _underlying_amount: &Amount) -> Result<(), DispatchError> {
+        Err(DispatchError::Other("No lending market available"))
+    }
+}
+
 #[impl_trait_for_tuples::impl_for_tuples(3)]
 impl<CurrencyId> OnExchangeRateChange<CurrencyId> for Tuple {
     fn on_exchange_rate_change(currency_id: &CurrencyId) {
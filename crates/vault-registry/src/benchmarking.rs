@@ -119,6 +119,69 @@ benchmarks! {
         Oracle::<T>::_set_exchange_rate(get_collateral_currency_id::<T>(), UnsignedFixedPoint::<T>::checked_from_rational(10, 1).unwrap()).unwrap();
         VaultRegistry::<T>::liquidate_vault(&vault_id).unwrap();
     }: _(RawOrigin::Signed(vault_id.account_id), vault_id.currencies.clone())
+
+    set_basket_collateral_haircut {
+    }: _(RawOrigin::Root, get_native_currency_id::<T>(), Some(UnsignedFixedPoint::<T>::checked_from_rational(1, 10).unwrap()))
+
+    deposit_basket_collateral {
+        let vault_id = get_vault_id::<T>();
+        mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+        register_vault_with_collateral::<T>(vault_id.clone(), 100000000);
+        VaultRegistry::<T>::_set_basket_collateral_haircut(get_native_currency_id::<T>(), Some(UnsignedFixedPoint::<T>::zero())).unwrap();
+    }: _(RawOrigin::Signed(vault_id.account_id), vault_id.currencies.clone(), get_native_currency_id::<T>(), 1000u32.into())
+
+    withdraw_basket_collateral {
+        let vault_id = get_vault_id::<T>();
+        mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+        register_vault_with_collateral::<T>(vault_id.clone(), 100000000);
+        Oracle::<T>::_set_exchange_rate(get_native_currency_id::<T>(), UnsignedFixedPoint::<T>::one()).unwrap();
+        VaultRegistry::<T>::_set_basket_collateral_haircut(get_native_currency_id::<T>(), Some(UnsignedFixedPoint::<T>::zero())).unwrap();
+        VaultRegistry::<T>::try_deposit_basket_collateral(&vault_id, &Amount::new(1000u32.into(), get_native_currency_id::<T>())).unwrap();
+    }: _(RawOrigin::Signed(vault_id.account_id), vault_id.currencies.clone(), get_native_currency_id::<T>(), 1000u32.into())
+
+    exit_vault {
+        let vault_id = get_vault_id::<T>();
+        mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+        register_vault_with_collateral::<T>(vault_id.clone(), 100000000);
+        VaultRegistry::<T>::try_increase_to_be_issued_tokens(&vault_id, &wrapped(5_000)).unwrap();
+        VaultRegistry::<T>::issue_tokens(&vault_id, &wrapped(5_000)).unwrap();
+    }: _(RawOrigin::Signed(vault_id.account_id), vault_id.currencies.clone())
+
+    finalize_exit {
+        let vault_id = get_vault_id::<T>();
+        let origin: T::AccountId = account("Origin", 0, 0);
+        mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+        register_vault_with_collateral::<T>(vault_id.clone(), 100000000);
+        Oracle::<T>::_set_exchange_rate(get_native_currency_id::<T>(), UnsignedFixedPoint::<T>::one()).unwrap();
+        VaultRegistry::<T>::_set_basket_collateral_haircut(get_native_currency_id::<T>(), Some(UnsignedFixedPoint::<T>::zero())).unwrap();
+        VaultRegistry::<T>::try_deposit_basket_collateral(&vault_id, &Amount::new(1000u32.into(), get_native_currency_id::<T>())).unwrap();
+        VaultRegistry::<T>::_exit_vault(&vault_id).unwrap();
+    }: _(RawOrigin::Signed(origin), vault_id.clone())
+
+    set_top_up_allowance {
+        let vault_id = get_vault_id::<T>();
+        let funder: T::AccountId = account("Funder", 0, 0);
+        mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+        register_vault_with_collateral::<T>(vault_id.clone(), 100000000);
+    }: _(RawOrigin::Signed(funder), vault_id.clone(), 1000u32.into(), TopUpSource::FreeBalance)
+
+    top_up_vault {
+        let vault_id = get_vault_id::<T>();
+        let funder: T::AccountId = account("Funder", 0, 0);
+        let origin: T::AccountId = account("Origin", 0, 0);
+        mint_collateral::<T>(&vault_id.account_id, (1u32 << 31).into());
+        mint_collateral::<T>(&funder, (1u32 << 31).into());
+
+        register_vault_with_collateral::<T>(vault_id.clone(), 10_000);
+        VaultRegistry::<T>::try_increase_to_be_issued_tokens(&vault_id, &wrapped(5_000)).unwrap();
+        VaultRegistry::<T>::issue_tokens(&vault_id, &wrapped(5_000)).unwrap();
+        VaultRegistry::<T>::_set_top_up_allowance(&vault_id, &funder, TopUpAllowance {
+            amount: (1u32 << 30).into(),
+            source: TopUpSource::FreeBalance,
+        }).unwrap();
+
+        Oracle::<T>::_set_exchange_rate(get_collateral_currency_id::<T>(), UnsignedFixedPoint::<T>::checked_from_rational(10, 1).unwrap()).unwrap();
+    }: _(RawOrigin::Signed(origin), vault_id.clone(), funder)
 }
 
 impl_benchmark_test_suite!(
@@ -49,6 +49,13 @@ pub trait WeightInfo {
 	fn set_current_client_release() -> Weight;
 	fn set_pending_client_release() -> Weight;
 	fn recover_vault_id() -> Weight;
+	fn set_basket_collateral_haircut() -> Weight;
+	fn deposit_basket_collateral() -> Weight;
+	fn withdraw_basket_collateral() -> Weight;
+	fn exit_vault() -> Weight;
+	fn finalize_exit() -> Weight;
+	fn set_top_up_allowance() -> Weight;
+	fn top_up_vault() -> Weight;
 }
 
 /// Weights for vault_registry using the Substrate node and recommended hardware.
@@ -198,6 +205,105 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+
+	// Storage: VaultRegistry BasketCollateralHaircut (r:0 w:1)
+	fn set_basket_collateral_haircut() -> Weight {
+		Weight::from_ref_time(4_130_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateralHaircut (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateral (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn deposit_basket_collateral() -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64)) // Dummy until we rerun benchmarks
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateral (r:2 w:1)
+	// Storage: VaultRegistry BasketCollateralHaircut (r:1 w:0)
+	// Storage: VaultStaking TotalCurrentStake (r:1 w:0)
+	// Storage: Oracle Aggregate (r:2 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn withdraw_basket_collateral() -> Weight {
+		Weight::from_ref_time(80_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64)) // Dummy until we rerun benchmarks
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:1)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Fee ReplaceGriefingCollateral (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: VaultStaking Nonce (r:1 w:0)
+	// Storage: VaultStaking TotalCurrentStake (r:1 w:0)
+	// Storage: VaultStaking RewardPerToken (r:2 w:0)
+	// Storage: VaultRewards Stake (r:1 w:1)
+	// Storage: VaultRewards TotalStake (r:1 w:1)
+	// Storage: VaultCapacity Stake (r:1 w:1)
+	// Storage: VaultCapacity TotalStake (r:1 w:1)
+	fn exit_vault() -> Weight {
+		Weight::from_ref_time(120_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:1)
+	// Storage: VaultStaking Nonce (r:1 w:1)
+	// Storage: VaultStaking TotalCurrentStake (r:2 w:2)
+	// Storage: VaultStaking Stake (r:2 w:2)
+	// Storage: VaultStaking RewardPerToken (r:2 w:0)
+	// Storage: VaultRewards Stake (r:1 w:1)
+	// Storage: VaultRewards TotalStake (r:1 w:1)
+	// Storage: VaultCapacity Stake (r:1 w:1)
+	// Storage: VaultCapacity TotalStake (r:1 w:1)
+	// Storage: VaultRegistry TotalUserVaultCollateral (r:1 w:1)
+	// Storage: VaultRegistry BasketCollateral (r:2 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn finalize_exit() -> Weight {
+		Weight::from_ref_time(150_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(17 as u64))
+			.saturating_add(T::DbWeight::get().writes(14 as u64)) // Dummy until we rerun benchmarks
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry TopUpAllowances (r:0 w:1)
+	fn set_top_up_allowance() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: VaultRegistry TopUpAllowances (r:1 w:1)
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateral (r:1 w:0)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: VaultRegistry TotalUserVaultCollateral (r:1 w:1)
+	// Storage: VaultRegistry SystemCollateralCeiling (r:1 w:0)
+	// Storage: VaultStaking Nonce (r:1 w:0)
+	// Storage: VaultStaking Stake (r:1 w:1)
+	// Storage: VaultStaking TotalStake (r:1 w:1)
+	// Storage: VaultStaking TotalCurrentStake (r:1 w:1)
+	// Storage: VaultStaking SlashPerToken (r:1 w:0)
+	// Storage: VaultStaking SlashTally (r:1 w:1)
+	// Storage: VaultRewards Stake (r:1 w:1)
+	// Storage: VaultRewards TotalStake (r:1 w:1)
+	// Storage: VaultCapacity Stake (r:1 w:1)
+	// Storage: VaultCapacity TotalStake (r:1 w:1)
+	// Storage: Nomination NominationEnabled (r:1 w:0)
+	// Storage: Nomination Vaults (r:1 w:0)
+	// Storage: Nomination NominationLimit (r:1 w:0)
+	fn top_up_vault() -> Weight {
+		Weight::from_ref_time(180_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(22 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64)) // Dummy until we rerun benchmarks
+	}
 }
 
 // For backwards compatibility and tests
@@ -346,5 +452,104 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as u64))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+
+	// Storage: VaultRegistry BasketCollateralHaircut (r:0 w:1)
+	fn set_basket_collateral_haircut() -> Weight {
+		Weight::from_ref_time(4_130_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateralHaircut (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateral (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn deposit_basket_collateral() -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64)) // dummy until we rerun benchmarks
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateral (r:2 w:1)
+	// Storage: VaultRegistry BasketCollateralHaircut (r:1 w:0)
+	// Storage: VaultStaking TotalCurrentStake (r:1 w:0)
+	// Storage: Oracle Aggregate (r:2 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn withdraw_basket_collateral() -> Weight {
+		Weight::from_ref_time(80_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64)) // dummy until we rerun benchmarks
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:1)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Fee ReplaceGriefingCollateral (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: VaultStaking Nonce (r:1 w:0)
+	// Storage: VaultStaking TotalCurrentStake (r:1 w:0)
+	// Storage: VaultStaking RewardPerToken (r:2 w:0)
+	// Storage: VaultRewards Stake (r:1 w:1)
+	// Storage: VaultRewards TotalStake (r:1 w:1)
+	// Storage: VaultCapacity Stake (r:1 w:1)
+	// Storage: VaultCapacity TotalStake (r:1 w:1)
+	fn exit_vault() -> Weight {
+		Weight::from_ref_time(120_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(13 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:1)
+	// Storage: VaultStaking Nonce (r:1 w:1)
+	// Storage: VaultStaking TotalCurrentStake (r:2 w:2)
+	// Storage: VaultStaking Stake (r:2 w:2)
+	// Storage: VaultStaking RewardPerToken (r:2 w:0)
+	// Storage: VaultRewards Stake (r:1 w:1)
+	// Storage: VaultRewards TotalStake (r:1 w:1)
+	// Storage: VaultCapacity Stake (r:1 w:1)
+	// Storage: VaultCapacity TotalStake (r:1 w:1)
+	// Storage: VaultRegistry TotalUserVaultCollateral (r:1 w:1)
+	// Storage: VaultRegistry BasketCollateral (r:2 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn finalize_exit() -> Weight {
+		Weight::from_ref_time(150_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(17 as u64))
+			.saturating_add(RocksDbWeight::get().writes(14 as u64)) // dummy until we rerun benchmarks
+	}
+
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry TopUpAllowances (r:0 w:1)
+	fn set_top_up_allowance() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+
+	// Storage: VaultRegistry TopUpAllowances (r:1 w:1)
+	// Storage: VaultRegistry Vaults (r:1 w:0)
+	// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	// Storage: Oracle Aggregate (r:1 w:0)
+	// Storage: VaultRegistry BasketCollateral (r:1 w:0)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: VaultRegistry TotalUserVaultCollateral (r:1 w:1)
+	// Storage: VaultRegistry SystemCollateralCeiling (r:1 w:0)
+	// Storage: VaultStaking Nonce (r:1 w:0)
+	// Storage: VaultStaking Stake (r:1 w:1)
+	// Storage: VaultStaking TotalStake (r:1 w:1)
+	// Storage: VaultStaking TotalCurrentStake (r:1 w:1)
+	// Storage: VaultStaking SlashPerToken (r:1 w:0)
+	// Storage: VaultStaking SlashTally (r:1 w:1)
+	// Storage: VaultRewards Stake (r:1 w:1)
+	// Storage: VaultRewards TotalStake (r:1 w:1)
+	// Storage: VaultCapacity Stake (r:1 w:1)
+	// Storage: VaultCapacity TotalStake (r:1 w:1)
+	// Storage: Nomination NominationEnabled (r:1 w:0)
+	// Storage: Nomination Vaults (r:1 w:0)
+	// Storage: Nomination NominationLimit (r:1 w:0)
+	fn top_up_vault() -> Weight {
+		Weight::from_ref_time(180_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(22 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64)) // dummy until we rerun benchmarks
+	}
 }
 
@@ -17,9 +17,15 @@ pub(crate) mod currency {
 
 #[cfg_attr(test, mockable)]
 pub(crate) mod security {
+    use frame_support::dispatch::DispatchResult;
+
     pub fn active_block_number<T: crate::Config>() -> T::BlockNumber {
         <security::Pallet<T>>::active_block_number()
     }
+
+    pub fn ensure_liquidations_enabled<T: crate::Config>() -> DispatchResult {
+        <security::Pallet<T>>::ensure_liquidations_enabled()
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -105,9 +111,14 @@ pub(crate) mod capacity {
 #[cfg_attr(test, mockable)]
 pub(crate) mod fee {
     use crate::DefaultVaultId;
-    use frame_support::dispatch::DispatchResult;
+    use currency::Amount;
+    use frame_support::dispatch::{DispatchError, DispatchResult};
 
     pub fn distribute_all_vault_rewards<T: crate::Config>(vault_id: &DefaultVaultId<T>) -> DispatchResult {
         <fee::Pallet<T>>::distribute_all_vault_rewards(vault_id)
     }
+
+    pub fn get_replace_griefing_collateral<T: crate::Config>(amount: &Amount<T>) -> Result<Amount<T>, DispatchError> {
+        <fee::Pallet<T>>::get_replace_griefing_collateral(amount)
+    }
 }
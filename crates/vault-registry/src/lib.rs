@@ -34,14 +34,15 @@ use mocktopus::macros::mockable;
 use primitives::VaultCurrencyPair;
 
 use crate::types::{
-    BalanceOf, BtcAddress, CurrencyId, DefaultSystemVault, RichSystemVault, RichVault, SignedInner, UnsignedFixedPoint,
-    Version,
+    BalanceOf, BtcAddress, CurrencyId, DefaultSystemVault, DefaultTopUpAllowance, RichSystemVault, RichVault,
+    SignedInner, UnsignedFixedPoint, Version,
 };
 
 use crate::types::DefaultVaultCurrencyPair;
 #[doc(inline)]
 pub use crate::types::{
-    BtcPublicKey, CurrencySource, DefaultVault, DefaultVaultId, SystemVault, Vault, VaultId, VaultStatus,
+    BtcPublicKey, CurrencySource, DefaultVault, DefaultVaultId, SystemVault, TopUpAllowance, TopUpSource, Vault,
+    VaultId, VaultStatus,
 };
 use bitcoin::types::Value;
 use codec::FullCodec;
@@ -49,9 +50,11 @@ pub use currency::Amount;
 use currency::Rounding;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
-    ensure,
+    ensure, storage,
     traits::Get,
-    transactional, PalletId,
+    transactional,
+    weights::Weight,
+    PalletId,
 };
 use frame_system::{
     ensure_signed,
@@ -71,11 +74,20 @@ use sp_std::{
     fmt::Debug,
     vec::Vec,
 };
-use traits::NominationApi;
+use traits::{LendTokenRedeemer, NominationApi};
 
 // value taken from https://github.com/substrate-developer-hub/recipes/blob/master/pallets/ocw-demo/src/lib.rs
 pub const UNSIGNED_TXS_PRIORITY: u64 = 100;
 
+/// Maximum number of vaults that a single issue or redeem amount is split across by automatic vault selection
+pub const MAX_VAULTS_PER_REQUEST: u32 = 5;
+
+/// Maximum number of candidate vaults that can be passed to automatic vault selection
+pub const MAX_CANDIDATE_VAULTS: u32 = 20;
+
+/// Maximum number of vault exits that are completed per block in `on_idle`
+pub const MAX_EXITS_PER_BLOCK: u32 = 10;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -131,7 +143,10 @@ pub mod pallet {
         #[pallet::constant]
         type GetGriefingCollateralCurrencyId: Get<CurrencyId<Self>>;
 
-        type NominationApi: NominationApi<DefaultVaultId<Self>, Amount<Self>>;
+        type NominationApi: NominationApi<DefaultVaultId<Self>, Self::AccountId, Amount<Self>>;
+
+        /// Redeems lend tokens when topping up vault collateral from a lend token position.
+        type LendTokens: LendTokenRedeemer<Self::AccountId, Amount<Self>>;
     }
 
     #[pallet::hooks]
@@ -141,6 +156,10 @@ pub mod pallet {
             Self::_offchain_worker();
         }
 
+        fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::finalize_pending_exits(remaining_weight)
+        }
+
         fn on_runtime_upgrade() -> frame_support::weights::Weight {
             crate::types::v1::migrate_v1_to_v6::<T>()
         }
@@ -184,7 +203,7 @@ pub mod pallet {
         ///
         /// # Errors
         /// * `InsufficientVaultCollateralAmount` - if the collateral is below the minimum threshold
-        /// * `VaultAlreadyRegistered` - if a vault is already registered for the origin account
+        /// * `VaultAlreadyRegistered` - if a vault that has not exited is registered for the origin account
         /// * `InsufficientCollateralAvailable` - if the vault does not own enough collateral
         #[pallet::call_index(0)]
         #[pallet::weight(<T as Config>::WeightInfo::register_vault())]
@@ -240,6 +259,7 @@ pub mod pallet {
             let account_id = ensure_signed(origin)?;
             let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
             let mut vault = Self::get_active_rich_vault_from_id(&vault_id)?;
+            ensure!(!vault.data.is_exiting(), Error::<T>::VaultExiting);
             vault.set_accept_new_issues(accept_new_issues)?;
             PoolManager::<T>::on_vault_settings_change(&vault_id)?;
             Ok(().into())
@@ -276,6 +296,7 @@ pub mod pallet {
             vault_id: DefaultVaultId<T>,
         ) -> DispatchResultWithPostInfo {
             log::info!("Vault reported");
+            ext::security::ensure_liquidations_enabled::<T>()?;
             let vault = Self::get_vault_from_id(&vault_id)?;
             let liquidation_threshold =
                 Self::liquidation_collateral_threshold(&vault_id.currencies).ok_or(Error::<T>::ThresholdNotSet)?;
@@ -399,6 +420,142 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Changes the haircut applied to a currency when it is held as basket collateral
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `currency_id` - the basket currency to configure
+        /// * `haircut` - the share of the oracle value that is discounted, or None to stop accepting the currency
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_basket_collateral_haircut())]
+        #[transactional]
+        pub fn set_basket_collateral_haircut(
+            origin: OriginFor<T>,
+            currency_id: CurrencyId<T>,
+            haircut: Option<UnsignedFixedPoint<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::_set_basket_collateral_haircut(currency_id, haircut)
+        }
+
+        /// Locks an amount of a secondary currency as additional collateral of the vault. The
+        /// currency must have a haircut configured and differ from the vault's collateral currency.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the currency pair of the vault
+        /// * `currency_id` - the basket currency to deposit
+        /// * `amount` - the amount to deposit
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::deposit_basket_collateral())]
+        #[transactional]
+        pub fn deposit_basket_collateral(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            currency_id: CurrencyId<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::try_deposit_basket_collateral(&vault_id, &Amount::new(amount, currency_id))?;
+            Ok(().into())
+        }
+
+        /// Unlocks an amount of basket collateral, provided the vault stays above its secure threshold.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the currency pair of the vault
+        /// * `currency_id` - the basket currency to withdraw
+        /// * `amount` - the amount to withdraw
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_basket_collateral())]
+        #[transactional]
+        pub fn withdraw_basket_collateral(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            currency_id: CurrencyId<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::try_withdraw_basket_collateral(&vault_id, &Amount::new(amount, currency_id))?;
+            Ok(().into())
+        }
+
+        /// Starts winding down the vault. The vault stops accepting new issue requests, offers its
+        /// issued tokens for replacement and is preferred when selecting vaults for redeem. Once it
+        /// no longer backs any tokens, its collateral is released in `on_idle` or through `finalize_exit`.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the currency pair of the vault
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::exit_vault())]
+        #[transactional]
+        pub fn exit_vault(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::_exit_vault(&vault_id)?;
+            Ok(().into())
+        }
+
+        /// Allows anyone to move up to `amount` of the caller's collateral into the vault whenever
+        /// it falls below its secure threshold. An amount of zero removes the allowance.
+        ///
+        /// # Arguments
+        /// * `vault_id` - the vault that may be topped up
+        /// * `amount` - the maximum amount of collateral to move into the vault
+        /// * `source` - whether the collateral is taken from free balance or from redeemed lend tokens
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_top_up_allowance())]
+        #[transactional]
+        pub fn set_top_up_allowance(
+            origin: OriginFor<T>,
+            vault_id: DefaultVaultId<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+            source: TopUpSource,
+        ) -> DispatchResultWithPostInfo {
+            let funder = ensure_signed(origin)?;
+            Self::_set_top_up_allowance(&vault_id, &funder, TopUpAllowance { amount, source })?;
+            Ok(().into())
+        }
+
+        /// Moves collateral of `funder` into a vault that is below its secure threshold, up to the
+        /// amount needed to restore it and bounded by the funder's allowance. Callable by anyone.
+        ///
+        /// # Arguments
+        /// * `vault_id` - the vault to top up
+        /// * `funder` - the account that granted the allowance
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::top_up_vault())]
+        #[transactional]
+        pub fn top_up_vault(
+            origin: OriginFor<T>,
+            vault_id: DefaultVaultId<T>,
+            funder: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::try_top_up_vault(&vault_id, &funder)?;
+            Ok(().into())
+        }
+
+        /// Completes the exit of a vault that no longer backs any tokens: nominators are refunded,
+        /// and the vault's own collateral as well as its basket collateral are released to its
+        /// free balance. Callable by anyone. The exit is otherwise completed in `on_idle` once the
+        /// vault's last tokens are redeemed or replaced.
+        ///
+        /// # Arguments
+        /// * `vault_id` - the exiting vault
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::finalize_exit())]
+        #[transactional]
+        pub fn finalize_exit(origin: OriginFor<T>, vault_id: DefaultVaultId<T>) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::_finalize_exit(&vault_id)?;
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -510,6 +667,54 @@ pub mod pallet {
             vault_id: DefaultVaultId<T>,
             banned_until: T::BlockNumber,
         },
+        DepositBasketCollateral {
+            vault_id: DefaultVaultId<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+            total: BalanceOf<T>,
+        },
+        WithdrawBasketCollateral {
+            vault_id: DefaultVaultId<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+            total: BalanceOf<T>,
+        },
+        SeizeBasketCollateral {
+            vault_id: DefaultVaultId<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+        },
+        RedeemBasketCollateralLiquidation {
+            redeemer_id: T::AccountId,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+        },
+        VaultExitStarted {
+            vault_id: DefaultVaultId<T>,
+            offered_tokens: BalanceOf<T>,
+        },
+        VaultExitProgress {
+            vault_id: DefaultVaultId<T>,
+            issued_tokens: BalanceOf<T>,
+            to_be_issued_tokens: BalanceOf<T>,
+            to_be_redeemed_tokens: BalanceOf<T>,
+            to_be_replaced_tokens: BalanceOf<T>,
+        },
+        VaultExited {
+            vault_id: DefaultVaultId<T>,
+            released_collateral: BalanceOf<T>,
+        },
+        SetTopUpAllowance {
+            vault_id: DefaultVaultId<T>,
+            funder: T::AccountId,
+            amount: BalanceOf<T>,
+            source: TopUpSource,
+        },
+        TopUpVault {
+            vault_id: DefaultVaultId<T>,
+            funder: T::AccountId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -572,6 +777,23 @@ pub mod pallet {
 
         // Minimum collateral was not found for the given currency
         MinimumCollateralNotSet,
+
+        /// The currency is not accepted as basket collateral for this vault.
+        BasketCurrencyNotAccepted,
+        /// Basket collateral haircuts must be below 100%.
+        InvalidHaircut,
+        /// Action not allowed on a vault that is winding down.
+        VaultExiting,
+        /// The funder has not granted a top-up allowance to the vault.
+        NoTopUpAllowance,
+        /// Attempted to top up a vault that is not below its secure threshold.
+        VaultNotBelowSecureThreshold,
+        /// Attempted to finalize the exit of a vault that is not exiting.
+        VaultNotExiting,
+        /// The exiting vault still backs, redeems or replaces tokens.
+        VaultExitPending,
+        /// Vault is no longer usable as it completed its exit.
+        VaultHasExited,
     }
 
     /// The minimum collateral (e.g. DOT/KSM) a Vault needs to provide to register.
@@ -638,6 +860,68 @@ pub mod pallet {
     pub(super) type TotalUserVaultCollateral<T: Config> =
         StorageMap<_, Blake2_128Concat, DefaultVaultCurrencyPair<T>, BalanceOf<T>, ValueQuery>;
 
+    /// Currencies that vaults may lock as basket collateral next to their primary collateral, mapped
+    /// to the haircut deducted from their oracle value. Deposits in a currency that is removed from
+    /// this map no longer count towards the vault's collateralization.
+    #[pallet::storage]
+    #[pallet::getter(fn basket_collateral_haircut)]
+    pub(super) type BasketCollateralHaircut<T: Config> =
+        StorageMap<_, Blake2_128Concat, CurrencyId<T>, UnsignedFixedPoint<T>, OptionQuery>;
+
+    /// Basket collateral locked by each vault, per currency.
+    #[pallet::storage]
+    pub(super) type BasketCollateral<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultId<T>,
+        Blake2_128Concat,
+        CurrencyId<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Basket collateral of liquidated vaults that backs their to-be-redeemed tokens, per currency. Like the
+    /// liquidated collateral, it stays locked by the vault until the redeem requests are executed or cancelled.
+    #[pallet::storage]
+    pub(super) type LiquidatedBasketCollateral<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultId<T>,
+        Blake2_128Concat,
+        CurrencyId<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Basket collateral seized from liquidated vaults, held by the liquidation vault of the currency pair.
+    #[pallet::storage]
+    pub(super) type LiquidationVaultBasket<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultCurrencyPair<T>,
+        Blake2_128Concat,
+        CurrencyId<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Collateral that funders allow to be moved into a vault once it falls below its secure
+    /// threshold, keyed by vault and funder.
+    #[pallet::storage]
+    pub(super) type TopUpAllowances<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultId<T>,
+        Blake2_128Concat,
+        T::AccountId,
+        DefaultTopUpAllowance<T>,
+        OptionQuery,
+    >;
+
+    /// Exiting vaults that no longer back any tokens, whose exit is completed in `on_idle`.
+    #[pallet::storage]
+    pub(super) type PendingExits<T: Config> = StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, (), OptionQuery>;
+
     #[pallet::type_value]
     pub(super) fn DefaultForStorageVersion() -> Version {
         Version::V6
@@ -700,6 +984,10 @@ pub mod pallet {
 #[cfg_attr(test, mockable)]
 impl<T: Config> Pallet<T> {
     fn _offchain_worker() {
+        if ext::security::ensure_liquidations_enabled::<T>().is_err() {
+            log::info!("Liquidations are paused; not reporting vaults");
+            return;
+        }
         for vault in Self::undercollateralized_vaults() {
             log::info!("Reporting vault {:?}", vault);
             let call = Call::report_undercollateralized_vault { vault_id: vault };
@@ -745,7 +1033,11 @@ impl<T: Config> Pallet<T> {
             amount.ge(&Self::get_minimum_collateral_vault(collateral_currency))?,
             Error::<T>::InsufficientVaultCollateralAmount
         );
-        ensure!(!Self::vault_exists(&vault_id), Error::<T>::VaultAlreadyRegistered);
+        // a vault that completed its exit may register again, which starts it afresh
+        ensure!(
+            Self::get_vault_from_id(&vault_id).map_or(true, |vault| vault.status == VaultStatus::Exited),
+            Error::<T>::VaultAlreadyRegistered
+        );
 
         let vault = Vault::new(vault_id.clone());
         Self::insert_vault(&vault_id, vault);
@@ -806,8 +1098,9 @@ impl<T: Config> Pallet<T> {
     pub fn get_active_vault_from_id(vault_id: &DefaultVaultId<T>) -> Result<DefaultVault<T>, DispatchError> {
         let vault = Self::get_vault_from_id(vault_id)?;
         match vault.status {
-            VaultStatus::Active(_) => Ok(vault),
+            VaultStatus::Active(_) | VaultStatus::Exiting => Ok(vault),
             VaultStatus::Liquidated => Err(Error::<T>::VaultLiquidated.into()),
+            VaultStatus::Exited => Err(Error::<T>::VaultHasExited.into()),
         }
     }
 
@@ -820,6 +1113,80 @@ impl<T: Config> Pallet<T> {
         T::NominationApi::deposit_vault_collateral(&vault_id, &amount)
     }
 
+    /// Set the collateral that `funder` allows to be moved into the vault, removing the allowance
+    /// if its amount is zero
+    ///
+    /// # Arguments
+    /// * `vault_id` - the id of the vault
+    /// * `funder` - the account providing the collateral
+    /// * `allowance` - the maximum amount of collateral and its source
+    pub fn _set_top_up_allowance(
+        vault_id: &DefaultVaultId<T>,
+        funder: &T::AccountId,
+        allowance: DefaultTopUpAllowance<T>,
+    ) -> DispatchResult {
+        if allowance.amount.is_zero() {
+            TopUpAllowances::<T>::remove(vault_id, funder);
+        } else {
+            let _ = Self::get_active_vault_from_id(vault_id)?;
+            TopUpAllowances::<T>::insert(vault_id, funder, allowance.clone());
+        }
+        Self::deposit_event(Event::<T>::SetTopUpAllowance {
+            vault_id: vault_id.clone(),
+            funder: funder.clone(),
+            amount: allowance.amount,
+            source: allowance.source,
+        });
+        Ok(())
+    }
+
+    /// Move collateral of `funder` into a vault that is below its secure threshold, up to the
+    /// amount required to restore the threshold and bounded by the remaining allowance. The
+    /// collateral is nominated to the vault, so it remains owned by the `funder`
+    ///
+    /// # Arguments
+    /// * `vault_id` - the id of the vault
+    /// * `funder` - the account providing the collateral
+    pub fn try_top_up_vault(vault_id: &DefaultVaultId<T>, funder: &T::AccountId) -> Result<Amount<T>, DispatchError> {
+        let mut allowance = TopUpAllowances::<T>::get(vault_id, funder).ok_or(Error::<T>::NoTopUpAllowance)?;
+
+        // measure against the issued tokens, like `is_vault_below_secure_threshold`
+        let vault = Self::get_active_rich_vault_from_id(vault_id)?;
+        let threshold = vault.get_secure_threshold()?;
+        let collateral = Self::get_effective_collateral(vault_id)?;
+        ensure!(
+            Self::is_collateral_below_threshold(&collateral, &vault.issued_tokens(), threshold)?,
+            Error::<T>::VaultNotBelowSecureThreshold
+        );
+
+        let missing = Self::get_required_collateral_for_wrapped_with_threshold(
+            &vault.issued_tokens(),
+            threshold,
+            vault_id.collateral_currency(),
+        )?
+        .saturating_sub(&collateral)?;
+        let amount = missing.min(&Amount::new(allowance.amount, vault_id.collateral_currency()))?;
+
+        if allowance.source == TopUpSource::LendTokens {
+            T::LendTokens::redeem_underlying(funder, &amount)?;
+        }
+        T::NominationApi::deposit_nominator_collateral(vault_id, funder, &amount)?;
+
+        allowance.amount = allowance.amount.saturating_sub(amount.amount());
+        if allowance.amount.is_zero() {
+            TopUpAllowances::<T>::remove(vault_id, funder);
+        } else {
+            TopUpAllowances::<T>::insert(vault_id, funder, allowance);
+        }
+
+        Self::deposit_event(Event::<T>::TopUpVault {
+            vault_id: vault_id.clone(),
+            funder: funder.clone(),
+            amount: amount.amount(),
+        });
+        Ok(amount)
+    }
+
     /// Withdraw an `amount` of collateral without checking collateralization
     ///
     /// # Arguments
@@ -848,12 +1215,254 @@ impl<T: Config> Pallet<T> {
             Err(x) if x == ArithmeticError::Underflow.into() => return Ok(false),
             Err(x) => return Err(x),
         };
+        let new_collateral = new_collateral.checked_add(&Self::get_basket_collateral_value(vault_id)?)?;
 
         let is_below_threshold =
             Pallet::<T>::is_collateral_below_vault_secure_threshold(&new_collateral, &vault.backed_tokens()?, &vault)?;
         Ok(!is_below_threshold)
     }
 
+    /// Lock an `amount` of a basket currency as additional collateral of the vault
+    ///
+    /// # Arguments
+    /// * `vault_id` - the id of the vault
+    /// * `amount` - the amount of basket collateral
+    pub fn try_deposit_basket_collateral(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        let _ = Self::get_active_vault_from_id(vault_id)?;
+        let currency_id = amount.currency();
+        ensure!(
+            currency_id != vault_id.collateral_currency() && BasketCollateralHaircut::<T>::contains_key(currency_id),
+            Error::<T>::BasketCurrencyNotAccepted
+        );
+
+        amount.lock_on(&vault_id.account_id)?;
+        let total = BasketCollateral::<T>::try_mutate(vault_id, currency_id, |balance| {
+            *balance = balance.checked_add(&amount.amount()).ok_or(ArithmeticError::Overflow)?;
+            Ok::<_, DispatchError>(*balance)
+        })?;
+
+        Self::deposit_event(Event::<T>::DepositBasketCollateral {
+            vault_id: vault_id.clone(),
+            currency_id,
+            amount: amount.amount(),
+            total,
+        });
+        Ok(())
+    }
+
+    /// Unlock an `amount` of basket collateral, if the vault remains above its secure threshold. The
+    /// basket collateral of liquidated vaults that backs their to-be-redeemed tokens can not be withdrawn.
+    ///
+    /// # Arguments
+    /// * `vault_id` - the id of the vault
+    /// * `amount` - the amount of basket collateral
+    pub fn try_withdraw_basket_collateral(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        let vault = Self::get_rich_vault_from_id(vault_id)?;
+        let currency_id = amount.currency();
+
+        let total = BasketCollateral::<T>::get(vault_id, currency_id)
+            .checked_sub(&amount.amount())
+            .ok_or(Error::<T>::InsufficientCollateral)?;
+        if total.is_zero() {
+            BasketCollateral::<T>::remove(vault_id, currency_id);
+        } else {
+            BasketCollateral::<T>::insert(vault_id, currency_id, total);
+        }
+
+        ensure!(
+            !Self::is_collateral_below_vault_secure_threshold(
+                &Self::get_effective_collateral(vault_id)?,
+                &vault.backed_tokens()?,
+                &vault
+            )?,
+            Error::<T>::InsufficientCollateral
+        );
+        amount.unlock_on(&vault_id.account_id)?;
+
+        Self::deposit_event(Event::<T>::WithdrawBasketCollateral {
+            vault_id: vault_id.clone(),
+            currency_id,
+            amount: amount.amount(),
+            total,
+        });
+        Ok(())
+    }
+
+    /// Get the amount of a basket currency locked by the vault
+    pub fn get_basket_collateral(vault_id: &DefaultVaultId<T>, currency_id: CurrencyId<T>) -> Amount<T> {
+        Amount::new(BasketCollateral::<T>::get(vault_id, currency_id), currency_id)
+    }
+
+    /// Get the value of the vault's basket collateral in its collateral currency, after haircuts.
+    /// Basket currencies without an exchange rate are valued at zero, while any other conversion
+    /// error is returned.
+    pub fn get_basket_collateral_value(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
+        let collateral_currency = vault_id.collateral_currency();
+        BasketCollateral::<T>::iter_prefix(vault_id).try_fold(
+            Amount::zero(collateral_currency),
+            |total, (currency_id, balance)| {
+                let haircut = match BasketCollateralHaircut::<T>::get(currency_id) {
+                    Some(haircut) => haircut,
+                    None => return Ok(total),
+                };
+                let value = match Amount::<T>::new(balance, currency_id).convert_to(collateral_currency) {
+                    Ok(value) => value,
+                    Err(x) if x == oracle::Error::<T>::MissingExchangeRate.into() => return Ok(total),
+                    Err(x) => return Err(x),
+                };
+                let value = value.checked_mul(&UnsignedFixedPoint::<T>::one().saturating_sub(haircut))?;
+                total.checked_add(&value)
+            },
+        )
+    }
+
+    /// Get the backing collateral of the vault plus the value of its basket collateral
+    pub fn get_effective_collateral(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
+        Self::get_backing_collateral(vault_id)?.checked_add(&Self::get_basket_collateral_value(vault_id)?)
+    }
+
+    /// Move the share `seized / total` of every basket currency of the vault to the liquidation vault of
+    /// its currency pair, and set aside the share `to_be_redeemed / total` as liquidated basket collateral
+    pub(crate) fn seize_basket_collateral(
+        vault_id: &DefaultVaultId<T>,
+        seized: &Amount<T>,
+        to_be_redeemed: &Amount<T>,
+        total: &Amount<T>,
+    ) -> DispatchResult {
+        if total.is_zero() {
+            return Ok(());
+        }
+        for (currency_id, balance) in BasketCollateral::<T>::iter_prefix(vault_id).collect::<Vec<_>>() {
+            let balance = Amount::new(balance, currency_id);
+            let seized_amount = Self::calculate_collateral(&balance, seized, total)?;
+            let liquidated_amount = Self::calculate_collateral(&balance, to_be_redeemed, total)?;
+            let remaining = balance
+                .saturating_sub(&seized_amount)?
+                .saturating_sub(&liquidated_amount)?;
+            BasketCollateral::<T>::insert(vault_id, currency_id, remaining.amount());
+
+            if !liquidated_amount.is_zero() {
+                LiquidatedBasketCollateral::<T>::try_mutate(vault_id, currency_id, |total| {
+                    *total = total
+                        .checked_add(&liquidated_amount.amount())
+                        .ok_or(ArithmeticError::Overflow)?;
+                    Ok::<_, DispatchError>(())
+                })?;
+            }
+
+            if !seized_amount.is_zero() {
+                Self::transfer_to_liquidation_vault_basket(vault_id, &seized_amount)?;
+                Self::deposit_event(Event::<T>::SeizeBasketCollateral {
+                    vault_id: vault_id.clone(),
+                    currency_id,
+                    amount: seized_amount.amount(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Move an `amount` of basket collateral locked by the vault to the liquidation vault of its currency pair
+    fn transfer_to_liquidation_vault_basket(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        let liquidation_vault_account_id = Self::liquidation_vault_account_id();
+        amount.unlock_on(&vault_id.account_id)?;
+        amount.transfer(&vault_id.account_id, &liquidation_vault_account_id)?;
+        amount.lock_on(&liquidation_vault_account_id)?;
+        LiquidationVaultBasket::<T>::try_mutate(&vault_id.currencies, amount.currency(), |total| {
+            *total = total.checked_add(&amount.amount()).ok_or(ArithmeticError::Overflow)?;
+            Ok::<_, DispatchError>(())
+        })
+    }
+
+    /// Take the share `tokens / to_be_redeemed_tokens` of every currency of the vault's liquidated basket
+    /// collateral. The returned amounts remain locked by the vault.
+    fn take_liquidated_basket_collateral(
+        vault_id: &DefaultVaultId<T>,
+        tokens: &Amount<T>,
+        to_be_redeemed_tokens: &Amount<T>,
+    ) -> Result<Vec<Amount<T>>, DispatchError> {
+        let mut taken = Vec::new();
+        for (currency_id, balance) in LiquidatedBasketCollateral::<T>::iter_prefix(vault_id).collect::<Vec<_>>() {
+            let amount = Self::calculate_collateral(&Amount::new(balance, currency_id), tokens, to_be_redeemed_tokens)?;
+            if amount.is_zero() {
+                continue;
+            }
+            let remaining = balance.saturating_sub(amount.amount());
+            if remaining.is_zero() {
+                LiquidatedBasketCollateral::<T>::remove(vault_id, currency_id);
+            } else {
+                LiquidatedBasketCollateral::<T>::insert(vault_id, currency_id, remaining);
+            }
+            taken.push(amount);
+        }
+        Ok(taken)
+    }
+
+    /// Confiscate the share `tokens / to_be_redeemed_tokens` of the liquidated basket collateral of the
+    /// vault, when redeem requests against it are cancelled. The collateral is transferred to the
+    /// redeemer if they are reimbursed, and to the liquidation vault otherwise.
+    ///
+    /// # Arguments
+    /// * `vault_id` - the id of the liquidated vault
+    /// * `tokens` - the amount of cancelled tokens
+    /// * `to_be_redeemed_tokens` - the to-be-redeemed tokens of the vault prior to the cancellation
+    /// * `reimbursed_redeemer` - the redeemer, if they are reimbursed
+    pub fn confiscate_liquidated_basket_collateral(
+        vault_id: &DefaultVaultId<T>,
+        tokens: &Amount<T>,
+        to_be_redeemed_tokens: &Amount<T>,
+        reimbursed_redeemer: Option<&T::AccountId>,
+    ) -> DispatchResult {
+        for amount in Self::take_liquidated_basket_collateral(vault_id, tokens, to_be_redeemed_tokens)? {
+            match reimbursed_redeemer {
+                Some(redeemer_id) => {
+                    amount.unlock_on(&vault_id.account_id)?;
+                    amount.transfer(&vault_id.account_id, redeemer_id)?;
+                }
+                None => Self::transfer_to_liquidation_vault_basket(vault_id, &amount)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the amount of a basket currency that the liquidated vault keeps for its to-be-redeemed tokens
+    pub fn get_liquidated_basket_collateral(vault_id: &DefaultVaultId<T>, currency_id: CurrencyId<T>) -> Amount<T> {
+        Amount::new(LiquidatedBasketCollateral::<T>::get(vault_id, currency_id), currency_id)
+    }
+
+    /// Transfer the share `amount_wrapped / to_be_backed_tokens` of every basket currency held by the
+    /// liquidation vault to the redeemer
+    fn redeem_basket_collateral_liquidation(
+        currency_pair: &DefaultVaultCurrencyPair<T>,
+        redeemer_id: &T::AccountId,
+        amount_wrapped: &Amount<T>,
+        to_be_backed_tokens: &Amount<T>,
+    ) -> DispatchResult {
+        let liquidation_vault_account_id = Self::liquidation_vault_account_id();
+        for (currency_id, balance) in LiquidationVaultBasket::<T>::iter_prefix(currency_pair).collect::<Vec<_>>() {
+            let to_transfer =
+                Self::calculate_collateral(&Amount::new(balance, currency_id), amount_wrapped, to_be_backed_tokens)?;
+            if to_transfer.is_zero() {
+                continue;
+            }
+            LiquidationVaultBasket::<T>::insert(
+                currency_pair,
+                currency_id,
+                balance.saturating_sub(to_transfer.amount()),
+            );
+
+            to_transfer.unlock_on(&liquidation_vault_account_id)?;
+            to_transfer.transfer(&liquidation_vault_account_id, redeemer_id)?;
+
+            Self::deposit_event(Event::<T>::RedeemBasketCollateralLiquidation {
+                redeemer_id: redeemer_id.clone(),
+                currency_id,
+                amount: to_transfer.amount(),
+            });
+        }
+        Ok(())
+    }
+
     pub fn transfer_funds_saturated(
         from: CurrencySource<T>,
         to: CurrencySource<T>,
@@ -968,6 +1577,7 @@ impl<T: Config> Pallet<T> {
         tokens: &Amount<T>,
     ) -> Result<(), DispatchError> {
         let mut vault = Self::get_active_rich_vault_from_id(&vault_id)?;
+        ensure!(!vault.data.is_exiting(), Error::<T>::VaultExiting);
 
         let issuable_tokens = vault.issuable_tokens()?;
         ensure!(issuable_tokens.ge(&tokens)?, Error::<T>::ExceedingVaultLimit);
@@ -1056,6 +1666,7 @@ impl<T: Config> Pallet<T> {
             vault_id: vault.id(),
             decrease: tokens.amount(),
         });
+        Self::report_exit_progress(vault_id, &used_tokens)?;
 
         Ok((used_tokens, used_collateral))
     }
@@ -1074,7 +1685,7 @@ impl<T: Config> Pallet<T> {
             vault_id: vault_id.clone(),
             decrease: tokens.amount(),
         });
-        Ok(())
+        Self::report_exit_progress(vault_id, tokens)
     }
 
     /// Issues an amount of `tokens` tokens for the given `vault_id`
@@ -1095,7 +1706,10 @@ impl<T: Config> Pallet<T> {
             vault_id: vault.id(),
             increase: tokens.amount(),
         });
-        Ok(())
+
+        // tokens issued to an exiting vault are offered for replacement right away
+        Self::offer_exiting_vault_tokens(vault_id)?;
+        Self::report_exit_progress(vault_id, tokens)
     }
 
     /// Adds an amount tokens to the to-be-redeemed tokens balance of a vault.
@@ -1143,7 +1757,10 @@ impl<T: Config> Pallet<T> {
             vault_id: vault.id(),
             decrease: tokens.amount(),
         });
-        Ok(())
+
+        // the tokens are no longer being redeemed, so offer them again if the vault is exiting
+        Self::offer_exiting_vault_tokens(vault_id)?;
+        Self::report_exit_progress(vault_id, tokens)
     }
 
     /// Decreases the amount of tokens f a redeem request is not fulfilled
@@ -1165,7 +1782,7 @@ impl<T: Config> Pallet<T> {
             user_id: user_id.clone(),
             decrease: tokens.amount(),
         });
-        Ok(())
+        Self::report_exit_progress(vault_id, tokens)
     }
 
     /// Decreases the amount of collateral held after liquidation for any remaining to_be_redeemed tokens.
@@ -1229,6 +1846,10 @@ impl<T: Config> Pallet<T> {
 
             // release the collateral back to the free balance of the vault
             to_be_released.unlock_on(&vault_id.account_id)?;
+            for basket_collateral in Self::take_liquidated_basket_collateral(vault_id, tokens, &to_be_redeemed_tokens)?
+            {
+                basket_collateral.unlock_on(&vault_id.account_id)?;
+            }
 
             Self::deposit_event(Event::<T>::RedeemTokensLiquidatedVault {
                 vault_id: vault_id.clone(),
@@ -1237,7 +1858,7 @@ impl<T: Config> Pallet<T> {
             });
         }
 
-        Ok(())
+        Self::report_exit_progress(vault_id, tokens)
     }
 
     /// Handles redeem requests which are executed against the LiquidationVault.
@@ -1284,6 +1905,14 @@ impl<T: Config> Pallet<T> {
             &to_transfer,
         )?;
 
+        // transfer the same share of the seized basket collateral
+        Self::redeem_basket_collateral_liquidation(
+            &currency_pair,
+            redeemer_id,
+            amount_wrapped,
+            &liquidation_vault.to_be_backed_tokens()?,
+        )?;
+
         // need to requery since the liquidation vault gets modified in `transfer_funds`
         let mut liquidation_vault = Self::get_rich_liquidation_vault(&currency_pair);
         liquidation_vault.burn_issued(amount_wrapped)?;
@@ -1329,6 +1958,16 @@ impl<T: Config> Pallet<T> {
 
             // deposit old-vault's collateral (this was withdrawn on liquidation)
             PoolManager::<T>::deposit_collateral(old_vault_id, &old_vault_id.account_id, &to_be_released)?;
+            for basket_collateral in
+                Self::take_liquidated_basket_collateral(old_vault_id, tokens, &old_vault.to_be_redeemed_tokens())?
+            {
+                BasketCollateral::<T>::try_mutate(old_vault_id, basket_collateral.currency(), |balance| {
+                    *balance = balance
+                        .checked_add(&basket_collateral.amount())
+                        .ok_or(ArithmeticError::Overflow)?;
+                    Ok::<_, DispatchError>(())
+                })?;
+            }
         }
 
         old_vault.execute_redeem_tokens(tokens)?;
@@ -1340,7 +1979,7 @@ impl<T: Config> Pallet<T> {
             amount: tokens.amount(),
             additional_collateral: collateral.amount(),
         });
-        Ok(())
+        Self::report_exit_progress(old_vault_id, tokens)
     }
 
     /// Cancels a replace - which in the normal case decreases the old-vault's
@@ -1374,12 +2013,19 @@ impl<T: Config> Pallet<T> {
                 CurrencySource::LiquidationVault(old_vault_id.currencies.clone()),
                 &to_be_transferred,
             )?;
+            Self::confiscate_liquidated_basket_collateral(
+                old_vault_id,
+                tokens,
+                &old_vault.to_be_redeemed_tokens(),
+                None,
+            )?;
         }
 
         old_vault.cancel_redeem_tokens(tokens)?;
         new_vault.cancel_issue_tokens(tokens)?;
 
-        Ok(())
+        Self::offer_exiting_vault_tokens(old_vault_id)?;
+        Self::report_exit_progress(old_vault_id, tokens)
     }
 
     /// Withdraws an `amount` of tokens that were requested for replacement by `vault_id`
@@ -1403,6 +2049,162 @@ impl<T: Config> Pallet<T> {
         Ok((withdrawn_tokens, to_withdraw_collateral))
     }
 
+    /// Puts the vault into the exiting state and offers all of its issued tokens for replacement.
+    ///
+    /// # Arguments
+    /// * `vault_id` - the id of the vault to wind down
+    ///
+    /// # Errors
+    /// * `VaultLiquidated` - if the vault has been liquidated
+    /// * `VaultExiting` - if the vault is already exiting
+    pub fn _exit_vault(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        let mut vault = Self::get_active_rich_vault_from_id(vault_id)?;
+        ensure!(!vault.data.is_exiting(), Error::<T>::VaultExiting);
+        vault.set_exiting()?;
+        // exiting vaults do not receive rewards
+        PoolManager::<T>::on_vault_settings_change(vault_id)?;
+
+        let offered_tokens = Self::offer_exiting_vault_tokens(vault_id)?;
+        Self::deposit_event(Event::<T>::VaultExitStarted {
+            vault_id: vault_id.clone(),
+            offered_tokens: offered_tokens.amount(),
+        });
+        Self::queue_settled_exit(&Self::get_vault_from_id(vault_id)?);
+        Ok(())
+    }
+
+    /// Offers the tokens of an exiting vault that are neither being redeemed nor replaced for
+    /// replacement. The tokens are only offered if the vault can lock the griefing collateral from
+    /// its free balance, otherwise they remain available for redeem only.
+    ///
+    /// Returns the amount of tokens that were offered.
+    fn offer_exiting_vault_tokens(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
+        let vault = Self::get_vault_from_id(vault_id)?;
+        let tokens = if vault.is_exiting() {
+            Self::requestable_to_be_replaced_tokens(vault_id)?
+        } else {
+            Amount::zero(vault_id.wrapped_currency())
+        };
+        if tokens.is_zero() {
+            return Ok(tokens);
+        }
+
+        let griefing_currency = T::GetGriefingCollateralCurrencyId::get();
+        let griefing_collateral =
+            ext::fee::get_replace_griefing_collateral::<T>(&tokens.convert_to(griefing_currency)?)?;
+        let free_balance = ext::currency::get_free_balance::<T>(griefing_currency, &vault_id.account_id);
+        if free_balance.lt(&griefing_collateral)? {
+            return Ok(Amount::zero(vault_id.wrapped_currency()));
+        }
+
+        Self::try_increase_to_be_replaced_tokens(vault_id, &tokens)?;
+        Self::transfer_funds(
+            CurrencySource::FreeBalance(vault_id.account_id.clone()),
+            CurrencySource::AvailableReplaceCollateral(vault_id.clone()),
+            &griefing_collateral,
+        )?;
+        Ok(tokens)
+    }
+
+    /// Reports the remaining tokens of an exiting vault after its token balances changed by `tokens`,
+    /// and queues the completion of its exit once it no longer backs any tokens
+    fn report_exit_progress(vault_id: &DefaultVaultId<T>, tokens: &Amount<T>) -> DispatchResult {
+        if tokens.is_zero() {
+            return Ok(());
+        }
+        let vault = Self::get_vault_from_id(vault_id)?;
+        if !vault.is_exiting() {
+            return Ok(());
+        }
+
+        Self::deposit_event(Event::<T>::VaultExitProgress {
+            vault_id: vault_id.clone(),
+            issued_tokens: vault.issued_tokens,
+            to_be_issued_tokens: vault.to_be_issued_tokens,
+            to_be_redeemed_tokens: vault.to_be_redeemed_tokens,
+            to_be_replaced_tokens: vault.to_be_replaced_tokens,
+        });
+        Self::queue_settled_exit(&vault);
+        Ok(())
+    }
+
+    /// Queues the exit of the vault to be completed in `on_idle` if it no longer backs, redeems or
+    /// replaces any tokens
+    fn queue_settled_exit(vault: &DefaultVault<T>) {
+        if vault.is_exiting()
+            && vault.issued_tokens.is_zero()
+            && vault.to_be_issued_tokens.is_zero()
+            && vault.to_be_redeemed_tokens.is_zero()
+            && vault.to_be_replaced_tokens.is_zero()
+        {
+            PendingExits::<T>::insert(&vault.id, ());
+        }
+    }
+
+    /// Completes the exits of queued vaults, as many as fit into `remaining_weight`. A failed exit
+    /// is dropped from the queue, it can still be completed through `finalize_exit`.
+    ///
+    /// The weight that was consumed
+    pub(crate) fn finalize_pending_exits(remaining_weight: Weight) -> Weight {
+        let weight_per_exit =
+            <T as Config>::WeightInfo::finalize_exit().saturating_add(T::DbWeight::get().reads_writes(1, 1));
+        let max_exits = remaining_weight.ref_time() / weight_per_exit.ref_time().max(1);
+        let max_exits = max_exits.min(MAX_EXITS_PER_BLOCK as u64) as usize;
+        if max_exits == 0 {
+            return Weight::from_ref_time(0);
+        }
+
+        let vault_ids: Vec<_> = PendingExits::<T>::iter_keys().take(max_exits).collect();
+        for vault_id in vault_ids.iter() {
+            PendingExits::<T>::remove(vault_id);
+            // on_idle is not transactional, discard partial changes on failure
+            if let Err(e) = storage::with_storage_layer(|| Self::_finalize_exit(vault_id)) {
+                log::warn!("Failed to complete the exit of vault {:?}: {:?}", vault_id, e);
+            }
+        }
+        weight_per_exit.saturating_mul(vault_ids.len() as u64)
+    }
+
+    /// Completes the exit of a vault that no longer backs any tokens: nominators are refunded, and
+    /// the vault's own collateral as well as its basket collateral are released to its free balance.
+    ///
+    /// # Arguments
+    /// * `vault_id` - the id of the exiting vault
+    ///
+    /// # Errors
+    /// * `VaultNotExiting` - if the vault is not exiting
+    /// * `VaultExitPending` - if the vault still backs, redeems or replaces tokens
+    pub fn _finalize_exit(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        let vault = Self::get_rich_vault_from_id(vault_id)?;
+        ensure!(vault.data.is_exiting(), Error::<T>::VaultNotExiting);
+        ensure!(
+            vault.backed_tokens()?.is_zero()
+                && vault.to_be_redeemed_tokens().is_zero()
+                && vault.to_be_replaced_tokens().is_zero(),
+            Error::<T>::VaultExitPending
+        );
+        PendingExits::<T>::remove(vault_id);
+
+        let refunded_collateral = PoolManager::<T>::kick_nominators(vault_id)?;
+        Self::decrease_total_backing_collateral(&vault_id.currencies, &refunded_collateral)?;
+
+        let released_collateral = Self::compute_collateral(vault_id)?;
+        Self::force_withdraw_collateral(vault_id, &released_collateral)?;
+
+        for (currency_id, balance) in BasketCollateral::<T>::iter_prefix(vault_id).collect::<Vec<_>>() {
+            Self::try_withdraw_basket_collateral(vault_id, &Amount::new(balance, currency_id))?;
+        }
+
+        // the vault struct is re-fetched, since the withdrawals above may have modified it
+        Self::get_rich_vault_from_id(vault_id)?.set_exited()?;
+
+        Self::deposit_event(Event::<T>::VaultExited {
+            vault_id: vault_id.clone(),
+            released_collateral: released_collateral.amount(),
+        });
+        Ok(())
+    }
+
     fn undercollateralized_vaults() -> impl Iterator<Item = DefaultVaultId<T>> {
         <Vaults<T>>::iter().filter_map(|(vault_id, vault)| {
             if let Some(liquidation_threshold) = Self::liquidation_collateral_threshold(&vault.id.currencies) {
@@ -1518,7 +2320,7 @@ impl<T: Config> Pallet<T> {
         liquidation_threshold: UnsignedFixedPoint<T>,
     ) -> Result<bool, DispatchError> {
         Self::is_collateral_below_threshold(
-            &Self::get_backing_collateral(&vault.id)?,
+            &Self::get_effective_collateral(&vault.id)?,
             &Amount::new(vault.issued_tokens, vault.id.wrapped_currency()),
             liquidation_threshold,
         )
@@ -1556,6 +2358,20 @@ impl<T: Config> Pallet<T> {
         LiquidationCollateralThreshold::<T>::insert(currency_pair, threshold);
     }
 
+    pub fn _set_basket_collateral_haircut(
+        currency_id: CurrencyId<T>,
+        haircut: Option<UnsignedFixedPoint<T>>,
+    ) -> DispatchResult {
+        match haircut {
+            Some(haircut) => {
+                ensure!(haircut < UnsignedFixedPoint::<T>::one(), Error::<T>::InvalidHaircut);
+                BasketCollateralHaircut::<T>::insert(currency_id, haircut);
+            }
+            None => BasketCollateralHaircut::<T>::remove(currency_id),
+        }
+        Ok(())
+    }
+
     /// return (collateral * Numerator) / denominator, used when dealing with liquidated vaults
     pub fn calculate_collateral(
         collateral: &Amount<T>,
@@ -1667,6 +2483,151 @@ impl<T: Config> Pallet<T> {
         Ok(vaults_with_redeemable_tokens)
     }
 
+    /// Selects the vaults to issue `amount` of wrapped tokens with from the given candidates, together
+    /// with the amount to request from each of them.
+    ///
+    /// Only active candidates of the given currency pair that accept new issues and are not banned are
+    /// considered. They are ordered by descending issuable tokens, ties being broken by ascending vault
+    /// id, and filled in that order. As such, the amount is only split if no single vault can issue all
+    /// of it, and never across more than [`MAX_VAULTS_PER_REQUEST`] vaults. No vault is assigned less
+    /// than `min_amount`, unless the full amount is below it.
+    ///
+    /// # Arguments
+    /// * `currency_pair` - the collateral and wrapped currency of the vaults to select
+    /// * `candidates` - the vaults to select from, at most [`MAX_CANDIDATE_VAULTS`]
+    /// * `amount` - the total amount of tokens to issue
+    /// * `min_amount` - the minimum amount to assign to a single vault
+    pub fn select_vaults_for_issue(
+        currency_pair: &DefaultVaultCurrencyPair<T>,
+        candidates: &[DefaultVaultId<T>],
+        amount: &Amount<T>,
+        min_amount: &Amount<T>,
+    ) -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
+        let mut candidates = Self::get_candidate_vaults(currency_pair, candidates)
+            .filter(|(_, vault)| vault.accepts_new_issues())
+            .filter_map(|(vault_id, vault)| {
+                // issuable tokens are zero if the vault is banned
+                let issuable_tokens = Into::<RichVault<T>>::into(vault).issuable_tokens().ok()?;
+                if !issuable_tokens.is_zero() {
+                    Some((vault_id, issuable_tokens))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(_, _)>>();
+
+        candidates.sort_by(|a, b| b.1.amount().cmp(&a.1.amount()).then_with(|| a.0.cmp(&b.0)));
+        Self::split_amount_across_vaults(
+            candidates,
+            amount,
+            min_amount,
+            Error::<T>::NoVaultWithSufficientCollateral,
+        )
+    }
+
+    /// Selects the vaults to redeem `amount` of wrapped tokens from out of the given candidates, together
+    /// with the amount to request from each of them.
+    ///
+    /// Only non-liquidated candidates of the given currency pair that are not banned are considered. Exiting
+    /// vaults come first, such that they can be wound down, followed by vaults below the premium redeem
+    /// threshold, such that redeeming restores their collateralization, and all other vaults. Within each group, vaults
+    /// are ordered by descending redeemable tokens, ties being broken by ascending vault id. The vaults are filled
+    /// in that order, so the amount is only split if the first vault can not redeem all of it, and never across
+    /// more than [`MAX_VAULTS_PER_REQUEST`] vaults. No vault is assigned less than `min_amount`, unless the full
+    /// amount is below it.
+    ///
+    /// # Arguments
+    /// * `currency_pair` - the collateral and wrapped currency of the vaults to select
+    /// * `candidates` - the vaults to select from, at most [`MAX_CANDIDATE_VAULTS`]
+    /// * `amount` - the total amount of tokens to redeem
+    /// * `min_amount` - the minimum amount to assign to a single vault
+    pub fn select_vaults_for_redeem(
+        currency_pair: &DefaultVaultCurrencyPair<T>,
+        candidates: &[DefaultVaultId<T>],
+        amount: &Amount<T>,
+        min_amount: &Amount<T>,
+    ) -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
+        let mut candidates = Self::get_candidate_vaults(currency_pair, candidates)
+            .filter(|(_, vault)| !vault.is_liquidated())
+            .filter_map(|(vault_id, vault)| {
+                let exiting = vault.is_exiting();
+                // redeemable tokens are zero if the vault is banned
+                let redeemable_tokens = Into::<RichVault<T>>::into(vault).redeemable_tokens().ok()?;
+                if !redeemable_tokens.is_zero() {
+                    let below_premium_threshold = Self::is_vault_below_premium_threshold(&vault_id).unwrap_or(false);
+                    Some((vault_id, redeemable_tokens, (exiting, below_premium_threshold)))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(_, _, _)>>();
+
+        candidates.sort_by(|a, b| {
+            b.2.cmp(&a.2)
+                .then_with(|| b.1.amount().cmp(&a.1.amount()))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        Self::split_amount_across_vaults(
+            candidates
+                .into_iter()
+                .map(|(vault_id, redeemable_tokens, _)| (vault_id, redeemable_tokens))
+                .collect(),
+            amount,
+            min_amount,
+            Error::<T>::NoVaultWithSufficientTokens,
+        )
+    }
+
+    /// Loads the distinct candidate vaults of the given currency pair, skipping unknown vaults.
+    fn get_candidate_vaults<'a>(
+        currency_pair: &'a DefaultVaultCurrencyPair<T>,
+        candidates: &[DefaultVaultId<T>],
+    ) -> impl Iterator<Item = (DefaultVaultId<T>, DefaultVault<T>)> + 'a {
+        let mut candidates = candidates.to_vec();
+        candidates.sort();
+        candidates.dedup();
+        candidates
+            .into_iter()
+            .take(MAX_CANDIDATE_VAULTS as usize)
+            .filter(move |vault_id| vault_id.currencies == *currency_pair)
+            .filter_map(|vault_id| Vaults::<T>::get(&vault_id).map(|vault| (vault_id, vault)))
+    }
+
+    /// Assigns `amount` to the given vaults in order, each up to its capacity, using at most
+    /// [`MAX_VAULTS_PER_REQUEST`] vaults. If the remainder left for the next vault would be below
+    /// `min_amount`, it is folded into the current chunk by holding back enough of the current chunk
+    /// instead; vaults that could then only take less than `min_amount` are skipped. Fails with `error`
+    /// if the vaults can not cover the full amount.
+    fn split_amount_across_vaults(
+        candidates: Vec<(DefaultVaultId<T>, Amount<T>)>,
+        amount: &Amount<T>,
+        min_amount: &Amount<T>,
+        error: Error<T>,
+    ) -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
+        let mut remaining = amount.clone();
+        let mut selected = Vec::new();
+        for (vault_id, capacity) in candidates {
+            if remaining.is_zero() || selected.len() >= MAX_VAULTS_PER_REQUEST as usize {
+                break;
+            }
+            let assigned = if capacity.ge(&remaining)? {
+                remaining.clone()
+            } else {
+                // leave at least `min_amount` for the next vault, so the final chunk is not below the dust amount
+                let assigned = capacity.min(&remaining.saturating_sub(min_amount)?)?;
+                if assigned.lt(min_amount)? || assigned.is_zero() {
+                    continue;
+                }
+                assigned
+            };
+            remaining = remaining.checked_sub(&assigned)?;
+            selected.push((vault_id, assigned));
+        }
+
+        ensure!(remaining.is_zero() && !selected.is_empty(), error);
+        Ok(selected)
+    }
+
     /// Get the amount of tokens a vault can issue
     pub fn get_issuable_tokens_from_vault(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
         let vault = Self::get_active_rich_vault_from_id(vault_id)?;
@@ -1700,7 +2661,7 @@ impl<T: Config> Pallet<T> {
         only_issued: bool,
     ) -> Result<UnsignedFixedPoint<T>, DispatchError> {
         let vault = Self::get_active_rich_vault_from_id(&vault_id)?;
-        let collateral = vault.get_total_collateral()?;
+        let collateral = vault.get_effective_collateral()?;
         Self::get_collateralization_from_vault_and_collateral(vault_id, &collateral, only_issued)
     }
 
@@ -1849,8 +2810,8 @@ impl<T: Config> Pallet<T> {
     ) -> Result<bool, DispatchError> {
         let vault = Self::get_rich_vault_from_id(&vault_id)?;
 
-        // the current locked backing collateral by the vault
-        let collateral = Self::get_backing_collateral(vault_id)?;
+        // the current locked backing collateral by the vault, including its basket
+        let collateral = Self::get_effective_collateral(vault_id)?;
 
         Self::is_collateral_below_threshold(&collateral, &vault.issued_tokens(), threshold)
     }
@@ -1903,7 +2864,8 @@ impl<T: Config> Pallet<T> {
     #[cfg(feature = "integration-tests")]
     pub fn collateral_integrity_check() {
         let griefing_currency = T::GetGriefingCollateralCurrencyId::get();
-        for (vault_id, vault) in Vaults::<T>::iter().filter(|(_, vault)| matches!(vault.status, VaultStatus::Active(_)))
+        for (vault_id, vault) in Vaults::<T>::iter()
+            .filter(|(_, vault)| matches!(vault.status, VaultStatus::Active(_) | VaultStatus::Exiting))
         {
             // check that there is enough griefing collateral
             let active_griefing = CurrencySource::<T>::ActiveReplaceCollateral(vault_id.clone())
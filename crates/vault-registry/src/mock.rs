@@ -43,7 +43,7 @@ frame_support::construct_runtime!(
         Security: security::{Pallet, Call, Storage, Event<T>},
         VaultRegistry: vault_registry::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
         Oracle: oracle::{Pallet, Call, Config<T>, Storage, Event<T>},
-        Fee: fee::{Pallet, Call, Config<T>, Storage},
+        Fee: fee::{Pallet, Call, Config<T>, Storage, Event<T>},
         Currency: currency::{Pallet},
     }
 );
@@ -179,9 +179,15 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const TreasuryPalletId: PalletId = PalletId(*b"mod/trsy");
+}
+
 impl oracle::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OnExchangeRateChange = vault_registry::PoolManager<Test>;
+    type TreasuryPalletId = TreasuryPalletId;
+    type DexPriceSource = ();
     type WeightInfo = ();
 }
 
@@ -220,6 +226,7 @@ parameter_types! {
 }
 
 impl fee::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type FeePalletId = FeePalletId;
     type WeightInfo = ();
     type SignedFixedPoint = SignedFixedPoint;
@@ -230,6 +237,9 @@ impl fee::Config for Test {
     type VaultRewards = VaultRewards;
     type VaultStaking = VaultStaking;
     type OnSweep = ();
+    type DexSwap = ();
+    type Nomination = MockDeposit;
+    type MaxCompoundingNominators = ConstU32<32>;
     type MaxExpectedValue = MaxExpectedValue;
 }
 
@@ -239,7 +249,7 @@ parameter_types! {
 
 pub struct MockDeposit;
 
-impl traits::NominationApi<VaultId<AccountId, CurrencyId>, currency::Amount<Test>> for MockDeposit {
+impl traits::NominationApi<VaultId<AccountId, CurrencyId>, AccountId, currency::Amount<Test>> for MockDeposit {
     fn deposit_vault_collateral(
         vault_id: &VaultId<AccountId, CurrencyId>,
         amount: &currency::Amount<Test>,
@@ -254,6 +264,25 @@ impl traits::NominationApi<VaultId<AccountId, CurrencyId>, currency::Amount<Test
 
         Ok(())
     }
+
+    fn deposit_nominator_collateral(
+        vault_id: &VaultId<AccountId, CurrencyId>,
+        nominator_id: &AccountId,
+        amount: &currency::Amount<Test>,
+    ) -> Result<(), DispatchError> {
+        let _vault = VaultRegistry::get_active_rich_vault_from_id(vault_id)?;
+
+        amount.transfer(nominator_id, &vault_id.account_id)?;
+        <vault_registry::PoolManager<Test>>::deposit_collateral(vault_id, nominator_id, amount)?;
+        amount.lock_on(&vault_id.account_id)?;
+        VaultRegistry::try_increase_total_backing_collateral(&vault_id.currencies, &amount)?;
+
+        Ok(())
+    }
+
+    fn get_total_unbonding(vault_id: &VaultId<AccountId, CurrencyId>) -> currency::Amount<Test> {
+        currency::Amount::new(0, vault_id.collateral_currency())
+    }
 }
 
 impl Config for Test {
@@ -263,6 +292,7 @@ impl Config for Test {
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
     type NominationApi = MockDeposit;
+    type LendTokens = ();
 }
 
 impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
@@ -1,5 +1,5 @@
 use crate::*;
-use traits::OnExchangeRateChange;
+use traits::{NominationApi, OnExchangeRateChange};
 
 pub struct PoolManager<T>(PhantomData<T>);
 
@@ -49,7 +49,7 @@ impl<T: Config> PoolManager<T> {
 
     // hook to be called _after_ one of the vault parameters that influences
     // capacity has been written. This is called when the custom secure threshold or
-    // the accept_new_issues setting changes.
+    // the accept_new_issues setting changes, and when nominators start unbonding.
     pub fn on_vault_settings_change(vault_id: &DefaultVaultId<T>) -> Result<(), DispatchError> {
         ext::fee::distribute_all_vault_rewards::<T>(vault_id)?;
         Self::update_reward_stake(vault_id)
@@ -62,7 +62,9 @@ impl<T: Config> PoolManager<T> {
             // if the vault is not accepting new issues it's not getting rewards
             Amount::zero(vault_id.collateral_currency())
         } else {
-            let total_collateral = ext::staking::total_current_stake::<T>(vault_id)?;
+            // unbonding collateral is not issued against, so it adds no capacity
+            let total_collateral = ext::staking::total_current_stake::<T>(vault_id)?
+                .saturating_sub(&T::NominationApi::get_total_unbonding(vault_id))?;
             let secure_threshold = Pallet::<T>::get_vault_secure_threshold(vault_id)?;
 
             total_collateral.checked_div(&secure_threshold)?
@@ -1313,6 +1313,184 @@ mod get_vaults_with_redeemable_tokens_test {
     }
 }
 
+mod select_vaults_tests {
+    use super::{assert_eq, *};
+
+    fn add_vault(id: DefaultVaultId<Test>, issued_tokens: u128, collateral: u128) {
+        create_vault_with_collateral(&id, collateral);
+
+        VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(issued_tokens)).unwrap();
+        assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(issued_tokens)));
+    }
+
+    #[test]
+    fn select_vaults_for_issue_selects_single_vault_with_most_capacity() {
+        run_test(|| {
+            let id1 = vault_id(3);
+            create_vault_with_collateral(&id1, 50);
+            let id2 = vault_id(4);
+            create_vault_with_collateral(&id2, 100);
+            let issuable_tokens1 = VaultRegistry::get_issuable_tokens_from_vault(&id1).unwrap();
+
+            // both vaults can issue the full amount, but only the one with the most capacity is used
+            assert_eq!(
+                VaultRegistry::select_vaults_for_issue(
+                    &DEFAULT_CURRENCY_PAIR,
+                    &[id1.clone(), id2.clone()],
+                    &issuable_tokens1,
+                    &wrapped(1)
+                ),
+                Ok(vec![(id2, issuable_tokens1)])
+            );
+        })
+    }
+
+    #[test]
+    fn select_vaults_for_issue_splits_amount_across_vaults() {
+        run_test(|| {
+            let id1 = vault_id(3);
+            create_vault_with_collateral(&id1, 50);
+            let id2 = vault_id(4);
+            create_vault_with_collateral(&id2, 100);
+            let issuable_tokens2 = VaultRegistry::get_issuable_tokens_from_vault(&id2).unwrap();
+
+            assert_eq!(
+                VaultRegistry::select_vaults_for_issue(
+                    &DEFAULT_CURRENCY_PAIR,
+                    &[id1.clone(), id2.clone()],
+                    &(issuable_tokens2 + wrapped(1)),
+                    &wrapped(1)
+                ),
+                Ok(vec![(id2, issuable_tokens2), (id1, wrapped(1))])
+            );
+        })
+    }
+
+    #[test]
+    fn select_vaults_for_issue_fails_with_insufficient_capacity() {
+        run_test(|| {
+            let id1 = vault_id(3);
+            create_vault_with_collateral(&id1, 50);
+            let id2 = vault_id(4);
+            create_vault_with_collateral(&id2, 100);
+            let issuable_tokens1 = VaultRegistry::get_issuable_tokens_from_vault(&id1).unwrap();
+            let issuable_tokens2 = VaultRegistry::get_issuable_tokens_from_vault(&id2).unwrap();
+
+            assert_err!(
+                VaultRegistry::select_vaults_for_issue(
+                    &DEFAULT_CURRENCY_PAIR,
+                    &[id1.clone(), id2.clone()],
+                    &(issuable_tokens1 + issuable_tokens2 + wrapped(1)),
+                    &wrapped(1)
+                ),
+                TestError::NoVaultWithSufficientCollateral
+            );
+        })
+    }
+
+    #[test]
+    fn select_vaults_for_issue_only_considers_candidates() {
+        run_test(|| {
+            let id1 = vault_id(3);
+            create_vault_with_collateral(&id1, 50);
+            let id2 = vault_id(4);
+            create_vault_with_collateral(&id2, 100);
+            let issuable_tokens1 = VaultRegistry::get_issuable_tokens_from_vault(&id1).unwrap();
+
+            // duplicate candidates are only used once
+            assert_eq!(
+                VaultRegistry::select_vaults_for_issue(
+                    &DEFAULT_CURRENCY_PAIR,
+                    &[id1.clone(), id1.clone()],
+                    &issuable_tokens1,
+                    &wrapped(1)
+                ),
+                Ok(vec![(id1.clone(), issuable_tokens1)])
+            );
+            assert_err!(
+                VaultRegistry::select_vaults_for_issue(
+                    &DEFAULT_CURRENCY_PAIR,
+                    &[id1.clone(), id1],
+                    &(issuable_tokens1 + wrapped(1)),
+                    &wrapped(1)
+                ),
+                TestError::NoVaultWithSufficientCollateral
+            );
+        })
+    }
+
+    #[test]
+    fn select_vaults_for_issue_does_not_leave_dust_for_the_last_vault() {
+        run_test(|| {
+            let id1 = vault_id(3);
+            create_vault_with_collateral(&id1, 50);
+            let id2 = vault_id(4);
+            create_vault_with_collateral(&id2, 100);
+            let issuable_tokens2 = VaultRegistry::get_issuable_tokens_from_vault(&id2).unwrap();
+
+            // the first vault keeps back enough for the second vault to reach the minimum amount
+            assert_eq!(
+                VaultRegistry::select_vaults_for_issue(
+                    &DEFAULT_CURRENCY_PAIR,
+                    &[id1.clone(), id2.clone()],
+                    &(issuable_tokens2 + wrapped(1)),
+                    &wrapped(3)
+                ),
+                Ok(vec![(id2, issuable_tokens2 - wrapped(2)), (id1, wrapped(3))])
+            );
+        })
+    }
+
+    #[test]
+    fn select_vaults_for_redeem_prefers_vaults_below_premium_threshold() {
+        run_test(|| {
+            VaultRegistry::_set_secure_collateral_threshold(DEFAULT_CURRENCY_PAIR, FixedU128::from_float(0.001));
+            VaultRegistry::_set_premium_redeem_threshold(DEFAULT_CURRENCY_PAIR, FixedU128::one());
+
+            // sufficiently collateralized, with the most redeemable tokens
+            let id1 = vault_id(3);
+            add_vault(id1.clone(), 50, 100);
+            // below the premium redeem threshold
+            let id2 = vault_id(4);
+            add_vault(id2.clone(), 20, 19);
+            let candidates = [id1.clone(), id2.clone()];
+
+            assert_eq!(
+                VaultRegistry::select_vaults_for_redeem(&DEFAULT_CURRENCY_PAIR, &candidates, &wrapped(10), &wrapped(1)),
+                Ok(vec![(id2.clone(), wrapped(10))])
+            );
+            assert_eq!(
+                VaultRegistry::select_vaults_for_redeem(&DEFAULT_CURRENCY_PAIR, &candidates, &wrapped(30), &wrapped(1)),
+                Ok(vec![(id2, wrapped(20)), (id1, wrapped(10))])
+            );
+            assert_err!(
+                VaultRegistry::select_vaults_for_redeem(&DEFAULT_CURRENCY_PAIR, &candidates, &wrapped(71), &wrapped(1)),
+                TestError::NoVaultWithSufficientTokens
+            );
+        })
+    }
+
+    #[test]
+    fn select_vaults_for_redeem_prefers_exiting_vaults() {
+        run_test(|| {
+            VaultRegistry::_set_secure_collateral_threshold(DEFAULT_CURRENCY_PAIR, FixedU128::from_float(0.001));
+            convert_to.mock_safe(|currency, x| MockResult::Return(Ok(Amount::new(x.amount(), currency))));
+
+            let id1 = vault_id(3);
+            add_vault(id1.clone(), 50, 100);
+            let id2 = vault_id(4);
+            add_vault(id2.clone(), 20, 100);
+            assert_ok!(VaultRegistry::_exit_vault(&id2));
+            let candidates = [id1.clone(), id2.clone()];
+
+            assert_eq!(
+                VaultRegistry::select_vaults_for_redeem(&DEFAULT_CURRENCY_PAIR, &candidates, &wrapped(30), &wrapped(1)),
+                Ok(vec![(id2, wrapped(20)), (id1, wrapped(10))])
+            );
+        })
+    }
+}
+
 #[test]
 fn test_try_increase_to_be_replaced_tokens() {
     run_test(|| {
@@ -1419,3 +1597,608 @@ fn test_offchain_worker_unsigned_transaction_submission() {
         );
     })
 }
+
+#[test]
+fn test_offchain_worker_does_not_report_vaults_when_liquidations_paused() {
+    let mut externalities = crate::mock::ExtBuilder::build();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    externalities.register_extension(TransactionPoolExt::new(pool));
+
+    externalities.execute_with(|| {
+        let id = vault_id(7);
+        System::set_block_number(1);
+        Security::<Test>::set_active_block_number(1);
+        Security::<Test>::insert_error(security::ErrorCode::PriceCircuitBreaker);
+        set_default_thresholds();
+        VaultRegistry::insert_vault(&id, Vault::new(id.clone()));
+        VaultRegistry::is_vault_below_liquidation_threshold.mock_safe(move |_, _| MockResult::Return(Ok(true)));
+
+        VaultRegistry::_offchain_worker();
+
+        assert!(pool_state.read().transactions.is_empty());
+        assert_noop!(
+            VaultRegistry::report_undercollateralized_vault(RuntimeOrigin::none(), id),
+            security::Error::<Test>::LiquidationsPaused
+        );
+    })
+}
+
+mod basket_collateral_tests {
+    use super::{assert_eq, *};
+
+    const BASKET_CURRENCY: CurrencyId = Token(INTR);
+
+    fn basket(amount: u128) -> Amount<Test> {
+        Amount::new(amount, BASKET_CURRENCY)
+    }
+
+    fn setup_basket_vault(haircut: FixedU128, basket_collateral: u128) -> DefaultVaultId<Test> {
+        let id = create_sample_vault();
+        convert_to.mock_safe(|currency, x| MockResult::Return(Ok(Amount::new(x.amount(), currency))));
+        assert_ok!(VaultRegistry::set_basket_collateral_haircut(
+            RuntimeOrigin::root(),
+            BASKET_CURRENCY,
+            Some(haircut)
+        ));
+        assert_ok!(basket(basket_collateral).mint_to(&id.account_id));
+        assert_ok!(VaultRegistry::deposit_basket_collateral(
+            RuntimeOrigin::signed(id.account_id),
+            id.currencies.clone(),
+            BASKET_CURRENCY,
+            basket_collateral
+        ));
+        id
+    }
+
+    #[test]
+    fn set_basket_collateral_haircut_fails_with_full_haircut() {
+        run_test(|| {
+            assert_noop!(
+                VaultRegistry::set_basket_collateral_haircut(
+                    RuntimeOrigin::root(),
+                    BASKET_CURRENCY,
+                    Some(FixedU128::one())
+                ),
+                TestError::InvalidHaircut
+            );
+        })
+    }
+
+    #[test]
+    fn deposit_basket_collateral_fails_with_unaccepted_currency() {
+        run_test(|| {
+            let id = create_sample_vault();
+            assert_noop!(
+                VaultRegistry::try_deposit_basket_collateral(&id, &basket(100)),
+                TestError::BasketCurrencyNotAccepted
+            );
+
+            // the primary collateral currency can not be part of the basket
+            assert_ok!(VaultRegistry::_set_basket_collateral_haircut(
+                DEFAULT_COLLATERAL_CURRENCY,
+                Some(FixedU128::from(0))
+            ));
+            assert_noop!(
+                VaultRegistry::try_deposit_basket_collateral(&id, &amount(100)),
+                TestError::BasketCurrencyNotAccepted
+            );
+        })
+    }
+
+    #[test]
+    fn basket_collateral_increases_issuable_tokens() {
+        run_test(|| {
+            let id = setup_basket_vault(FixedU128::checked_from_rational(1, 2).unwrap(), 20000);
+
+            assert_eq!(
+                currency::get_reserved_balance::<Test>(BASKET_CURRENCY, &id.account_id),
+                basket(20000)
+            );
+            assert_eq!(VaultRegistry::get_basket_collateral_value(&id), Ok(amount(10000)));
+            assert_eq!(
+                VaultRegistry::get_effective_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL + 10000))
+            );
+            // 200% secure threshold
+            assert_eq!(
+                VaultRegistry::get_issuable_tokens_from_vault(&id),
+                Ok(wrapped((DEFAULT_COLLATERAL + 10000) / 2))
+            );
+
+            // a basket currency without a price is valued at zero
+            convert_to.mock_safe(|currency, x| {
+                if x.currency() == BASKET_CURRENCY {
+                    MockResult::Return(Err(oracle::Error::<Test>::MissingExchangeRate.into()))
+                } else {
+                    MockResult::Return(Ok(Amount::new(x.amount(), currency)))
+                }
+            });
+            assert_eq!(
+                VaultRegistry::get_effective_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL))
+            );
+
+            // other conversion errors are not hidden
+            convert_to.mock_safe(|currency, x| {
+                if x.currency() == BASKET_CURRENCY {
+                    MockResult::Return(Err(ArithmeticError::Overflow.into()))
+                } else {
+                    MockResult::Return(Ok(Amount::new(x.amount(), currency)))
+                }
+            });
+            assert_err!(VaultRegistry::get_effective_collateral(&id), ArithmeticError::Overflow);
+
+            // removing the currency from the basket discards its value
+            assert_ok!(VaultRegistry::_set_basket_collateral_haircut(BASKET_CURRENCY, None));
+            assert_eq!(
+                VaultRegistry::get_effective_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL))
+            );
+        })
+    }
+
+    #[test]
+    fn withdraw_basket_collateral_fails_below_secure_threshold() {
+        run_test(|| {
+            let id = setup_basket_vault(FixedU128::from(0), DEFAULT_COLLATERAL);
+            let issue_tokens = DEFAULT_COLLATERAL;
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(
+                &id,
+                &wrapped(issue_tokens)
+            ));
+
+            assert_noop!(
+                VaultRegistry::withdraw_basket_collateral(
+                    RuntimeOrigin::signed(id.account_id),
+                    id.currencies.clone(),
+                    BASKET_CURRENCY,
+                    1
+                ),
+                TestError::InsufficientCollateral
+            );
+
+            assert_ok!(VaultRegistry::decrease_to_be_issued_tokens(&id, &wrapped(issue_tokens)));
+            assert_ok!(VaultRegistry::try_withdraw_basket_collateral(
+                &id,
+                &basket(DEFAULT_COLLATERAL)
+            ));
+            assert!(VaultRegistry::get_basket_collateral(&id, BASKET_CURRENCY).is_zero());
+            assert_eq!(
+                currency::get_free_balance::<Test>(BASKET_CURRENCY, &id.account_id),
+                basket(DEFAULT_COLLATERAL)
+            );
+        })
+    }
+
+    #[test]
+    fn liquidate_vault_seizes_basket_proportionally() {
+        run_test(|| {
+            let id = setup_basket_vault(FixedU128::from(0), DEFAULT_COLLATERAL);
+            let issue_tokens = DEFAULT_COLLATERAL / 2;
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(
+                &id,
+                &wrapped(issue_tokens)
+            ));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(issue_tokens)));
+
+            assert_ok!(VaultRegistry::liquidate_vault(&id));
+
+            // 110% liquidation threshold: 55_000 of the 200_000 collateral value is seized,
+            // split evenly between the backing collateral and the basket
+            let seized = issue_tokens * 11 / 10 / 2;
+            let liquidation_vault = VaultRegistry::get_liquidation_vault(&id.currencies);
+            assert_eq!(liquidation_vault.collateral, seized);
+            assert_eq!(
+                crate::LiquidationVaultBasket::<Test>::get(&id.currencies, BASKET_CURRENCY),
+                seized
+            );
+            assert_eq!(
+                VaultRegistry::get_basket_collateral(&id, BASKET_CURRENCY),
+                basket(DEFAULT_COLLATERAL - seized)
+            );
+            assert_eq!(
+                currency::get_reserved_balance::<Test>(BASKET_CURRENCY, &VaultRegistry::liquidation_vault_account_id()),
+                basket(seized)
+            );
+            assert_emitted!(Event::SeizeBasketCollateral {
+                vault_id: id,
+                currency_id: BASKET_CURRENCY,
+                amount: seized,
+            });
+        })
+    }
+
+    #[test]
+    fn liquidated_vault_can_not_withdraw_basket_backing_to_be_redeemed_tokens() {
+        run_test(|| {
+            let id = setup_basket_vault(FixedU128::from(0), DEFAULT_COLLATERAL);
+            let issue_tokens = DEFAULT_COLLATERAL / 2;
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(
+                &id,
+                &wrapped(issue_tokens)
+            ));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(issue_tokens)));
+            assert_ok!(VaultRegistry::try_increase_to_be_redeemed_tokens(
+                &id,
+                &wrapped(issue_tokens / 2)
+            ));
+
+            assert_ok!(VaultRegistry::liquidate_vault(&id));
+
+            // 55_000 of the 200_000 collateral value is liquidated, half of which backs the
+            // to_be_redeemed tokens. Of each half, the basket provides one half.
+            let share = issue_tokens * 11 / 10 / 2 / 2;
+            assert_eq!(
+                crate::LiquidationVaultBasket::<Test>::get(&id.currencies, BASKET_CURRENCY),
+                share
+            );
+            assert_eq!(
+                VaultRegistry::get_liquidated_basket_collateral(&id, BASKET_CURRENCY),
+                basket(share)
+            );
+            let remaining = DEFAULT_COLLATERAL - 2 * share;
+            assert_eq!(
+                VaultRegistry::get_basket_collateral(&id, BASKET_CURRENCY),
+                basket(remaining)
+            );
+
+            // the share backing the to_be_redeemed tokens can not be withdrawn
+            assert_noop!(
+                VaultRegistry::try_withdraw_basket_collateral(&id, &basket(remaining + share)),
+                TestError::InsufficientCollateral
+            );
+            assert_ok!(VaultRegistry::try_withdraw_basket_collateral(&id, &basket(remaining)));
+            assert_eq!(
+                currency::get_reserved_balance::<Test>(BASKET_CURRENCY, &id.account_id),
+                basket(share)
+            );
+
+            // it is released once the tokens are redeemed
+            assert_ok!(VaultRegistry::redeem_tokens(
+                &id,
+                &wrapped(issue_tokens / 2),
+                &amount(0),
+                &0
+            ));
+            assert!(VaultRegistry::get_liquidated_basket_collateral(&id, BASKET_CURRENCY).is_zero());
+            assert!(currency::get_reserved_balance::<Test>(BASKET_CURRENCY, &id.account_id).is_zero());
+            assert_eq!(
+                currency::get_free_balance::<Test>(BASKET_CURRENCY, &id.account_id),
+                basket(remaining + share)
+            );
+        })
+    }
+}
+
+mod exit_vault_tests {
+    use super::{assert_eq, *};
+    use crate::{PendingExits, VaultStatus};
+    use frame_support::weights::Weight;
+
+    fn finalize_pending_exits() {
+        VaultRegistry::finalize_pending_exits(Weight::from_ref_time(u64::MAX));
+    }
+
+    fn setup_exiting_vault(issued_tokens: u128) -> DefaultVaultId<Test> {
+        let id = create_sample_vault();
+        convert_to.mock_safe(|currency, x| MockResult::Return(Ok(Amount::new(x.amount(), currency))));
+        if issued_tokens > 0 {
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(
+                &id,
+                &wrapped(issued_tokens)
+            ));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(issued_tokens)));
+        }
+        assert_ok!(VaultRegistry::exit_vault(
+            RuntimeOrigin::signed(id.account_id),
+            id.currencies.clone()
+        ));
+        id
+    }
+
+    #[test]
+    fn exit_vault_offers_issued_tokens_and_blocks_new_issues() {
+        run_test(|| {
+            let id = setup_exiting_vault(50);
+
+            let vault = VaultRegistry::get_vault_from_id(&id).unwrap();
+            assert_eq!(vault.status, VaultStatus::Exiting);
+            assert_eq!(vault.to_be_replaced_tokens, 50);
+            assert_emitted!(Event::VaultExitStarted {
+                vault_id: id.clone(),
+                offered_tokens: 50,
+            });
+
+            assert_noop!(
+                VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(1)),
+                TestError::VaultExiting
+            );
+            assert_noop!(
+                VaultRegistry::accept_new_issues(RuntimeOrigin::signed(id.account_id), id.currencies.clone(), true),
+                TestError::VaultExiting
+            );
+            assert_noop!(
+                VaultRegistry::exit_vault(RuntimeOrigin::signed(id.account_id), id.currencies.clone()),
+                TestError::VaultExiting
+            );
+        })
+    }
+
+    #[test]
+    fn exit_vault_without_tokens_releases_collateral() {
+        run_test(|| {
+            let id = setup_exiting_vault(0);
+            assert!(PendingExits::<Test>::contains_key(&id));
+
+            // the exit is only completed if the remaining weight allows for it
+            assert_eq!(
+                VaultRegistry::finalize_pending_exits(Weight::from_ref_time(0)),
+                Weight::from_ref_time(0)
+            );
+            assert_eq!(
+                VaultRegistry::get_vault_from_id(&id).unwrap().status,
+                VaultStatus::Exiting
+            );
+
+            finalize_pending_exits();
+            assert!(!PendingExits::<Test>::contains_key(&id));
+            let vault = VaultRegistry::get_vault_from_id(&id).unwrap();
+            assert_eq!(vault.status, VaultStatus::Exited);
+            assert_eq!(VaultRegistry::get_backing_collateral(&id), Ok(amount(0)));
+            assert_eq!(
+                currency::get_free_balance::<Test>(DEFAULT_COLLATERAL_CURRENCY, &id.account_id),
+                amount(DEFAULT_COLLATERAL)
+            );
+            assert_emitted!(Event::VaultExited {
+                vault_id: id.clone(),
+                released_collateral: DEFAULT_COLLATERAL,
+            });
+
+            assert_noop!(
+                VaultRegistry::finalize_exit(RuntimeOrigin::signed(RICH_ID.account_id), id.clone()),
+                TestError::VaultNotExiting
+            );
+            assert_noop!(
+                VaultRegistry::try_deposit_collateral(&id, &amount(100)),
+                TestError::VaultHasExited
+            );
+        })
+    }
+
+    #[test]
+    fn exited_vault_can_register_again() {
+        run_test(|| {
+            let id = setup_exiting_vault(0);
+            finalize_pending_exits();
+            assert_eq!(
+                VaultRegistry::get_vault_from_id(&id).unwrap().status,
+                VaultStatus::Exited
+            );
+
+            assert_ok!(VaultRegistry::register_vault(
+                RuntimeOrigin::signed(id.account_id),
+                id.currencies.clone(),
+                DEFAULT_COLLATERAL
+            ));
+            let vault = VaultRegistry::get_vault_from_id(&id).unwrap();
+            assert_eq!(vault.status, VaultStatus::Active(true));
+            assert_eq!(
+                VaultRegistry::get_backing_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL))
+            );
+        })
+    }
+
+    #[test]
+    fn exit_completes_once_tokens_are_redeemed() {
+        run_test(|| {
+            let id = setup_exiting_vault(50);
+
+            // a redeem request takes over the tokens offered for replacement
+            assert_ok!(VaultRegistry::try_increase_to_be_redeemed_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::decrease_to_be_replaced_tokens(&id, &wrapped(50)));
+            assert_emitted!(Event::VaultExitProgress {
+                vault_id: id.clone(),
+                issued_tokens: 50,
+                to_be_issued_tokens: 0,
+                to_be_redeemed_tokens: 50,
+                to_be_replaced_tokens: 0,
+            });
+            assert_noop!(
+                VaultRegistry::finalize_exit(RuntimeOrigin::signed(RICH_ID.account_id), id.clone()),
+                TestError::VaultExitPending
+            );
+            assert!(!PendingExits::<Test>::contains_key(&id));
+
+            assert_ok!(VaultRegistry::redeem_tokens(&id, &wrapped(50), &amount(0), &0));
+            assert!(PendingExits::<Test>::contains_key(&id));
+            finalize_pending_exits();
+            assert_eq!(
+                VaultRegistry::get_vault_from_id(&id).unwrap().status,
+                VaultStatus::Exited
+            );
+            assert_eq!(VaultRegistry::get_backing_collateral(&id), Ok(amount(0)));
+            assert_eq!(
+                currency::get_free_balance::<Test>(DEFAULT_COLLATERAL_CURRENCY, &id.account_id),
+                amount(DEFAULT_COLLATERAL)
+            );
+            assert_emitted!(Event::VaultExited {
+                vault_id: id,
+                released_collateral: DEFAULT_COLLATERAL,
+            });
+        })
+    }
+
+    #[test]
+    fn cancelled_redeem_is_offered_for_replacement_again() {
+        run_test(|| {
+            let id = setup_exiting_vault(50);
+
+            assert_ok!(VaultRegistry::try_increase_to_be_redeemed_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::decrease_to_be_replaced_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::decrease_to_be_redeemed_tokens(&id, &wrapped(50)));
+
+            let vault = VaultRegistry::get_vault_from_id(&id).unwrap();
+            assert_eq!(vault.status, VaultStatus::Exiting);
+            assert!(!PendingExits::<Test>::contains_key(&id));
+            assert_eq!(vault.to_be_redeemed_tokens, 0);
+            assert_eq!(vault.to_be_replaced_tokens, 50);
+        })
+    }
+
+    #[test]
+    fn exit_progress_is_only_reported_when_tokens_change() {
+        run_test(|| {
+            let id = setup_exiting_vault(50);
+            assert_ok!(VaultRegistry::try_increase_to_be_redeemed_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::decrease_to_be_replaced_tokens(&id, &wrapped(50)));
+            let progress_events = || {
+                System::events()
+                    .iter()
+                    .filter(|record| matches!(record.event, TestEvent::VaultRegistry(Event::VaultExitProgress { .. })))
+                    .count()
+            };
+            assert_eq!(progress_events(), 1);
+
+            // nothing is left to be replaced
+            assert_ok!(VaultRegistry::decrease_to_be_replaced_tokens(&id, &wrapped(50)));
+            assert_eq!(progress_events(), 1);
+        })
+    }
+}
+
+mod top_up_tests {
+    use super::{assert_eq, *};
+    use crate::{TopUpAllowance, TopUpAllowances, TopUpSource};
+
+    const FUNDER: AccountId = RICH_ID.account_id;
+
+    fn setup_undercollateralized_vault() -> DefaultVaultId<Test> {
+        let id = create_sample_vault();
+        convert_to.mock_safe(|currency, x| MockResult::Return(Ok(Amount::new(x.amount(), currency))));
+        let issue_tokens = DEFAULT_COLLATERAL / 2;
+        assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(
+            &id,
+            &wrapped(issue_tokens)
+        ));
+        assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(issue_tokens)));
+
+        // the collateral loses half its value, so 200_000 is required to be at the 200% threshold
+        convert_to.mock_safe(convert_with_exchange_rate(2));
+        assert_eq!(VaultRegistry::is_vault_below_secure_threshold(&id), Ok(true));
+        id
+    }
+
+    fn set_allowance(id: &DefaultVaultId<Test>, amount: u128, source: TopUpSource) {
+        assert_ok!(VaultRegistry::set_top_up_allowance(
+            RuntimeOrigin::signed(FUNDER),
+            id.clone(),
+            amount,
+            source
+        ));
+    }
+
+    #[test]
+    fn top_up_vault_is_capped_by_allowance() {
+        run_test(|| {
+            let id = setup_undercollateralized_vault();
+            set_allowance(&id, 60000, TopUpSource::FreeBalance);
+
+            assert_ok!(VaultRegistry::top_up_vault(
+                RuntimeOrigin::signed(OTHER_ID.account_id),
+                id.clone(),
+                FUNDER
+            ));
+
+            assert_eq!(
+                VaultRegistry::get_backing_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL + 60000))
+            );
+            assert_eq!(
+                currency::get_free_balance::<Test>(DEFAULT_COLLATERAL_CURRENCY, &FUNDER),
+                amount(RICH_COLLATERAL - 60000)
+            );
+            // the collateral is nominated by the funder rather than owned by the vault
+            assert_eq!(VaultRegistry::compute_collateral(&id), Ok(amount(DEFAULT_COLLATERAL)));
+            assert_emitted!(Event::TopUpVault {
+                vault_id: id.clone(),
+                funder: FUNDER,
+                amount: 60000,
+            });
+
+            // the allowance is used up
+            assert!(TopUpAllowances::<Test>::get(&id, FUNDER).is_none());
+            assert_noop!(
+                VaultRegistry::top_up_vault(RuntimeOrigin::signed(OTHER_ID.account_id), id, FUNDER),
+                TestError::NoTopUpAllowance
+            );
+        })
+    }
+
+    #[test]
+    fn top_up_vault_restores_secure_threshold() {
+        run_test(|| {
+            let id = setup_undercollateralized_vault();
+            set_allowance(&id, 150000, TopUpSource::FreeBalance);
+
+            assert_ok!(VaultRegistry::try_top_up_vault(&id, &FUNDER), amount(100000));
+            assert_eq!(
+                VaultRegistry::get_backing_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL + 100000))
+            );
+            assert_eq!(
+                TopUpAllowances::<Test>::get(&id, FUNDER),
+                Some(TopUpAllowance {
+                    amount: 50000,
+                    source: TopUpSource::FreeBalance
+                })
+            );
+
+            assert_noop!(
+                VaultRegistry::top_up_vault(RuntimeOrigin::signed(OTHER_ID.account_id), id, FUNDER),
+                TestError::VaultNotBelowSecureThreshold
+            );
+        })
+    }
+
+    #[test]
+    fn top_up_vault_is_measured_against_issued_tokens() {
+        run_test(|| {
+            let id = create_sample_vault();
+            convert_to.mock_safe(|currency, x| MockResult::Return(Ok(Amount::new(x.amount(), currency))));
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(50000)));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(40000)));
+
+            // 160_000 is required for the issued tokens, pending issues are not taken into account
+            convert_to.mock_safe(convert_with_exchange_rate(2));
+            set_allowance(&id, 150000, TopUpSource::FreeBalance);
+            assert_ok!(VaultRegistry::try_top_up_vault(&id, &FUNDER), amount(60000));
+            assert_eq!(VaultRegistry::is_vault_below_secure_threshold(&id), Ok(false));
+        })
+    }
+
+    #[test]
+    fn top_up_vault_from_lend_tokens_fails_without_lending_market() {
+        run_test(|| {
+            let id = setup_undercollateralized_vault();
+            set_allowance(&id, 150000, TopUpSource::LendTokens);
+
+            assert_noop!(
+                VaultRegistry::top_up_vault(RuntimeOrigin::signed(OTHER_ID.account_id), id, FUNDER),
+                DispatchError::Other("No lending market available")
+            );
+        })
+    }
+
+    #[test]
+    fn set_top_up_allowance_to_zero_removes_allowance() {
+        run_test(|| {
+            let id = create_sample_vault();
+            set_allowance(&id, 150000, TopUpSource::FreeBalance);
+            assert!(TopUpAllowances::<Test>::get(&id, FUNDER).is_some());
+
+            set_allowance(&id, 0, TopUpSource::FreeBalance);
+            assert!(TopUpAllowances::<Test>::get(&id, FUNDER).is_none());
+        })
+    }
+}
@@ -4,7 +4,8 @@ use currency::Amount;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    traits::Get,
+    traits::{ConstU32, Get},
+    BoundedVec,
 };
 pub use primitives::{VaultCurrencyPair, VaultId};
 use scale_info::TypeInfo;
@@ -18,6 +19,7 @@ use sp_runtime::{
 use mocktopus::macros::mockable;
 
 pub use bitcoin::{Address as BtcAddress, PublicKey as BtcPublicKey};
+use traits::NominationApi;
 
 /// Storage version.
 #[derive(Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
@@ -111,6 +113,9 @@ pub type DefaultVaultId<T> = VaultId<<T as frame_system::Config>::AccountId, Cur
 
 pub type DefaultVaultCurrencyPair<T> = VaultCurrencyPair<CurrencyId<T>>;
 
+/// Vaults to choose from in automatic vault selection.
+pub type CandidateVaults<T> = BoundedVec<DefaultVaultId<T>, ConstU32<{ crate::MAX_CANDIDATE_VAULTS }>>;
+
 pub mod v1 {
     use super::*;
 
@@ -133,6 +138,13 @@ pub enum VaultStatus {
 
     /// Vault has been liquidated
     Liquidated,
+
+    /// Vault is winding down - it does not accept new issue requests and its collateral
+    /// can be released once it no longer backs any tokens
+    Exiting,
+
+    /// Vault has completed its exit - its collateral was released
+    Exited,
 }
 
 impl Default for VaultStatus {
@@ -141,6 +153,24 @@ impl Default for VaultStatus {
     }
 }
 
+/// Where the funds for an automatic top-up of a vault's collateral are taken from.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum TopUpSource {
+    /// The free balance of the funder in the vault's collateral currency
+    FreeBalance,
+    /// Lend tokens of the funder, redeemed for the vault's collateral currency
+    LendTokens,
+}
+
+/// Collateral that a funder allows keepers to move into a vault that fell below its secure threshold.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct TopUpAllowance<Balance> {
+    /// Remaining amount of collateral that may be moved into the vault
+    pub amount: Balance,
+    /// Where the collateral is taken from
+    pub source: TopUpSource,
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct Vault<AccountId, BlockNumber, Balance, CurrencyId: Copy, UnsignedFixedPoint> {
@@ -221,6 +251,10 @@ impl<
     pub fn accepts_new_issues(&self) -> bool {
         matches!(self.status, VaultStatus::Active(true))
     }
+
+    pub fn is_exiting(&self) -> bool {
+        matches!(self.status, VaultStatus::Exiting)
+    }
 }
 
 pub type DefaultVault<T> = Vault<
@@ -233,6 +267,8 @@ pub type DefaultVault<T> = Vault<
 
 pub type DefaultSystemVault<T> = SystemVault<BalanceOf<T>, CurrencyId<T>>;
 
+pub type DefaultTopUpAllowance<T> = TopUpAllowance<BalanceOf<T>>;
+
 #[cfg_attr(any(test, feature = "integration-tests"), visibility::make(pub))]
 trait UpdatableVault<T: Config> {
     fn increase_issued(&mut self, tokens: &Amount<T>) -> DispatchResult;
@@ -403,6 +439,10 @@ impl<T: Config> RichVault<T> {
         Pallet::<T>::get_backing_collateral(&self.id())
     }
 
+    pub fn get_effective_collateral(&self) -> Result<Amount<T>, DispatchError> {
+        Pallet::<T>::get_effective_collateral(&self.id())
+    }
+
     pub fn get_secure_threshold(&self) -> Result<UnsignedFixedPoint<T>, DispatchError> {
         let global_threshold =
             Pallet::<T>::secure_collateral_threshold(&self.id().currencies).ok_or(Error::<T>::ThresholdNotSet)?;
@@ -413,16 +453,23 @@ impl<T: Config> RichVault<T> {
             .max(global_threshold))
     }
 
+    /// Collateral that can be issued against: the effective collateral that is neither used for the
+    /// backed tokens nor being unbonded by nominators
     pub fn get_free_collateral(&self) -> Result<Amount<T>, DispatchError> {
         let used_collateral = self.get_used_collateral(self.get_secure_threshold()?)?;
-        self.get_total_collateral()?.checked_sub(&used_collateral)
+        let unbonding_collateral = T::NominationApi::get_total_unbonding(&self.id());
+        self.get_effective_collateral()?
+            .checked_sub(&used_collateral)?
+            .saturating_sub(&unbonding_collateral)
     }
 
+    /// Value of the collateral (including the basket) used for the issued + to_be_issued tokens,
+    /// denominated in the collateral currency
     pub fn get_used_collateral(&self, threshold: UnsignedFixedPoint<T>) -> Result<Amount<T>, DispatchError> {
         let issued_tokens = self.backed_tokens()?;
         let issued_tokens_in_collateral = issued_tokens.convert_to(self.data.id.currencies.collateral)?;
         let used_collateral = issued_tokens_in_collateral.checked_mul(&threshold)?;
-        self.get_total_collateral()?.min(&used_collateral)
+        self.get_effective_collateral()?.min(&used_collateral)
     }
 
     pub fn issuable_tokens(&self) -> Result<Amount<T>, DispatchError> {
@@ -517,6 +564,20 @@ impl<T: Config> RichVault<T> {
         })
     }
 
+    pub(crate) fn set_exiting(&mut self) -> DispatchResult {
+        self.update(|v| {
+            v.status = VaultStatus::Exiting;
+            Ok(())
+        })
+    }
+
+    pub(crate) fn set_exited(&mut self) -> DispatchResult {
+        self.update(|v| {
+            v.status = VaultStatus::Exited;
+            Ok(())
+        })
+    }
+
     pub(crate) fn increase_liquidated_collateral(&mut self, amount: &Amount<T>) -> DispatchResult {
         self.update(|v| {
             v.liquidated_collateral = v
@@ -575,11 +636,16 @@ impl<T: Config> RichVault<T> {
 
         // we liquidate at most LIQUIDATION_THRESHOLD * collateral
         // this value is the amount of collateral held for the issued + to_be_issued
-        let liquidated_collateral = self.get_used_collateral(
+        let liquidated_value = self.get_used_collateral(
             Pallet::<T>::liquidation_collateral_threshold(&self.data.id.currencies)
                 .ok_or(Error::<T>::ThresholdNotSet)?,
         )?;
 
+        // the liquidated value is taken proportionally from the backing collateral and the basket
+        let effective_collateral = self.get_effective_collateral()?;
+        let liquidated_collateral =
+            Pallet::<T>::calculate_collateral(&self.get_total_collateral()?, &liquidated_value, &effective_collateral)?;
+
         // Clear `to_be_replaced` tokens, since the vault will have no more `issued` or `to_be_issued` tokens.
         let _ = Pallet::<T>::withdraw_replace_request(&self.data.id, &self.to_be_replaced_tokens())?;
         // the vault struct was modified in the call above - we need to re-fetch,
@@ -606,6 +672,20 @@ impl<T: Config> RichVault<T> {
         // slash collateral used for issued + to_be_issued to the liquidation vault
         self.slash_to_liquidation_vault(&liquidated_collateral_excluding_to_be_redeemed)?;
 
+        // seize the same share of the basket for the liquidation vault, and set aside the share
+        // for the to_be_redeemed tokens, which is released or confiscated like the liquidated collateral
+        let liquidated_value_excluding_to_be_redeemed = Pallet::<T>::calculate_collateral(
+            &liquidated_value,
+            &collateral_tokens.checked_sub(&self.to_be_redeemed_tokens())?,
+            &collateral_tokens,
+        )?;
+        Pallet::<T>::seize_basket_collateral(
+            &vault_id,
+            &liquidated_value_excluding_to_be_redeemed,
+            &liquidated_value.saturating_sub(&liquidated_value_excluding_to_be_redeemed)?,
+            &effective_collateral,
+        )?;
+
         // Copy all tokens to the liquidation vault
         let mut liquidation_vault = Pallet::<T>::get_rich_liquidation_vault(&self.data.id.currencies);
         liquidation_vault.increase_issued(&self.issued_tokens())?;
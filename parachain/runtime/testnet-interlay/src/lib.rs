@@ -63,6 +63,7 @@ pub use sp_runtime::{FixedU128, Perbill, Permill};
 // interBTC exports
 pub use btc_relay::{bitcoin, Call as BtcRelayCall, TARGET_SPACING};
 pub use constants::{currency::*, time::*};
+pub use oracle::OracleKey;
 pub use oracle_rpc_runtime_api::BalanceWrapper;
 pub use security::StatusCode;
 
@@ -577,6 +578,7 @@ impl orml_unknown_tokens::Config for Runtime {
 
 parameter_types! {
     pub const ParachainBlocksPerBitcoinBlock: BlockNumber = BITCOIN_BLOCK_SPACING;
+    pub const BitcoinPruningWindow: u32 = 2 * btc_relay::DIFFICULTY_ADJUSTMENT_INTERVAL;
 }
 
 impl btc_relay::Config for Runtime {
@@ -1070,6 +1072,7 @@ impl vault_registry::Config for Runtime {
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
     type NominationApi = Nomination;
+    type LendTokens = Loans;
 }
 
 impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
@@ -1083,6 +1086,8 @@ where
 impl oracle::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnExchangeRateChange = (vault_registry::PoolManager<Runtime>, Loans);
+    type TreasuryPalletId = TreasuryPalletId;
+    type DexPriceSource = dex::DexGeneralPriceSource;
     type WeightInfo = ();
 }
 
@@ -1091,6 +1096,7 @@ parameter_types! {
 }
 
 impl fee::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type FeePalletId = FeePalletId;
     type WeightInfo = ();
     type SignedFixedPoint = SignedFixedPoint;
@@ -1101,6 +1107,9 @@ impl fee::Config for Runtime {
     type VaultRewards = VaultRewards;
     type VaultStaking = VaultStaking;
     type OnSweep = currency::SweepFunds<Runtime, FeeAccount>;
+    type DexSwap = dex::DexSwapRouterSwap;
+    type Nomination = Nomination;
+    type MaxCompoundingNominators = ConstU32<32>;
     type MaxExpectedValue = MaxExpectedValue;
 }
 
@@ -1127,11 +1136,12 @@ impl replace::Config for Runtime {
     type WeightInfo = ();
 }
 
-pub use nomination::Event as NominationEvent;
+pub use nomination::{Event as NominationEvent, NominatorPosition, UnbondingRequest};
 
 impl nomination::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type MaxUnbondingChunks = ConstU32<32>;
 }
 
 impl clients_info::Config for Runtime {
@@ -1165,6 +1175,7 @@ impl loans::Config for Runtime {
     type RewardAssetId = GetNativeCurrencyId;
     type ReferenceAssetId = GetWrappedCurrencyId;
     type OnExchangeRateChange = vault_registry::PoolManager<Runtime>;
+    type LiquidationGuard = Oracle;
 }
 
 construct_runtime! {
@@ -1215,7 +1226,7 @@ construct_runtime! {
         Issue: issue::{Pallet, Call, Config<T>, Storage, Event<T>} = 63,
         Redeem: redeem::{Pallet, Call, Config<T>, Storage, Event<T>} = 64,
         Replace: replace::{Pallet, Call, Config<T>, Storage, Event<T>} = 65,
-        Fee: fee::{Pallet, Call, Config<T>, Storage} = 66,
+        Fee: fee::{Pallet, Call, Config<T>, Storage, Event<T>} = 66,
         // Refund: 67
         Nomination: nomination::{Pallet, Call, Config, Storage, Event<T>} = 68,
         ClientsInfo: clients_info::{Pallet, Call, Storage, Event<T>} = 69,
@@ -1276,8 +1287,21 @@ pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, RuntimeCall,
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
-pub type Executive =
-    frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
+pub type Executive = frame_executive::Executive<
+    Runtime,
+    Block,
+    frame_system::ChainContext<Runtime>,
+    Runtime,
+    AllPalletsWithSystem,
+    (
+        // Cumulative chainwork based fork choice
+        btc_relay::migration::v1::MigrateToV1<Runtime, BitcoinPruningWindow>,
+        // Block header pruning
+        btc_relay::migration::v2::MigrateToV2<Runtime, BitcoinPruningWindow>,
+        // Per-account index of nominations
+        staking::migration::v1::MigrateToV1<Runtime>,
+    ),
+>;
 
 #[cfg(not(feature = "disable-runtime-api"))]
 impl_runtime_apis! {
@@ -1464,7 +1488,13 @@ impl_runtime_apis! {
     impl oracle_rpc_runtime_api::OracleApi<
         Block,
         Balance,
-        CurrencyId
+        CurrencyId,
+        OracleKey,
+        UnsignedFixedPoint,
+        Moment,
+        AccountId,
+        oracle::OraclePerformance<Moment, UnsignedFixedPoint>,
+        oracle::AggregateInfo<AccountId, Moment, UnsignedFixedPoint>,
     > for Runtime {
         fn wrapped_to_collateral( amount: BalanceWrapper<Balance>, currency_id: CurrencyId) -> Result<BalanceWrapper<Balance>, DispatchError> {
             let result = Oracle::wrapped_to_collateral(amount.amount, currency_id)?;
@@ -1475,6 +1505,25 @@ impl_runtime_apis! {
             let result = Oracle::collateral_to_wrapped(amount.amount, currency_id)?;
             Ok(BalanceWrapper{amount:result})
         }
+        fn get_price_history(key: OracleKey) -> Vec<(Moment, UnsignedFixedPoint)> {
+            Oracle::get_price_history(&key).into_iter().map(|x| (x.timestamp, x.value)).collect()
+        }
+
+        fn get_twap(key: OracleKey, window: Moment) -> Result<UnsignedFixedPoint, DispatchError> {
+            Oracle::get_twap(&key, window)
+        }
+
+        fn get_oracle_performance(oracle_id: AccountId) -> oracle::OraclePerformance<Moment, UnsignedFixedPoint> {
+            Oracle::get_oracle_performance(&oracle_id)
+        }
+
+        fn get_oracle_keys() -> Vec<OracleKey> {
+            Oracle::get_oracle_keys()
+        }
+
+        fn get_aggregate_info(key: OracleKey) -> oracle::AggregateInfo<AccountId, Moment, UnsignedFixedPoint> {
+            Oracle::get_aggregate_info(&key)
+        }
     }
 
     impl vault_registry_rpc_runtime_api::VaultRegistryApi<
@@ -1618,6 +1667,17 @@ impl_runtime_apis! {
         ) -> Result<UnsignedFixedPoint, DispatchError> {
             runtime_common::estimate_vault_reward_rate::<Runtime, VaultAnnuityInstance, VaultStaking, VaultCapacity, _>(vault_id)
         }
+
+        fn get_vault_commission(
+            vault_id: VaultId,
+        ) -> reward_rpc_runtime_api::VaultCommission<UnsignedFixedPoint, BlockNumber> {
+            let pending = Fee::get_pending_commission(&vault_id);
+            reward_rpc_runtime_api::VaultCommission {
+                current: Fee::get_effective_commission_rate(&vault_id),
+                pending: pending.map(|(commission, _)| commission),
+                apply_height: pending.map(|(_, apply_height)| apply_height),
+            }
+        }
     }
 
 
@@ -1634,6 +1694,10 @@ impl_runtime_apis! {
         fn get_vault_issue_requests(vault_id: AccountId) -> Vec<H256> {
             Issue::get_issue_requests_for_vault(vault_id)
         }
+
+        fn get_issue_group_requests(group_id: H256) -> Vec<IssueRequest<AccountId, BlockNumber, Balance, CurrencyId>> {
+            Issue::get_issue_group_requests(group_id)
+        }
     }
 
     impl redeem_rpc_runtime_api::RedeemApi<
@@ -1666,6 +1730,21 @@ impl_runtime_apis! {
         }
     }
 
+    impl nomination_rpc_runtime_api::NominationApi<
+        Block,
+        AccountId,
+        UnbondingRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+        NominatorPosition<AccountId, Nonce, Balance, CurrencyId>
+    > for Runtime {
+        fn get_unbonding_requests(account_id: AccountId) -> Vec<UnbondingRequest<AccountId, BlockNumber, Balance, CurrencyId>> {
+            Nomination::get_unbonding_requests(account_id)
+        }
+
+        fn get_nominator_positions(account_id: AccountId) -> Result<Vec<NominatorPosition<AccountId, Nonce, Balance, CurrencyId>>, DispatchError> {
+            Nomination::get_nominator_positions(account_id)
+        }
+    }
+
     impl loans_rpc_runtime_api::LoansApi<
         Block,
         AccountId,
@@ -1,9 +1,12 @@
 use super::{
-    parameter_types, Balance, CurrencyId, DexGeneral, DexStable, Get, PalletId, Runtime, RuntimeEvent, StablePoolId,
-    Timestamp, Tokens,
+    parameter_types, AccountId, Balance, CurrencyId, DexGeneral, DexStable, DexSwapRouter, Get, PalletId, Runtime,
+    RuntimeEvent, StablePoolId, Timestamp, Tokens,
 };
+use dex_swap_router::{Route, StablePath, StableSwapMode};
+use sp_runtime::DispatchError;
+use sp_std::{vec, vec::Vec};
 
-pub use dex_general::{AssetBalance, GenerateLpAssetId, PairInfo};
+pub use dex_general::{AssetBalance, ExportDexGeneral, GenerateLpAssetId, PairInfo};
 pub use dex_stable::traits::{StablePoolLpCurrencyIdGenerate, ValidateCurrency};
 
 parameter_types! {
@@ -28,6 +31,61 @@ impl dex_general::Config for Runtime {
     type WeightInfo = ();
 }
 
+pub struct DexGeneralPriceSource;
+impl traits::DexPriceSource<CurrencyId, Balance> for DexGeneralPriceSource {
+    fn get_amount_out(amount_in: Balance, path: &[CurrencyId]) -> Option<Balance> {
+        DexGeneral::get_amount_out_by_path(amount_in, path)
+            .ok()?
+            .last()
+            .copied()
+    }
+}
+
+pub struct DexSwapRouterSwap;
+impl DexSwapRouterSwap {
+    /// Swaps directly through a stable pool that holds both currencies if there is one, otherwise
+    /// through the pairs along the path.
+    fn routes(path: &[CurrencyId]) -> Vec<Route<StablePoolId, CurrencyId, CurrencyId>> {
+        if let [from_currency, to_currency] = *path {
+            let stable_pool_id = dex_stable::Pools::<Runtime>::iter_keys().find(|pool_id| {
+                DexStable::get_currency_index(*pool_id, from_currency).is_some()
+                    && DexStable::get_currency_index(*pool_id, to_currency).is_some()
+            });
+            if let Some(pool_id) = stable_pool_id {
+                return vec![Route::Stable(StablePath {
+                    pool_id,
+                    base_pool_id: pool_id,
+                    mode: StableSwapMode::Single,
+                    from_currency,
+                    to_currency,
+                })];
+            }
+        }
+        vec![Route::Normal(path.to_vec())]
+    }
+}
+
+impl traits::DexSwap<AccountId, CurrencyId, Balance> for DexSwapRouterSwap {
+    fn is_available() -> bool {
+        true
+    }
+
+    fn swap_exact_amount_in(
+        who: &AccountId,
+        amount_in: Balance,
+        amount_out_min: Balance,
+        path: &[CurrencyId],
+    ) -> Result<Balance, DispatchError> {
+        DexSwapRouter::inner_swap_exact_token_for_tokens_through_stable_pool(
+            who,
+            amount_in,
+            amount_out_min,
+            &Self::routes(path),
+            who,
+        )
+    }
+}
+
 pub struct PoolLpGenerate;
 impl StablePoolLpCurrencyIdGenerate<CurrencyId, StablePoolId> for PoolLpGenerate {
     fn generate_by_pool_id(pool_id: StablePoolId) -> CurrencyId {
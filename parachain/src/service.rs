@@ -69,8 +69,17 @@ pub trait RuntimeApiCollection:
     + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
     + cumulus_primitives_core::CollectCollationInfo<Block>
     + btc_relay_rpc_runtime_api::BtcRelayApi<Block, H256Le>
-    + oracle_rpc_runtime_api::OracleApi<Block, Balance, CurrencyId>
-    + vault_registry_rpc_runtime_api::VaultRegistryApi<
+    + oracle_rpc_runtime_api::OracleApi<
+        Block,
+        Balance,
+        CurrencyId,
+        oracle::Key,
+        UnsignedFixedPoint,
+        Moment,
+        AccountId,
+        oracle::OraclePerformance<Moment, UnsignedFixedPoint>,
+        oracle::AggregateInfo<AccountId, Moment, UnsignedFixedPoint>,
+    > + vault_registry_rpc_runtime_api::VaultRegistryApi<
         Block,
         VaultId<AccountId, CurrencyId>,
         Balance,
@@ -93,6 +102,11 @@ pub trait RuntimeApiCollection:
         AccountId,
         H256,
         replace::ReplaceRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+    > + nomination_rpc_runtime_api::NominationApi<
+        Block,
+        AccountId,
+        nomination::UnbondingRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+        nomination::NominatorPosition<AccountId, Nonce, Balance, CurrencyId>,
     > + reward_rpc_runtime_api::RewardApi<
         Block,
         AccountId,
@@ -121,8 +135,17 @@ where
         + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
         + cumulus_primitives_core::CollectCollationInfo<Block>
         + btc_relay_rpc_runtime_api::BtcRelayApi<Block, H256Le>
-        + oracle_rpc_runtime_api::OracleApi<Block, Balance, CurrencyId>
-        + vault_registry_rpc_runtime_api::VaultRegistryApi<
+        + oracle_rpc_runtime_api::OracleApi<
+            Block,
+            Balance,
+            CurrencyId,
+            oracle::Key,
+            UnsignedFixedPoint,
+            Moment,
+            AccountId,
+            oracle::OraclePerformance<Moment, UnsignedFixedPoint>,
+            oracle::AggregateInfo<AccountId, Moment, UnsignedFixedPoint>,
+        > + vault_registry_rpc_runtime_api::VaultRegistryApi<
             Block,
             VaultId<AccountId, CurrencyId>,
             Balance,
@@ -145,6 +168,11 @@ where
             AccountId,
             H256,
             replace::ReplaceRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+        > + nomination_rpc_runtime_api::NominationApi<
+            Block,
+            AccountId,
+            nomination::UnbondingRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+            nomination::NominatorPosition<AccountId, Nonce, Balance, CurrencyId>,
         > + reward_rpc_runtime_api::RewardApi<
             Block,
             AccountId,
@@ -303,6 +303,75 @@ pub mod replace {
     }
 }
 
+pub mod nomination {
+    use super::*;
+
+    // Due to a known bug in serde we need to specify how u128 is (de)serialized.
+    // See https://github.com/paritytech/substrate/issues/4641
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize, Eq))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct UnbondingRequest<AccountId, BlockNumber, Balance, CurrencyId: Copy> {
+        /// the vault the collateral is withdrawn from
+        pub vault_id: VaultId<AccountId, CurrencyId>,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// the amount of collateral being unbonded
+        pub amount: Balance,
+        /// the *active* block height from which the collateral can be withdrawn
+        pub unlock_height: BlockNumber,
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize, Eq))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct NominatorReward<Balance, CurrencyId> {
+        /// the currency of the reward
+        pub currency_id: CurrencyId,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// the amount that can currently be claimed
+        pub amount: Balance,
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize, Eq))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct NominatorPosition<AccountId, Index, Balance, CurrencyId: Copy> {
+        /// the nominated vault
+        pub vault_id: VaultId<AccountId, CurrencyId>,
+        /// the nonce of the vault's staking pool holding the stake
+        pub nonce: Index,
+        /// whether the vault's staking pool nonce has advanced past `nonce`, e.g. after a liquidation
+        pub stale: bool,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// the collateral staked after slashing
+        pub stake: Balance,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// the collateral slashed since the stake last changed
+        pub slashed: Balance,
+        #[cfg_attr(
+            feature = "std",
+            serde(bound(
+                deserialize = "Balance: std::str::FromStr, CurrencyId: Deserialize<'de>",
+                serialize = "Balance: std::fmt::Display, CurrencyId: Serialize"
+            ))
+        )]
+        /// the claimable rewards per currency
+        pub rewards: Vec<NominatorReward<Balance, CurrencyId>>,
+    }
+}
+
 pub mod oracle {
     use super::*;
 
@@ -313,6 +382,40 @@ pub mod oracle {
         ExchangeRate(CurrencyId),
         FeeEstimation,
     }
+
+    #[derive(Encode, Decode, Default, Clone, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct OraclePerformance<Moment, UnsignedFixedPoint> {
+        /// the number of accepted `feed_values` calls
+        pub submissions: u32,
+        /// the number of submissions made after the previous one had expired
+        pub stale_submissions: u32,
+        /// the number of submissions that deviated too far from the aggregate
+        pub deviating_submissions: u32,
+        /// stale and deviating submissions since the oracle was last slashed
+        pub faults: u32,
+        /// the time of the last submission
+        pub last_submission: Moment,
+        /// the largest relative deviation from the aggregate in the last submission
+        pub last_deviation: UnsignedFixedPoint,
+    }
+
+    #[derive(Encode, Decode, Default, Clone, Eq, PartialEq, Debug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct AggregateInfo<AccountId, Moment, UnsignedFixedPoint> {
+        /// the current aggregate, if any
+        pub aggregate: Option<UnsignedFixedPoint>,
+        /// the time until which the aggregate is valid
+        pub valid_until: Option<Moment>,
+        /// the maximum delay for a reported value to be included in the aggregate
+        pub max_delay: Moment,
+        /// true if the aggregate is derived from the DEX fallback source
+        pub is_fallback: bool,
+        /// the latest value of every feeder as (feeder, timestamp, value)
+        pub raw_values: Vec<(AccountId, Moment, UnsignedFixedPoint)>,
+    }
 }
 
 /// An index to a block.
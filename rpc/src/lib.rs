@@ -6,8 +6,12 @@
 #![warn(missing_docs)]
 
 use primitives::{
-    issue::IssueRequest, redeem::RedeemRequest, replace::ReplaceRequest, AccountId, Balance, Block, BlockNumber,
-    CurrencyId, H256Le, Hash, Nonce, StablePoolId, VaultId,
+    issue::IssueRequest,
+    nomination::{NominatorPosition, UnbondingRequest},
+    oracle::{AggregateInfo, Key as OracleKey, OraclePerformance},
+    redeem::RedeemRequest,
+    replace::ReplaceRequest,
+    AccountId, Balance, Block, BlockNumber, CurrencyId, H256Le, Hash, Moment, Nonce, StablePoolId, VaultId,
 };
 use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApiServer};
 pub use sc_rpc_api::DenyUnsafe;
@@ -45,7 +49,17 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: btc_relay_rpc::BtcRelayRuntimeApi<Block, H256Le>,
-    C::Api: oracle_rpc::OracleRuntimeApi<Block, Balance, CurrencyId>,
+    C::Api: oracle_rpc::OracleRuntimeApi<
+        Block,
+        Balance,
+        CurrencyId,
+        OracleKey,
+        FixedU128,
+        Moment,
+        AccountId,
+        OraclePerformance<Moment, FixedU128>,
+        AggregateInfo<AccountId, Moment, FixedU128>,
+    >,
     C::Api: vault_registry_rpc::VaultRegistryRuntimeApi<
         Block,
         VaultId<AccountId, CurrencyId>,
@@ -68,6 +82,12 @@ where
         H256,
         ReplaceRequest<AccountId, BlockNumber, Balance, CurrencyId>,
     >,
+    C::Api: nomination_rpc::NominationRuntimeApi<
+        Block,
+        AccountId,
+        UnbondingRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+        NominatorPosition<AccountId, Nonce, Balance, CurrencyId>,
+    >,
     C::Api: escrow_rpc::EscrowRuntimeApi<Block, AccountId, BlockNumber, Balance>,
     C::Api: reward_rpc::RewardRuntimeApi<
         Block,
@@ -90,6 +110,7 @@ where
     use escrow_rpc::{Escrow, EscrowApiServer};
     use issue_rpc::{Issue, IssueApiServer};
     use loans_rpc::{Loans, LoansApiServer};
+    use nomination_rpc::{Nomination, NominationApiServer};
     use oracle_rpc::{Oracle, OracleApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use redeem_rpc::{Redeem, RedeemApiServer};
@@ -134,6 +155,8 @@ where
 
     module.merge(Replace::new(client.clone()).into_rpc())?;
 
+    module.merge(Nomination::new(client.clone()).into_rpc())?;
+
     module.merge(Loans::new(client.clone()).into_rpc())?;
 
     module.merge(DexGeneral::new(client.clone()).into_rpc())?;
@@ -34,6 +34,7 @@ use self::redeem_testing_utils::USER_BTC_ADDRESS;
 pub use issue::{types::IssueRequestExt, IssueRequest, IssueRequestStatus};
 pub use loans::{InterestRateModel, Market, MarketState};
 pub use loans_testing_utils::activate_lending_and_mint;
+pub use nomination::UnbondingRequest;
 pub use oracle::OracleKey;
 pub use redeem::{types::RedeemRequestExt, RedeemRequest};
 pub use replace::{types::ReplaceRequestExt, ReplaceRequest};
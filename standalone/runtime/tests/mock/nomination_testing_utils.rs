@@ -100,6 +100,18 @@ pub fn assert_withdraw_nominator_collateral(nominator_id: AccountId, vault_id: &
     assert_ok!(withdraw_nominator_collateral(nominator_id, vault_id, amount_dot));
 }
 
+pub fn set_unbonding_period(period: BlockNumber) {
+    assert_ok!(RuntimeCall::Nomination(NominationCall::set_unbonding_period { period })
+        .dispatch(<Runtime as frame_system::Config>::RuntimeOrigin::root()));
+}
+
+pub fn withdraw_unbonded(nominator_id: AccountId, vault_id: &VaultId) -> DispatchResultWithPostInfo {
+    RuntimeCall::Nomination(NominationCall::withdraw_unbonded {
+        vault_id: vault_id.clone(),
+    })
+    .dispatch(origin_of(nominator_id))
+}
+
 pub fn assert_total_nominated_collateral_is(vault_id: &VaultId, amount_collateral: Amount<Runtime>) {
     let nominated_collateral = NominationPallet::get_total_nominated_collateral(vault_id).unwrap();
     assert_eq!(nominated_collateral, amount_collateral);
@@ -548,6 +548,32 @@ fn integration_test_nominator_withdrawal_request_reduces_issuable_tokens() {
     });
 }
 
+#[test]
+fn integration_test_unbonding_collateral_is_not_issuable() {
+    test_with_nomination_enabled_and_vault_opted_in(|vault_id| {
+        set_unbonding_period(DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        let issuance_capacity_before_nomination =
+            VaultRegistryPallet::get_issuable_tokens_from_vault(&vault_id).unwrap();
+        assert_nominate_collateral(&vault_id, account_of(USER), default_nomination(&vault_id));
+        assert_withdraw_nominator_collateral(account_of(USER), &vault_id, default_nomination(&vault_id));
+
+        // the unbonding collateral keeps backing the vault, but adds no issuance capacity
+        assert_eq!(
+            VaultRegistryPallet::get_issuable_tokens_from_vault(&vault_id).unwrap(),
+            issuance_capacity_before_nomination
+        );
+
+        // issuing up to the capacity does not block the release of the unbonding collateral
+        let (issue_id, _) = issue_testing_utils::request_issue(&vault_id, issuance_capacity_before_nomination);
+        issue_testing_utils::execute_issue(issue_id);
+        SecurityPallet::set_active_block_number(
+            SecurityPallet::active_block_number() + DEFAULT_NOMINATOR_UNBONDING_PERIOD,
+        );
+        assert_ok!(withdraw_unbonded(account_of(USER), &vault_id));
+        assert!(NominationPallet::get_unbonding_requests(account_of(USER)).is_empty());
+    });
+}
+
 #[test]
 fn integration_test_nominator_withdrawal_below_collateralization_threshold_fails() {
     test_with_nomination_enabled(|vault_id| {
@@ -655,3 +681,189 @@ fn integration_test_rewards_are_preserved_on_collateral_withdrawal() {
         );
     })
 }
+
+#[test]
+fn integration_test_nominator_withdrawal_is_unbonded() {
+    test_with_nomination_enabled_and_vault_opted_in(|vault_id| {
+        set_unbonding_period(DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_nominate_collateral(&vault_id, account_of(USER), default_nomination(&vault_id));
+
+        let backing_collateral_before = VaultRegistryPallet::get_backing_collateral(&vault_id).unwrap();
+        let user_state_before = UserData::get(USER);
+
+        assert_withdraw_nominator_collateral(account_of(USER), &vault_id, default_nomination(&vault_id));
+
+        // the collateral keeps backing the vault until it is released
+        assert_eq!(
+            VaultRegistryPallet::get_backing_collateral(&vault_id).unwrap(),
+            backing_collateral_before
+        );
+        assert_eq!(
+            get_nominator_collateral(&vault_id, account_of(USER)),
+            default_nomination(&vault_id)
+        );
+        assert_eq!(UserData::get(USER), user_state_before);
+        assert_eq!(
+            NominationPallet::get_unbonding_requests(account_of(USER)),
+            vec![UnbondingRequest {
+                vault_id: vault_id.clone(),
+                amount: DEFAULT_NOMINATION,
+                unlock_height: 1 + DEFAULT_NOMINATOR_UNBONDING_PERIOD,
+            }]
+        );
+
+        assert_noop!(
+            withdraw_unbonded(account_of(USER), &vault_id),
+            NominationError::NoUnbondedCollateral
+        );
+
+        SecurityPallet::set_active_block_number(1 + DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_ok!(withdraw_unbonded(account_of(USER), &vault_id));
+
+        assert_eq!(
+            VaultRegistryPallet::get_backing_collateral(&vault_id).unwrap(),
+            backing_collateral_before - default_nomination(&vault_id)
+        );
+        assert_eq!(
+            get_nominator_collateral(&vault_id, account_of(USER)),
+            Amount::new(0, vault_id.collateral_currency())
+        );
+        assert_eq!(
+            UserData::get(USER).balances[&vault_id.collateral_currency()].free,
+            user_state_before.balances[&vault_id.collateral_currency()].free + default_nomination(&vault_id)
+        );
+        assert!(NominationPallet::get_unbonding_requests(account_of(USER)).is_empty());
+    })
+}
+
+#[test]
+fn integration_test_nominator_cannot_unbond_more_than_nominated() {
+    test_with_nomination_enabled_and_vault_opted_in(|vault_id| {
+        set_unbonding_period(DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_nominate_collateral(&vault_id, account_of(USER), default_nomination(&vault_id));
+        assert_withdraw_nominator_collateral(account_of(USER), &vault_id, default_nomination(&vault_id));
+
+        assert_noop!(
+            withdraw_nominator_collateral(
+                account_of(USER),
+                &vault_id,
+                Amount::new(1, vault_id.collateral_currency())
+            ),
+            NominationError::UnbondingExceedsStake
+        );
+    })
+}
+
+#[test]
+fn integration_test_nominator_unbonding_chunks_are_bounded() {
+    use frame_support::traits::Get;
+    test_with_nomination_enabled_and_vault_opted_in(|vault_id| {
+        set_unbonding_period(DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_nominate_collateral(&vault_id, account_of(USER), default_nomination(&vault_id));
+
+        let max_chunks = <Runtime as nomination::Config>::MaxUnbondingChunks::get();
+        for height in 1..=max_chunks {
+            SecurityPallet::set_active_block_number(height);
+            assert_withdraw_nominator_collateral(account_of(USER), &vault_id, vault_id.collateral(1));
+        }
+        // chunks unlocking at the same height are merged
+        assert_withdraw_nominator_collateral(account_of(USER), &vault_id, vault_id.collateral(1));
+
+        SecurityPallet::set_active_block_number(max_chunks + 1);
+        assert_noop!(
+            withdraw_nominator_collateral(account_of(USER), &vault_id, vault_id.collateral(1)),
+            NominationError::TooManyUnbondingChunks
+        );
+        assert_eq!(
+            NominationPallet::get_unbonding_requests(account_of(USER)).len(),
+            max_chunks as usize
+        );
+    })
+}
+
+#[test]
+fn integration_test_unbonded_collateral_is_checked_against_the_threshold_on_release() {
+    test_with_nomination_enabled(|vault_id| {
+        assert_ok!(RuntimeCall::Nomination(NominationCall::withdraw_collateral {
+            vault_id: vault_id.clone(),
+            index: None,
+            amount: 750000
+        })
+        .dispatch(origin_of(account_of(VAULT))));
+        assert_nomination_opt_in(&vault_id);
+        set_unbonding_period(DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_nominate_collateral(&vault_id, account_of(USER), default_nomination(&vault_id));
+        assert_withdraw_nominator_collateral(account_of(USER), &vault_id, default_nomination(&vault_id));
+
+        set_collateral_exchange_rate(&vault_id, FixedU128::checked_from_integer(3u128).unwrap());
+        SecurityPallet::set_active_block_number(1 + DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_noop!(
+            withdraw_unbonded(account_of(USER), &vault_id),
+            NominationError::CannotWithdrawCollateral
+        );
+    });
+}
+
+#[test]
+fn integration_test_unbonding_is_cleared_when_nominators_are_refunded() {
+    test_with_nomination_enabled_and_vault_opted_in(|vault_id| {
+        set_unbonding_period(DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_nominate_collateral(&vault_id, account_of(USER), default_nomination(&vault_id));
+        assert_withdraw_nominator_collateral(account_of(USER), &vault_id, default_nomination(&vault_id));
+        assert_eq!(
+            NominationPallet::get_total_unbonding(&vault_id),
+            default_nomination(&vault_id)
+        );
+
+        // opting out advances the nonce and refunds the nominator
+        assert_ok!(nomination_opt_out(&vault_id));
+        assert!(NominationPallet::get_unbonding_requests(account_of(USER)).is_empty());
+        assert_eq!(NominationPallet::get_total_unbonding(&vault_id), vault_id.collateral(0));
+
+        SecurityPallet::set_active_block_number(1 + DEFAULT_NOMINATOR_UNBONDING_PERIOD);
+        assert_ok!(withdraw_unbonded(account_of(USER), &vault_id));
+        assert_noop!(
+            withdraw_unbonded(account_of(USER), &vault_id),
+            NominationError::NoUnbondedCollateral
+        );
+    })
+}
+
+#[test]
+fn integration_test_nominator_positions() {
+    test_with_nomination_enabled_and_vault_opted_in(|vault_id| {
+        assert_nominate_collateral(&vault_id, account_of(USER), default_nomination(&vault_id));
+
+        let (issue_id, _) = issue_testing_utils::request_issue(&vault_id, vault_id.wrapped(400000));
+        issue_testing_utils::execute_issue(issue_id);
+
+        let positions = NominationPallet::get_nominator_positions(account_of(USER)).unwrap();
+        assert_eq!(positions.len(), 1);
+        let position = &positions[0];
+        assert_eq!(position.vault_id, vault_id);
+        assert_eq!(position.nonce, 0);
+        assert!(!position.stale);
+        assert_eq!(position.stake, DEFAULT_NOMINATION);
+        assert_eq!(position.slashed, 0);
+        let wrapped_reward = position
+            .rewards
+            .iter()
+            .find(|reward| reward.currency_id == vault_id.wrapped_currency())
+            .unwrap();
+        assert!(wrapped_reward.amount > 0);
+        assert_eq!(
+            wrapped_reward.amount,
+            FeePallet::compute_vault_rewards(&vault_id, &account_of(USER), vault_id.wrapped_currency())
+                .unwrap()
+                .amount()
+        );
+
+        // opting out advances the nonce, leaving the stake in a stale pool
+        assert_ok!(nomination_opt_out(&vault_id));
+        let positions = NominationPallet::get_nominator_positions(account_of(USER)).unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].nonce, 0);
+        assert!(positions[0].stale);
+        assert_eq!(positions[0].stake, DEFAULT_NOMINATION);
+    })
+}
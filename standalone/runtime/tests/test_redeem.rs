@@ -465,22 +465,18 @@ mod spec_based_tests {
                 let redeem = RedeemPallet::get_open_redeem_request_from_id(&redeem_id).unwrap();
                 let user_btc_address = BtcAddress::P2PKH(H160([2; 20]));
 
-                // The `rawTx` MUST decode to a valid transaction that transfers exactly the amount specified in the
-                // `RedeemRequest` struct.
+                // The `rawTx` MUST decode to a valid transaction that transfers at most the amount specified in the
+                // `RedeemRequest` struct. Only the redeemer may execute a transaction that transfers less.
+                ExecuteRedeemBuilder::new(redeem_id)
+                    .with_amount(redeem.amount_btc().with_amount(|x| x - 1))
+                    .with_submitter(VAULT)
+                    .assert_noop(RedeemError::InvalidExecutor);
                 let mut current_block = assert_redeem_error(
-                    redeem_id,
-                    user_btc_address,
-                    redeem.amount_btc().with_amount(|x| x - 1),
-                    redeem_id,
-                    1,
-                    BTCRelayError::InvalidPaymentAmount,
-                );
-                current_block = assert_redeem_error(
                     redeem_id,
                     user_btc_address,
                     redeem.amount_btc().with_amount(|x| x + 1),
                     redeem_id,
-                    current_block,
+                    1,
                     BTCRelayError::InvalidPaymentAmount,
                 );
 
@@ -1152,18 +1148,55 @@ mod spec_based_tests {
 
 mod execute_redeem_payment_limits {
     use super::{assert_eq, *};
+    use redeem::RedeemRequestStatus;
 
     #[test]
-    fn integration_test_redeem_polka_btc_execute_underpayment_fails() {
+    fn integration_test_redeem_polka_btc_execute_underpayment_by_vault_fails() {
         test_with(|vault_id| {
             let redeem_id = setup_redeem(vault_id.wrapped(10_000), USER, &vault_id);
             let redeem = RedeemPallet::get_open_redeem_request_from_id(&redeem_id).unwrap();
 
-            assert_noop!(
-                ExecuteRedeemBuilder::new(redeem_id)
-                    .with_amount(redeem.amount_btc().with_amount(|x| x - 1))
-                    .execute(),
-                BTCRelayError::InvalidPaymentAmount
+            ExecuteRedeemBuilder::new(redeem_id)
+                .with_amount(redeem.amount_btc().with_amount(|x| x - 1))
+                .with_submitter(VAULT)
+                .assert_noop(RedeemError::InvalidExecutor);
+        });
+    }
+
+    #[test]
+    fn integration_test_redeem_polka_btc_execute_underpayment_by_redeemer_succeeds() {
+        test_with(|vault_id| {
+            let issued_tokens = vault_id.wrapped(10_000);
+            let redeem_id = setup_redeem(issued_tokens, USER, &vault_id);
+            let redeem = RedeemPallet::get_open_redeem_request_from_id(&redeem_id).unwrap();
+            let amount_transferred = redeem.amount_btc().with_amount(|x| x / 2);
+
+            ExecuteRedeemBuilder::new(redeem_id)
+                .with_amount(amount_transferred)
+                .assert_execute();
+
+            let executed_redeem = RedeemPallet::redeem_requests(redeem_id).unwrap();
+            assert_eq!(executed_redeem.status, RedeemRequestStatus::Completed);
+            assert_eq!(executed_redeem.amount_btc(), amount_transferred);
+
+            let outstanding_amount = redeem.amount_btc() - amount_transferred;
+            let outstanding_fee = redeem.fee() - executed_redeem.fee();
+            let punishment_fee =
+                FeePallet::get_punishment_fee(&outstanding_amount.convert_to(vault_id.collateral_currency()).unwrap())
+                    .unwrap();
+
+            assert_eq!(
+                ParachainState::get(&vault_id),
+                ParachainState::get_default(&vault_id).with_changes(|user, vault, _, fee_pool| {
+                    // only the paid part is redeemed, the vault is punished for the remainder
+                    vault.issued -= amount_transferred + redeem.transfer_fee_btc();
+                    vault.backing_collateral -= punishment_fee;
+                    (*user.balances.get_mut(&vault_id.collateral_currency()).unwrap()).free += punishment_fee;
+                    (*user.balances.get_mut(&vault_id.wrapped_currency()).unwrap()).free -=
+                        issued_tokens - outstanding_amount - outstanding_fee;
+                    *fee_pool.rewards_for(&vault_id) += executed_redeem.fee();
+                    consume_to_be_replaced(vault, redeem.amount_btc() + redeem.transfer_fee_btc());
+                })
             );
         });
     }
@@ -1,11 +1,18 @@
 mod mock;
 
 use currency::Amount;
+use frame_support::migration::put_storage_value;
+use interbtc_runtime_standalone::Timestamp;
 use mock::{assert_eq, *};
+use sp_consensus_aura::{Slot, SlotDuration};
+use sp_timestamp::Timestamp as SlotTimestamp;
 
 use crate::{
-    loans_testing_utils::activate_lending_and_mint,
-    mock::issue_testing_utils::{execute_issue, request_issue},
+    loans_testing_utils::{activate_lending_and_mint, deposit_and_borrow},
+    mock::{
+        issue_testing_utils::{execute_issue, request_issue},
+        nomination_testing_utils::{assert_nomination_opt_in, enable_nomination, get_nominator_collateral},
+    },
 };
 
 pub const USER: [u8; 32] = ALICE;
@@ -446,3 +453,107 @@ fn integration_test_vault_registry_liquidation_recovery_works() {
         deposit_collateral_and_issue(vault_id);
     });
 }
+
+#[test]
+fn integration_test_vault_registry_lend_token_collateral_accrues_interest() {
+    ExtBuilder::build().execute_with(|| {
+        SecurityPallet::set_active_block_number(1);
+        // The timestamp has to be non-zero for interest to start accruing
+        Timestamp::set_timestamp(1_000);
+        for currency_id in iter_collateral_currencies().filter(|c| !c.is_lend_token()) {
+            assert_ok!(OraclePallet::_set_exchange_rate(currency_id, FixedU128::one()));
+        }
+        activate_lending_and_mint(Token(DOT), LendToken(1));
+        let vault_id = PrimitiveVaultId::new(account_of(VAULT), LendToken(1), Token(IBTC));
+        CoreVaultData::force_to(&vault_id, default_vault_state(&vault_id));
+
+        let initial_collateralization =
+            VaultRegistryPallet::get_collateralization_from_vault(vault_id.clone(), false).unwrap();
+        let initial_issuable_tokens = VaultRegistryPallet::get_issuable_tokens_from_vault(&vault_id).unwrap();
+
+        // borrow from the market so that suppliers, including the vault, earn interest
+        deposit_and_borrow(account_of(USER), Amount::new(1_000_000_000_000_000, Token(DOT)));
+
+        // move to a future slot so that a meaningful amount of interest accrues
+        let slot_duration = SlotDuration::from_millis(AuraPallet::slot_duration());
+        let slot_to_set = Slot::from_timestamp(SlotTimestamp::try_from(1000000000000000).unwrap(), slot_duration);
+        put_storage_value(b"Aura", b"CurrentSlot", &[], slot_to_set);
+        Timestamp::set_timestamp(*slot_to_set * AuraPallet::slot_duration());
+
+        // the amount of locked lend tokens is unchanged, but their value in DOT has grown
+        let backing_collateral = VaultRegistryPallet::get_backing_collateral(&vault_id).unwrap();
+        assert_eq!(backing_collateral, default_vault_backing_collateral(LendToken(1)));
+        assert!(
+            VaultRegistryPallet::get_collateralization_from_vault(vault_id.clone(), false).unwrap()
+                > initial_collateralization
+        );
+        assert!(VaultRegistryPallet::get_issuable_tokens_from_vault(&vault_id)
+            .unwrap()
+            .gt(&initial_issuable_tokens)
+            .unwrap());
+    });
+}
+
+#[test]
+fn integration_test_vault_registry_top_up_vault_from_lend_tokens() {
+    ExtBuilder::build().execute_with(|| {
+        SecurityPallet::set_active_block_number(1);
+        for currency_id in iter_collateral_currencies().filter(|c| !c.is_lend_token()) {
+            assert_ok!(OraclePallet::_set_exchange_rate(currency_id, FixedU128::one()));
+        }
+        activate_lending_and_mint(Token(DOT), LendToken(1));
+        let vault_id = PrimitiveVaultId::new(account_of(VAULT), Token(DOT), Token(IBTC));
+        CoreVaultData::force_to(&vault_id, default_vault_state(&vault_id));
+
+        // top-ups are deposited as nominated collateral of the funder
+        enable_nomination();
+        assert_nomination_opt_in(&vault_id);
+
+        assert_ok!(RuntimeCall::VaultRegistry(VaultRegistryCall::set_top_up_allowance {
+            vault_id: vault_id.clone(),
+            amount: 500_000,
+            source: vault_registry::TopUpSource::LendTokens,
+        })
+        .dispatch(origin_of(account_of(USER))));
+
+        // 1_000_000 DOT backing 100_000 issued tokens at a rate of 8 is a collateralization of 125%
+        set_collateral_exchange_rate(&vault_id, FixedU128::checked_from_integer(8u128).unwrap());
+        assert!(VaultRegistryPallet::is_vault_below_secure_threshold(&vault_id).unwrap());
+
+        let lend_tokens_before = CurrencySource::<Runtime>::FreeBalance(account_of(USER))
+            .current_balance(LendToken(1))
+            .unwrap();
+        let free_collateral_before = CurrencySource::<Runtime>::FreeBalance(account_of(USER))
+            .current_balance(Token(DOT))
+            .unwrap();
+
+        assert_ok!(RuntimeCall::VaultRegistry(VaultRegistryCall::top_up_vault {
+            vault_id: vault_id.clone(),
+            funder: account_of(USER),
+        })
+        .dispatch(origin_of(account_of(CAROL))));
+
+        // the secure threshold of 150% requires 1_200_000 DOT
+        let top_up = Amount::new(200_000, Token(DOT));
+        assert_eq!(
+            VaultRegistryPallet::get_backing_collateral(&vault_id).unwrap(),
+            default_vault_backing_collateral(Token(DOT)) + top_up
+        );
+        assert_eq!(get_nominator_collateral(&vault_id, account_of(USER)), top_up);
+        assert!(!VaultRegistryPallet::is_vault_below_secure_threshold(&vault_id).unwrap());
+
+        // the collateral was redeemed from the market rather than taken from the free balance
+        assert!(CurrencySource::<Runtime>::FreeBalance(account_of(USER))
+            .current_balance(LendToken(1))
+            .unwrap()
+            .lt(&lend_tokens_before)
+            .unwrap());
+        assert_eq!(
+            CurrencySource::<Runtime>::FreeBalance(account_of(USER))
+                .current_balance(Token(DOT))
+                .unwrap(),
+            free_collateral_before
+        );
+        VaultRegistryPallet::collateral_integrity_check();
+    });
+}